@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use mongodb::bson::oid::ObjectId;
+
+use crate::{
+    routes::{site::site_content, traits::CustomerRepository},
+    utils::site::{build_site, write_dir, write_zip, Theme},
+    Container,
+};
+
+const USAGE: &str = "\
+Usage: portfolio-cms [COMMAND]
+
+Without a command the API server starts.
+
+Commands:
+  build-site --customer <_id or email> --base-url <url> [--out <dir or file.zip>]
+             [--theme <dir>] [--locale <locale>]
+      Build the portfolio of a customer into a static site, `--out` defaults to `site`.
+      The theme defaults to SITE_THEME_DIR, or the built-in one.
+  help
+      Print this message.";
+
+/// Run a command of the command line, `args` without the program name.
+pub async fn run(args: &[String], container: &Container) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("build-site") => build_site_command(&args[1..], container).await,
+        Some("help" | "--help" | "-h") => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Some(command) => Err(format!("Unknown command {command}\n\n{USAGE}")),
+        None => Err(USAGE.to_string()),
+    }
+}
+
+async fn build_site_command(args: &[String], container: &Container) -> Result<(), String> {
+    let mut customer = None;
+    let mut base_url = None;
+    let mut out = PathBuf::from("site");
+    let mut theme = None;
+    let mut locale = None;
+
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("{flag} needs a value"))
+        };
+        match flag.as_str() {
+            "--customer" => customer = Some(value()?),
+            "--base-url" => base_url = Some(value()?),
+            "--out" => out = PathBuf::from(value()?),
+            "--theme" => theme = Some(PathBuf::from(value()?)),
+            "--locale" => locale = Some(value()?),
+            _ => return Err(format!("Unknown option {flag}\n\n{USAGE}")),
+        }
+    }
+    let customer = customer.ok_or_else(|| format!("--customer is required\n\n{USAGE}"))?;
+    let base_url = base_url.ok_or_else(|| format!("--base-url is required\n\n{USAGE}"))?;
+
+    let customer_repo = container
+        .get::<Arc<dyn CustomerRepository + Send + Sync>>()
+        .ok_or_else(|| "Service not found".to_string())?;
+    let found = match ObjectId::parse_str(&customer) {
+        Ok(oid) => customer_repo.find_customer_by_id(oid).await,
+        Err(_) => customer_repo.find_customer_by_email(&customer).await,
+    }
+    .map_err(|error| error.to_string())?
+    .ok_or_else(|| format!("Customer not found: {customer}"))?;
+
+    let theme = match theme {
+        Some(dir) => Theme::from_dir(&dir)?,
+        None => Theme::from_env()?,
+    };
+
+    let (content, media) = site_content(container, found, &base_url, locale.as_deref()).await?;
+    let files = build_site(&theme, &content, media)?;
+
+    if out.extension().and_then(|extension| extension.to_str()) == Some("zip") {
+        std::fs::write(&out, write_zip(&files)?)
+            .map_err(|error| format!("{}: {error}", out.display()))?;
+    } else {
+        write_dir(&files, Path::new(&out))?;
+    }
+    println!("Wrote {} files to {}", files.len(), out.display());
+
+    Ok(())
+}
@@ -0,0 +1,464 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::models::archive::{
+    ImportMode, ImportPlan, ImportReport, PortfolioArchive, SectionDiff, SectionPlan,
+    ARCHIVE_FORMAT, ARCHIVE_VERSION,
+};
+use crate::routes::traits::ArchiveRepository;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, Bson, Document},
+    options::FindOptions,
+    Database,
+};
+use serde_json::Value;
+
+// A collection holding a section of the portfolio.
+struct Section {
+    collection: &'static str,
+    // unique per customer, matches a document of another instance to an existing one
+    natural_key: Option<&'static str>,
+}
+
+// Referenced sections come first, the order does not matter otherwise.
+const SECTIONS: [Section; 12] = [
+    Section {
+        collection: "media",
+        natural_key: None,
+    },
+    Section {
+        collection: "skill",
+        natural_key: None,
+    },
+    Section {
+        collection: "project",
+        natural_key: None,
+    },
+    Section {
+        collection: "experience",
+        natural_key: None,
+    },
+    Section {
+        collection: "education",
+        natural_key: None,
+    },
+    Section {
+        collection: "certification",
+        natural_key: None,
+    },
+    Section {
+        collection: "service",
+        natural_key: None,
+    },
+    Section {
+        collection: "social",
+        natural_key: None,
+    },
+    Section {
+        collection: "testimonial",
+        natural_key: None,
+    },
+    Section {
+        collection: "article",
+        natural_key: Some("slug"),
+    },
+    Section {
+        collection: "custom_collection",
+        natural_key: Some("slug"),
+    },
+    Section {
+        collection: "custom_entry",
+        natural_key: None,
+    },
+];
+
+// Customer fields that are not part of the profile.
+const PRIVATE_FIELDS: [&str; 7] = [
+    "_id",
+    "api_key",
+    "cv_share_token",
+    "password",
+    "email",
+    "createdAt",
+    "updatedAt",
+];
+
+// Fields left out when telling whether a document changed.
+const TIMESTAMP_FIELDS: [&str; 4] = ["created_at", "updated_at", "createdAt", "updatedAt"];
+
+pub struct ArchiveRepo {
+    pub db: Database,
+}
+
+impl ArchiveRepo {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    async fn find_section(
+        &self,
+        collection: &str,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Document>> {
+        let find_options = FindOptions::builder()
+            .sort(doc! { "order": 1, "_id": 1 })
+            .build();
+
+        self.db
+            .collection::<Document>(collection)
+            .find(doc! { "customer_id": oid }, find_options)
+            .await?
+            .try_collect()
+            .await
+    }
+}
+
+#[async_trait]
+impl ArchiveRepository for ArchiveRepo {
+    async fn export(&self, oid: ObjectId) -> mongodb::error::Result<Option<PortfolioArchive>> {
+        let Some(mut profile) = self
+            .db
+            .collection::<Document>("customer")
+            .find_one(doc! { "_id": oid }, None)
+            .await?
+        else {
+            return Ok(None);
+        };
+        strip_private_fields(&mut profile);
+
+        let mut sections = std::collections::BTreeMap::new();
+        for section in &SECTIONS {
+            let documents = self
+                .find_section(section.collection, oid)
+                .await?
+                .into_iter()
+                .map(|mut document| {
+                    document.remove("customer_id");
+                    Bson::Document(document).into_relaxed_extjson()
+                })
+                .collect();
+            sections.insert(section.collection.to_string(), documents);
+        }
+
+        let Value::Object(profile) = Bson::Document(profile).into_relaxed_extjson() else {
+            unreachable!("a document is a JSON object")
+        };
+
+        Ok(Some(PortfolioArchive {
+            format: ARCHIVE_FORMAT.to_string(),
+            version: ARCHIVE_VERSION,
+            exported_at: Utc::now().to_rfc3339(),
+            customer_id: oid.to_string(),
+            profile,
+            sections,
+        }))
+    }
+
+    async fn plan_import(
+        &self,
+        oid: ObjectId,
+        archive: PortfolioArchive,
+        version: u32,
+        mode: ImportMode,
+    ) -> mongodb::error::Result<ImportPlan> {
+        if let Some(section) = archive
+            .sections
+            .keys()
+            .find(|name| !SECTIONS.iter().any(|known| known.collection == *name))
+        {
+            return Err(invalid(format!("Unknown section {section}")));
+        }
+
+        // Ids of the archive mapped to the ids they get in this portfolio.
+        let mut ids: HashMap<ObjectId, ObjectId> = HashMap::new();
+        if let Ok(source) = ObjectId::parse_str(&archive.customer_id) {
+            ids.insert(source, oid);
+        }
+
+        let mut remapped = 0;
+        let mut sections = vec![];
+        for section in &SECTIONS {
+            let existing = self.find_section(section.collection, oid).await?;
+            let existing_ids: HashSet<ObjectId> = existing
+                .iter()
+                .filter_map(|document| document.get_object_id("_id").ok())
+                .collect();
+
+            let mut documents = vec![];
+            let mut claimed = HashSet::new();
+            for value in archive
+                .sections
+                .get(section.collection)
+                .into_iter()
+                .flatten()
+            {
+                let document = to_document(value.clone())
+                    .map_err(|error| invalid(format!("{}: {error}", section.collection)))?;
+                let Ok(source) = document.get_object_id("_id") else {
+                    return Err(invalid(format!(
+                        "{}: a document has no _id",
+                        section.collection
+                    )));
+                };
+
+                let by_natural_key = || {
+                    let key = section.natural_key?;
+                    let value = document.get(key)?;
+                    existing
+                        .iter()
+                        .find(|known| known.get(key) == Some(value))
+                        .and_then(|known| known.get_object_id("_id").ok())
+                };
+                let target = if existing_ids.contains(&source) {
+                    source
+                } else if let Some(known) = by_natural_key().filter(|id| !claimed.contains(id)) {
+                    known
+                } else {
+                    ObjectId::new()
+                };
+                if !claimed.insert(target) {
+                    return Err(invalid(format!(
+                        "{}: _id {source} appears twice",
+                        section.collection
+                    )));
+                }
+                if target != source {
+                    remapped += 1;
+                }
+                ids.insert(source, target);
+                documents.push((target, document));
+            }
+            sections.push((section, existing, documents));
+        }
+
+        // Hex ids inside strings, e.g. media keys and links.
+        let replacements: Vec<(String, String)> = ids
+            .iter()
+            .filter(|(source, target)| source != target)
+            .map(|(source, target)| (source.to_hex(), target.to_hex()))
+            .collect();
+        let remap =
+            |document: Document| match remap_ids(Bson::Document(document), &ids, &replacements) {
+                Bson::Document(document) => document,
+                _ => unreachable!("a document stays a document"),
+            };
+
+        let kept: HashSet<ObjectId> = ids.values().copied().collect();
+        let mut diffs = vec![];
+        let mut plans = vec![];
+        let mut media_files = vec![];
+        let mut deleted_files = vec![];
+        for (section, existing, documents) in sections {
+            let mut diff = SectionDiff {
+                section: section.collection.to_string(),
+                ..Default::default()
+            };
+            let mut plan = SectionPlan {
+                collection: section.collection,
+                documents: vec![],
+                deleted: vec![],
+            };
+
+            for (target, document) in documents {
+                let archive_keys = media_keys(&document);
+                let mut document = remap(document);
+                document.insert("_id", target);
+                document.insert("customer_id", oid);
+
+                if section.collection == "media" {
+                    media_files.extend(archive_keys.into_iter().zip(media_keys(&document)));
+                }
+
+                match existing
+                    .iter()
+                    .find(|known| known.get_object_id("_id").ok() == Some(target))
+                {
+                    Some(known) if same_content(known, &document) => diff.unchanged += 1,
+                    Some(_) => {
+                        diff.updated += 1;
+                        plan.documents.push(document);
+                    }
+                    None => {
+                        diff.created += 1;
+                        plan.documents.push(document);
+                    }
+                }
+            }
+
+            if mode == ImportMode::Replace {
+                for known in &existing {
+                    let Ok(id) = known.get_object_id("_id") else {
+                        continue;
+                    };
+                    if kept.contains(&id) {
+                        continue;
+                    }
+                    diff.deleted += 1;
+                    plan.deleted.push(id);
+                    if section.collection == "media" {
+                        deleted_files.extend(media_keys(known));
+                    }
+                }
+            }
+
+            diffs.push(diff);
+            plans.push(plan);
+        }
+
+        let customer = self
+            .db
+            .collection::<Document>("customer")
+            .find_one(doc! { "_id": oid }, None)
+            .await?
+            .unwrap_or_default();
+        let mut profile = remap(
+            to_document(Value::Object(archive.profile))
+                .map_err(|error| invalid(format!("profile: {error}")))?,
+        );
+        strip_private_fields(&mut profile);
+        if profile.iter().all(|(field, value)| {
+            customer
+                .get(field)
+                .is_some_and(|known| same_value(known, value))
+        }) {
+            profile.clear();
+        }
+
+        Ok(ImportPlan {
+            report: ImportReport {
+                mode,
+                dry_run: true,
+                version,
+                profile_updated: !profile.is_empty(),
+                sections: diffs,
+                remapped,
+                media_files: 0,
+            },
+            profile,
+            sections: plans,
+            media_files,
+            deleted_files,
+        })
+    }
+
+    async fn apply_import(&self, oid: ObjectId, plan: &ImportPlan) -> mongodb::error::Result<()> {
+        if !plan.profile.is_empty() {
+            let mut profile = plan.profile.clone();
+            profile.insert("updatedAt", mongodb::bson::DateTime::now());
+            self.db
+                .collection::<Document>("customer")
+                .update_one(doc! { "_id": oid }, doc! { "$set": profile }, None)
+                .await?;
+        }
+
+        for section in &plan.sections {
+            let collection = self.db.collection::<Document>(section.collection);
+            if !section.deleted.is_empty() {
+                collection
+                    .delete_many(
+                        doc! { "_id": { "$in": &section.deleted }, "customer_id": oid },
+                        None,
+                    )
+                    .await?;
+            }
+            for document in &section.documents {
+                let options = mongodb::options::ReplaceOptions::builder()
+                    .upsert(true)
+                    .build();
+                collection
+                    .replace_one(doc! { "_id": document.get("_id") }, document, options)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Remove the customer fields that are not part of the profile, e.g. the keys and the CV share
+/// token, which must not travel to another portfolio.
+pub fn strip_private_fields(profile: &mut Document) {
+    for field in PRIVATE_FIELDS {
+        profile.remove(field);
+    }
+}
+
+fn invalid(message: String) -> mongodb::error::Error {
+    mongodb::error::Error::custom(message)
+}
+
+fn to_document(value: Value) -> Result<Document, String> {
+    match Bson::try_from(value).map_err(|error| error.to_string())? {
+        Bson::Document(document) => Ok(document),
+        _ => Err("expected an object".to_string()),
+    }
+}
+
+/// Swap the ids of the archive for the ids of this portfolio, in values and inside strings.
+pub fn remap_ids(
+    value: Bson,
+    ids: &HashMap<ObjectId, ObjectId>,
+    replacements: &[(String, String)],
+) -> Bson {
+    match value {
+        Bson::ObjectId(id) => Bson::ObjectId(ids.get(&id).copied().unwrap_or(id)),
+        Bson::String(mut text) => {
+            for (source, target) in replacements {
+                if text.contains(source.as_str()) {
+                    text = text.replace(source.as_str(), target);
+                }
+            }
+            Bson::String(text)
+        }
+        Bson::Array(items) => Bson::Array(
+            items
+                .into_iter()
+                .map(|item| remap_ids(item, ids, replacements))
+                .collect(),
+        ),
+        Bson::Document(document) => Bson::Document(
+            document
+                .into_iter()
+                .map(|(field, value)| (field, remap_ids(value, ids, replacements)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+// Media store keys of a media document, the file and its variants.
+fn media_keys(document: &Document) -> Vec<String> {
+    let variants = document
+        .get_array("variants")
+        .map(|variants| variants.iter().filter_map(Bson::as_document).collect())
+        .unwrap_or_else(|_| vec![]);
+
+    document
+        .get_str("key")
+        .into_iter()
+        .chain(
+            variants
+                .into_iter()
+                .filter_map(|variant| variant.get_str("key").ok()),
+        )
+        .map(str::to_string)
+        .collect()
+}
+
+fn same_content(known: &Document, document: &Document) -> bool {
+    let strip = |document: &Document| {
+        let mut document = document.clone();
+        for field in TIMESTAMP_FIELDS {
+            document.remove(field);
+        }
+        Bson::Document(document)
+    };
+
+    same_value(&strip(known), &strip(document))
+}
+
+// Compared as JSON, a number read back from an archive may not keep its BSON type.
+fn same_value(known: &Bson, value: &Bson) -> bool {
+    known.clone().into_relaxed_extjson() == value.clone().into_relaxed_extjson()
+}
@@ -0,0 +1,283 @@
+use crate::db::{media::find_media, owner_of};
+use crate::models::article::{Article, ArticleDocument, ArticleInput};
+use crate::routes::traits::ArticleRepository;
+use crate::utils::markdown::{reading_time, render_markdown, slugify};
+use crate::utils::util::parse_date;
+use chrono::{DateTime, Utc};
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, to_bson, Document},
+    options::{FindOneAndUpdateOptions, FindOptions, IndexOptions, ReturnDocument},
+    Database, IndexModel,
+};
+use rocket::serde::json::Json;
+
+pub struct ArticleRepo {
+    pub db: Database,
+}
+
+impl ArticleRepo {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+/// Slugs are unique per customer, a duplicate is rejected by the index rather than a lookup
+/// which would race with a concurrent write.
+pub async fn ensure_indexes(db: &Database) -> mongodb::error::Result<()> {
+    db.collection::<Document>("article")
+        .create_index(
+            IndexModel::builder()
+                .keys(doc! { "customer_id": 1, "slug": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+            None,
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl ArticleRepository for ArticleRepo {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        published_only: bool,
+    ) -> mongodb::error::Result<Vec<Article>> {
+        let collection = self.db.collection::<ArticleDocument>("article");
+
+        let filter = filter_document(oid, published_only);
+
+        // Most recently published first, drafts last.
+        let find_options = FindOptions::builder()
+            .sort(doc! { "published_at": -1, "created_at": -1 })
+            .limit(limit)
+            .skip(u64::try_from((page - 1) * limit).unwrap())
+            .build();
+
+        let mut cursor = collection.find(filter, find_options).await?;
+
+        let mut resp: Vec<Article> = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            resp.push(to_article(result));
+        }
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Article>> {
+        let collection = self.db.collection::<ArticleDocument>("article");
+
+        let resp = collection
+            .find_one(doc! {"_id":oid }, None)
+            .await?
+            .map(to_article);
+
+        Ok(resp)
+    }
+
+    async fn find_by_slug(
+        &self,
+        oid: ObjectId,
+        slug: String,
+        published_only: bool,
+    ) -> mongodb::error::Result<Option<Article>> {
+        let collection = self.db.collection::<ArticleDocument>("article");
+
+        let mut filter = filter_document(oid, published_only);
+        filter.insert("slug", slug);
+
+        let resp = collection.find_one(filter, None).await?.map(to_article);
+
+        Ok(resp)
+    }
+
+    async fn insert(
+        &self,
+        input: Json<ArticleInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let collection = self.db.collection::<Document>("article");
+
+        let created_at = mongodb::bson::DateTime::from_chrono(Utc::now());
+        let published_at = parse_date(input.published_at.as_deref())?;
+        let cover_id = input
+            .cover_id
+            .as_deref()
+            .and_then(|id| ObjectId::parse_str(id).ok());
+        let media = find_media(&self.db, oid, cover_id).await?;
+        let cover_link = media
+            .as_ref()
+            .map(|media| media.url.clone())
+            .or_else(|| input.cover_link.clone());
+        let cover_srcset = media.as_ref().and_then(|media| media.srcset.clone());
+        let cover_blurhash = media.and_then(|media| media.blurhash);
+
+        let resp = collection
+            .insert_one(
+                doc! {
+                    "customer_id": oid,
+                    "title": &input.title,
+                    "slug": article_slug(&input),
+                    "body": &input.body,
+                    "html": render_markdown(&input.body),
+                    "excerpt": &input.excerpt,
+                    "tags": &input.tags,
+                    "cover_link": &cover_link,
+                    "cover_id": cover_id,
+                    "cover_srcset": &cover_srcset,
+                    "cover_blurhash": &cover_blurhash,
+                    "reading_time": reading_time(&input.body),
+                    "published_at": published_at,
+                    "seo": to_bson(&input.seo)?,
+                    "created_at": created_at,
+                    "updated_at": created_at,
+                },
+                None,
+            )
+            .await?;
+
+        Ok(resp.inserted_id.to_string())
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<ArticleInput>,
+    ) -> mongodb::error::Result<Option<Article>> {
+        let collection = self.db.collection::<ArticleDocument>("article");
+        let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let Some(customer_oid) = owner_of(&self.db, "article", oid).await? else {
+            return Ok(None);
+        };
+        let published_at = parse_date(input.published_at.as_deref())?;
+        let cover_id = input
+            .cover_id
+            .as_deref()
+            .and_then(|id| ObjectId::parse_str(id).ok());
+        let media = find_media(&self.db, customer_oid, cover_id).await?;
+        let cover_link = media
+            .as_ref()
+            .map(|media| media.url.clone())
+            .or_else(|| input.cover_link.clone());
+        let cover_srcset = media.as_ref().and_then(|media| media.srcset.clone());
+        let cover_blurhash = media.and_then(|media| media.blurhash);
+
+        let Some(result) = collection
+            .find_one_and_update(
+                doc! {"_id":oid },
+                doc! {
+                    "$set": {
+                        "title": &input.title,
+                        "slug": article_slug(&input),
+                        "body": &input.body,
+                        "html": render_markdown(&input.body),
+                        "excerpt": &input.excerpt,
+                        "tags": &input.tags,
+                        "cover_link": &cover_link,
+                        "cover_id": cover_id,
+                        "cover_srcset": &cover_srcset,
+                        "cover_blurhash": &cover_blurhash,
+                        "reading_time": reading_time(&input.body),
+                        "published_at": published_at,
+                        "seo": to_bson(&input.seo)?,
+                        "updated_at": mongodb::bson::DateTime::now(),
+                    }
+                },
+                find_one_and_update_options,
+            )
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(to_article(result)))
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Article>> {
+        let collection = self.db.collection::<ArticleDocument>("article");
+
+        let resp = collection
+            .find_one_and_delete(doc! {"_id":oid }, None)
+            .await?
+            .map(to_article);
+
+        Ok(resp)
+    }
+}
+
+/// Articles scheduled ahead whose `published_at` fell in `(from, to]`. Articles saved with a date
+/// already past are left out, they were public as soon as they were saved.
+pub async fn find_due_between(
+    db: &Database,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> mongodb::error::Result<Vec<Article>> {
+    let collection = db.collection::<ArticleDocument>("article");
+
+    let filter = doc! {
+        "published_at": {
+            "$gt": mongodb::bson::DateTime::from_chrono(from),
+            "$lte": mongodb::bson::DateTime::from_chrono(to),
+        },
+        "$expr": { "$lt": [{ "$ifNull": ["$updated_at", "$created_at"] }, "$published_at"] },
+    };
+
+    let mut cursor = collection.find(filter, None).await?;
+
+    let mut resp: Vec<Article> = vec![];
+    while let Some(result) = cursor.try_next().await? {
+        resp.push(to_article(result));
+    }
+
+    Ok(resp)
+}
+
+/// Slug of an article input, generated from the title when none is given.
+pub fn article_slug(input: &ArticleInput) -> String {
+    match input.slug.as_deref().map(slugify) {
+        Some(slug) if !slug.is_empty() => slug,
+        _ => slugify(&input.title),
+    }
+}
+
+fn filter_document(oid: ObjectId, published_only: bool) -> Document {
+    let mut document = doc! { "customer_id": oid };
+    if published_only {
+        document.insert(
+            "published_at",
+            doc! { "$lte": mongodb::bson::DateTime::now() },
+        );
+    }
+
+    document
+}
+
+// transform ObjectId to String
+fn to_article(result: ArticleDocument) -> Article {
+    Article {
+        id: result.id.to_string(),
+        customer_id: result.customer_id.to_string(),
+        title: result.title,
+        slug: result.slug,
+        body: result.body,
+        html: result.html,
+        excerpt: result.excerpt,
+        tags: result.tags,
+        cover_link: result.cover_link,
+        cover_id: result.cover_id.map(|id| id.to_string()),
+        cover_srcset: result.cover_srcset,
+        cover_blurhash: result.cover_blurhash,
+        reading_time: result.reading_time,
+        published_at: result.published_at.map(|d| d.to_string()),
+        seo: result.seo,
+        created_at: result.created_at.to_string(),
+        updated_at: result.updated_at.map(|d| d.to_string()),
+    }
+}
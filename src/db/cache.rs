@@ -0,0 +1,1133 @@
+use crate::models::{
+    article::{Article, ArticleInput},
+    cache::CacheStats,
+    certification::{Certification, CertificationInput, CertificationsInput},
+    collection::{
+        CustomCollection, CustomCollectionInput, CustomEntriesInput, CustomEntry, CustomEntryInput,
+    },
+    customer::{Customer, CustomerInput, CustomerUpdateInput},
+    education::{Education, EducationInput, EducationsInput},
+    experience::{Experience, ExperienceInput, ExperiencesInput},
+    project::{Project, ProjectFilter, ProjectInput, ProjectsInput},
+    service::{Service, ServiceInput, ServicesInput},
+    skill::{Skill, SkillInput, SkillsInput},
+    social::{Social, SocialInput, SocialsInput},
+    testimonial::{
+        Testimonial, TestimonialInput, TestimonialStatus, TestimonialSubmission, TestimonialsInput,
+    },
+};
+use crate::routes::traits::{
+    ArticleRepository, CertificationRepository, CustomCollectionRepository, CustomEntryRepository,
+    CustomerRepository, EducationRepository, ExperienceRepository, ProjectRepository,
+    ServiceRepository, SkillRepository, SocialRepository, TestimonialRepository,
+};
+use mongodb::bson::oid::ObjectId;
+use rocket::serde::json::Json;
+use serde_json::{Map, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::env;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+// (customer, section, variant) - the variant tells apart pages of the same section.
+type CacheKey = (String, &'static str, String);
+
+struct CacheEntry {
+    value: Box<dyn Any + Send + Sync>,
+    expires_at: Instant,
+}
+
+/// In-memory store for content read through the repositories.
+pub struct ContentCache {
+    entries: Mutex<HashMap<CacheKey, CacheEntry>>,
+    ttl: Duration,
+    max_entries: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ContentCache {
+    pub fn new() -> Self {
+        let ttl = env::var("CONTENT_CACHE_TTL")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(30);
+        let max_entries = env::var("CONTENT_CACHE_MAX_ENTRIES")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(1000);
+
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            ttl: Duration::from_secs(ttl),
+            max_entries,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<CacheKey, CacheEntry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn get<T: Clone + 'static>(
+        &self,
+        customer: &str,
+        section: &'static str,
+        variant: &str,
+    ) -> Option<T> {
+        let key = (customer.to_string(), section, variant.to_string());
+        let mut entries = self.entries();
+
+        let value = match entries.get(&key) {
+            Some(entry) if entry.expires_at > Instant::now() => {
+                entry.value.downcast_ref::<T>().cloned()
+            }
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        };
+
+        match value {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+
+        value
+    }
+
+    pub fn insert<T: Send + Sync + 'static>(
+        &self,
+        customer: &str,
+        section: &'static str,
+        variant: &str,
+        value: T,
+    ) {
+        if self.ttl.is_zero() || self.max_entries == 0 {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut entries = self.entries();
+
+        if entries.len() >= self.max_entries {
+            entries.retain(|_, entry| entry.expires_at > now);
+        }
+        if entries.len() >= self.max_entries {
+            // Still full: drop the entry closest to expiring, i.e. the oldest one.
+            if let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.expires_at)
+                .map(|(key, _)| key.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+
+        entries.insert(
+            (customer.to_string(), section, variant.to_string()),
+            CacheEntry {
+                value: Box::new(value),
+                expires_at: now + self.ttl,
+            },
+        );
+    }
+
+    pub fn invalidate(&self, customer: &str, section: &'static str) {
+        self.entries().retain(|(key_customer, key_section, _), _| {
+            key_customer != customer || *key_section != section
+        });
+    }
+
+    /// Drop every section of a customer, e.g. after an import.
+    pub fn invalidate_customer(&self, customer: &str) {
+        self.entries()
+            .retain(|(key_customer, _, _), _| key_customer != customer);
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries().len() as u64,
+            max_entries: self.max_entries as u64,
+            ttl: self.ttl.as_secs(),
+        }
+    }
+}
+
+impl Default for ContentCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Repository decorator that reads through the [`ContentCache`] and
+/// invalidates the customer's section on every write.
+pub struct CachedRepo<R> {
+    inner: R,
+    cache: Arc<ContentCache>,
+}
+
+impl<R> CachedRepo<R> {
+    pub fn new(inner: R, cache: Arc<ContentCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl<R: ArticleRepository + Send + Sync> ArticleRepository for CachedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        published_only: bool,
+    ) -> mongodb::error::Result<Vec<Article>> {
+        let customer = oid.to_string();
+        let variant = format!("{limit}:{page}:{published_only}");
+        if let Some(resp) = self.cache.get(&customer, "article", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find(limit, page, oid, published_only).await?;
+        self.cache
+            .insert(&customer, "article", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Article>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn find_by_slug(
+        &self,
+        oid: ObjectId,
+        slug: String,
+        published_only: bool,
+    ) -> mongodb::error::Result<Option<Article>> {
+        let customer = oid.to_string();
+        let variant = format!("slug:{slug}:{published_only}");
+        if let Some(resp) = self.cache.get(&customer, "article", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find_by_slug(oid, slug, published_only).await?;
+        self.cache
+            .insert(&customer, "article", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn insert(
+        &self,
+        input: Json<ArticleInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        self.cache.invalidate(&oid.to_string(), "article");
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<ArticleInput>,
+    ) -> mongodb::error::Result<Option<Article>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(article) = &resp {
+            self.cache.invalidate(&article.customer_id, "article");
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Article>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(article) = &resp {
+            self.cache.invalidate(&article.customer_id, "article");
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: CertificationRepository + Send + Sync> CertificationRepository for CachedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        include_expired: bool,
+    ) -> mongodb::error::Result<Vec<Certification>> {
+        let customer = oid.to_string();
+        let variant = format!("{limit}:{page}:{include_expired}");
+        if let Some(resp) = self.cache.get(&customer, "certification", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find(limit, page, oid, include_expired).await?;
+        self.cache
+            .insert(&customer, "certification", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Certification>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<CertificationInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        self.cache.invalidate(&oid.to_string(), "certification");
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<CertificationInput>,
+    ) -> mongodb::error::Result<Option<Certification>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(certification) = &resp {
+            self.cache
+                .invalidate(&certification.customer_id, "certification");
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<CertificationsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Certification>>> {
+        let resp = self.inner.update_many(customer_oid, input).await?;
+        for certification in resp.iter().flatten() {
+            self.cache
+                .invalidate(&certification.customer_id, "certification");
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Certification>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(certification) = &resp {
+            self.cache
+                .invalidate(&certification.customer_id, "certification");
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: CustomerRepository + Send + Sync> CustomerRepository for CachedRepo<R> {
+    async fn find_customer_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Customer>> {
+        self.inner.find_customer_by_id(oid).await
+    }
+
+    async fn find_customer_by_api_key(
+        &self,
+        api_key: &str,
+    ) -> mongodb::error::Result<Option<Customer>> {
+        if let Some(customer) = self.cache.get::<Customer>(api_key, "customer", "") {
+            return Ok(Some(customer));
+        }
+
+        let resp = self.inner.find_customer_by_api_key(api_key).await?;
+        if let Some(customer) = &resp {
+            self.cache.insert(api_key, "customer", "", customer.clone());
+        }
+
+        Ok(resp)
+    }
+
+    async fn find_customer_by_email(
+        &self,
+        email: &str,
+    ) -> mongodb::error::Result<Option<Customer>> {
+        self.inner.find_customer_by_email(email).await
+    }
+
+    async fn insert_customer(&self, input: Json<CustomerInput>) -> mongodb::error::Result<String> {
+        self.inner.insert_customer(input).await
+    }
+
+    async fn update_customer_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<CustomerUpdateInput>,
+    ) -> mongodb::error::Result<Option<Customer>> {
+        let resp = self.inner.update_customer_by_id(oid, input).await?;
+        if let Some(customer) = &resp {
+            self.cache.invalidate(&customer.api_key, "customer");
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: CustomCollectionRepository + Send + Sync> CustomCollectionRepository for CachedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<CustomCollection>> {
+        let customer = oid.to_string();
+        let variant = format!("{limit}:{page}");
+        if let Some(resp) = self.cache.get(&customer, "collection", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find(limit, page, oid).await?;
+        self.cache
+            .insert(&customer, "collection", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<CustomCollection>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn find_by_slug(
+        &self,
+        oid: ObjectId,
+        slug: String,
+    ) -> mongodb::error::Result<Option<CustomCollection>> {
+        let customer = oid.to_string();
+        let variant = format!("slug:{slug}");
+        if let Some(resp) = self.cache.get(&customer, "collection", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find_by_slug(oid, slug).await?;
+        self.cache
+            .insert(&customer, "collection", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn insert(
+        &self,
+        input: Json<CustomCollectionInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        self.cache.invalidate(&oid.to_string(), "collection");
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<CustomCollectionInput>,
+    ) -> mongodb::error::Result<Option<CustomCollection>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(collection) = &resp {
+            self.cache.invalidate(&collection.customer_id, "collection");
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(
+        &self,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Option<CustomCollection>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(collection) = &resp {
+            self.cache.invalidate(&collection.customer_id, "collection");
+            self.cache.invalidate(&collection.customer_id, "entry");
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: CustomEntryRepository + Send + Sync> CustomEntryRepository for CachedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        collection_oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<CustomEntry>> {
+        let customer = oid.to_string();
+        let variant = format!("{collection_oid}:{limit}:{page}");
+        if let Some(resp) = self.cache.get(&customer, "entry", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find(limit, page, oid, collection_oid).await?;
+        self.cache
+            .insert(&customer, "entry", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<CustomEntry>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<CustomEntryInput>,
+        oid: ObjectId,
+        collection_oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid, collection_oid).await?;
+        self.cache.invalidate(&oid.to_string(), "entry");
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<CustomEntryInput>,
+    ) -> mongodb::error::Result<Option<CustomEntry>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(entry) = &resp {
+            self.cache.invalidate(&entry.customer_id, "entry");
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        collection_oid: ObjectId,
+        input: Json<Vec<CustomEntriesInput>>,
+    ) -> mongodb::error::Result<Option<Vec<CustomEntry>>> {
+        let resp = self.inner.update_many(collection_oid, input).await?;
+        for entry in resp.iter().flatten() {
+            self.cache.invalidate(&entry.customer_id, "entry");
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<CustomEntry>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(entry) = &resp {
+            self.cache.invalidate(&entry.customer_id, "entry");
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: EducationRepository + Send + Sync> EducationRepository for CachedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Education>> {
+        let customer = oid.to_string();
+        let variant = format!("{limit}:{page}");
+        if let Some(resp) = self.cache.get(&customer, "education", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find(limit, page, oid).await?;
+        self.cache
+            .insert(&customer, "education", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Education>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<EducationInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        self.cache.invalidate(&oid.to_string(), "education");
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<EducationInput>,
+    ) -> mongodb::error::Result<Option<Education>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(education) = &resp {
+            self.cache.invalidate(&education.customer_id, "education");
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<EducationsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Education>>> {
+        let resp = self.inner.update_many(customer_oid, input).await?;
+        for education in resp.iter().flatten() {
+            self.cache.invalidate(&education.customer_id, "education");
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Education>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(education) = &resp {
+            self.cache.invalidate(&education.customer_id, "education");
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: ExperienceRepository + Send + Sync> ExperienceRepository for CachedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Experience>> {
+        let customer = oid.to_string();
+        let variant = format!("{limit}:{page}");
+        if let Some(resp) = self.cache.get(&customer, "experience", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find(limit, page, oid).await?;
+        self.cache
+            .insert(&customer, "experience", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn find_fields(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        fields: Vec<String>,
+    ) -> mongodb::error::Result<Vec<Map<String, Value>>> {
+        let customer = oid.to_string();
+        let variant = format!("{limit}:{page}:{}", fields.join(","));
+        if let Some(resp) = self.cache.get(&customer, "experience", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find_fields(limit, page, oid, fields).await?;
+        self.cache
+            .insert(&customer, "experience", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Experience>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn find_by_skill(
+        &self,
+        oid: ObjectId,
+        skill_oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Experience>> {
+        let customer = oid.to_string();
+        let variant = format!("skill:{skill_oid}");
+        if let Some(resp) = self.cache.get(&customer, "experience", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find_by_skill(oid, skill_oid).await?;
+        self.cache
+            .insert(&customer, "experience", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn insert(
+        &self,
+        input: Json<ExperienceInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        self.cache.invalidate(&oid.to_string(), "experience");
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<ExperienceInput>,
+    ) -> mongodb::error::Result<Option<Experience>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(experience) = &resp {
+            self.cache.invalidate(&experience.customer_id, "experience");
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        input: Json<Vec<ExperiencesInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Experience>>> {
+        let resp = self.inner.update_many(input).await?;
+        for experience in resp.iter().flatten() {
+            self.cache.invalidate(&experience.customer_id, "experience");
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Experience>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(experience) = &resp {
+            self.cache.invalidate(&experience.customer_id, "experience");
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: ProjectRepository + Send + Sync> ProjectRepository for CachedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        filter: ProjectFilter,
+    ) -> mongodb::error::Result<Vec<Project>> {
+        let customer = oid.to_string();
+        let variant = format!(
+            "{limit}:{page}:{:?}:{}:{:?}",
+            filter.featured,
+            filter.stack.join(","),
+            filter.skill_id
+        );
+        if let Some(resp) = self.cache.get(&customer, "project", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find(limit, page, oid, filter).await?;
+        self.cache
+            .insert(&customer, "project", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn find_fields(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        fields: Vec<String>,
+        filter: ProjectFilter,
+    ) -> mongodb::error::Result<Vec<Map<String, Value>>> {
+        let customer = oid.to_string();
+        let variant = format!(
+            "{limit}:{page}:{}:{:?}:{}:{:?}",
+            fields.join(","),
+            filter.featured,
+            filter.stack.join(","),
+            filter.skill_id
+        );
+        if let Some(resp) = self.cache.get(&customer, "project", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self
+            .inner
+            .find_fields(limit, page, oid, fields, filter)
+            .await?;
+        self.cache
+            .insert(&customer, "project", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Project>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<ProjectInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        self.cache.invalidate(&oid.to_string(), "project");
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<ProjectInput>,
+    ) -> mongodb::error::Result<Option<Project>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(project) = &resp {
+            self.cache.invalidate(&project.customer_id, "project");
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        input: Json<Vec<ProjectsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Project>>> {
+        let resp = self.inner.update_many(input).await?;
+        for project in resp.iter().flatten() {
+            self.cache.invalidate(&project.customer_id, "project");
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Project>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(project) = &resp {
+            self.cache.invalidate(&project.customer_id, "project");
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: ServiceRepository + Send + Sync> ServiceRepository for CachedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Service>> {
+        let customer = oid.to_string();
+        let variant = format!("{limit}:{page}");
+        if let Some(resp) = self.cache.get(&customer, "service", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find(limit, page, oid).await?;
+        self.cache
+            .insert(&customer, "service", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Service>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<ServiceInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        self.cache.invalidate(&oid.to_string(), "service");
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<ServiceInput>,
+    ) -> mongodb::error::Result<Option<Service>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(service) = &resp {
+            self.cache.invalidate(&service.customer_id, "service");
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<ServicesInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Service>>> {
+        let resp = self.inner.update_many(customer_oid, input).await?;
+        for service in resp.iter().flatten() {
+            self.cache.invalidate(&service.customer_id, "service");
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Service>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(service) = &resp {
+            self.cache.invalidate(&service.customer_id, "service");
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: SkillRepository + Send + Sync> SkillRepository for CachedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Skill>> {
+        let customer = oid.to_string();
+        let variant = format!("{limit}:{page}");
+        if let Some(resp) = self.cache.get(&customer, "skill", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find(limit, page, oid).await?;
+        self.cache
+            .insert(&customer, "skill", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Skill>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<SkillInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        self.cache.invalidate(&oid.to_string(), "skill");
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<SkillInput>,
+    ) -> mongodb::error::Result<Option<Skill>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(skill) = &resp {
+            self.cache.invalidate(&skill.customer_id, "skill");
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        input: Json<Vec<SkillsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Skill>>> {
+        let resp = self.inner.update_many(input).await?;
+        for skill in resp.iter().flatten() {
+            self.cache.invalidate(&skill.customer_id, "skill");
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Skill>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(skill) = &resp {
+            self.cache.invalidate(&skill.customer_id, "skill");
+            // Deleting a skill may have removed it from projects and experiences.
+            self.cache.invalidate(&skill.customer_id, "project");
+            self.cache.invalidate(&skill.customer_id, "experience");
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: SocialRepository + Send + Sync> SocialRepository for CachedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Social>> {
+        let customer = oid.to_string();
+        let variant = format!("{limit}:{page}");
+        if let Some(resp) = self.cache.get(&customer, "social", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find(limit, page, oid).await?;
+        self.cache
+            .insert(&customer, "social", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Social>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<SocialInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        self.cache.invalidate(&oid.to_string(), "social");
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<SocialInput>,
+    ) -> mongodb::error::Result<Option<Social>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(social) = &resp {
+            self.cache.invalidate(&social.customer_id, "social");
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        input: Json<Vec<SocialsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Social>>> {
+        let resp = self.inner.update_many(input).await?;
+        for social in resp.iter().flatten() {
+            self.cache.invalidate(&social.customer_id, "social");
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Social>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(social) = &resp {
+            self.cache.invalidate(&social.customer_id, "social");
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: TestimonialRepository + Send + Sync> TestimonialRepository for CachedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        status: Option<TestimonialStatus>,
+    ) -> mongodb::error::Result<Vec<Testimonial>> {
+        let customer = oid.to_string();
+        let variant = format!("{limit}:{page}:{status:?}");
+        if let Some(resp) = self.cache.get(&customer, "testimonial", &variant) {
+            return Ok(resp);
+        }
+
+        let resp = self.inner.find(limit, page, oid, status).await?;
+        self.cache
+            .insert(&customer, "testimonial", &variant, resp.clone());
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Testimonial>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<TestimonialSubmission>,
+        oid: ObjectId,
+        order: i32,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid, order).await?;
+        self.cache.invalidate(&oid.to_string(), "testimonial");
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<TestimonialInput>,
+    ) -> mongodb::error::Result<Option<Testimonial>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(testimonial) = &resp {
+            self.cache
+                .invalidate(&testimonial.customer_id, "testimonial");
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_status(
+        &self,
+        oid: ObjectId,
+        status: TestimonialStatus,
+    ) -> mongodb::error::Result<Option<Testimonial>> {
+        let resp = self.inner.update_status(oid, status).await?;
+        if let Some(testimonial) = &resp {
+            self.cache
+                .invalidate(&testimonial.customer_id, "testimonial");
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<TestimonialsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Testimonial>>> {
+        let resp = self.inner.update_many(customer_oid, input).await?;
+        for testimonial in resp.iter().flatten() {
+            self.cache
+                .invalidate(&testimonial.customer_id, "testimonial");
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Testimonial>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(testimonial) = &resp {
+            self.cache
+                .invalidate(&testimonial.customer_id, "testimonial");
+        }
+
+        Ok(resp)
+    }
+}
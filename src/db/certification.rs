@@ -0,0 +1,221 @@
+use crate::models::certification::{
+    Certification, CertificationDocument, CertificationInput, CertificationsInput,
+};
+use crate::routes::traits::CertificationRepository;
+use crate::utils::util::parse_date;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, Document},
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Database,
+};
+use rocket::serde::json::Json;
+
+pub struct CertificationRepo {
+    pub db: Database,
+}
+
+impl CertificationRepo {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl CertificationRepository for CertificationRepo {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        include_expired: bool,
+    ) -> mongodb::error::Result<Vec<Certification>> {
+        let collection = self.db.collection::<CertificationDocument>("certification");
+
+        let mut filter = doc! { "customer_id": oid };
+        if !include_expired {
+            // `null` also matches certifications without an expiry date.
+            filter.insert(
+                "$or",
+                vec![
+                    doc! { "expiry_date": null },
+                    doc! { "expiry_date": { "$gt": mongodb::bson::DateTime::now() } },
+                ],
+            );
+        }
+
+        let find_options = FindOptions::builder()
+            .sort(doc! { "order": -1 })
+            .limit(limit)
+            .skip(u64::try_from((page - 1) * limit).unwrap())
+            .build();
+
+        let mut cursor = collection.find(filter, find_options).await?;
+
+        let mut resp: Vec<Certification> = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            resp.push(to_certification(result));
+        }
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Certification>> {
+        let collection = self.db.collection::<CertificationDocument>("certification");
+
+        let resp = collection
+            .find_one(doc! {"_id":oid }, None)
+            .await?
+            .map(to_certification);
+
+        Ok(resp)
+    }
+
+    async fn insert(
+        &self,
+        input: Json<CertificationInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let collection = self.db.collection::<Document>("certification");
+
+        let created_at = mongodb::bson::DateTime::from_chrono(Utc::now());
+        let issue_date = parse_date(Some(&input.issue_date))?;
+        let expiry_date = parse_date(input.expiry_date.as_deref())?;
+
+        let resp = collection
+            .insert_one(
+                doc! {
+                    "customer_id": oid,
+                    "name": &input.name,
+                    "issuer": &input.issuer,
+                    "credential_id": &input.credential_id,
+                    "credential_url": &input.credential_url,
+                    "issue_date": issue_date,
+                    "expiry_date": expiry_date,
+                    "order": input.order,
+                    "created_at": created_at,
+                    "updated_at": created_at,
+                },
+                None,
+            )
+            .await?;
+
+        Ok(resp.inserted_id.to_string())
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<CertificationInput>,
+    ) -> mongodb::error::Result<Option<Certification>> {
+        let collection = self.db.collection::<CertificationDocument>("certification");
+        let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let issue_date = parse_date(Some(&input.issue_date))?;
+        let expiry_date = parse_date(input.expiry_date.as_deref())?;
+
+        let resp = collection
+            .find_one_and_update(
+                doc! {"_id":oid },
+                doc! {
+                    "$set": {
+                        "name": &input.name,
+                        "issuer": &input.issuer,
+                        "credential_id": &input.credential_id,
+                        "credential_url": &input.credential_url,
+                        "issue_date": issue_date,
+                        "expiry_date": expiry_date,
+                        "order": input.order,
+                        "updated_at": mongodb::bson::DateTime::now(),
+                    }
+                },
+                find_one_and_update_options,
+            )
+            .await?
+            .map(to_certification);
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<CertificationsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Certification>>> {
+        let collection = self.db.collection::<CertificationDocument>("certification");
+        let mut updated_certifications = vec![];
+
+        for item in input.iter() {
+            let oid = match ObjectId::parse_str(&item.id) {
+                Ok(oid) => oid,
+                Err(_) => continue, // skip invalid IDs
+            };
+
+            let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+                .return_document(ReturnDocument::After)
+                .build();
+
+            let issue_date = parse_date(Some(&item.issue_date))?;
+            let expiry_date = parse_date(item.expiry_date.as_deref())?;
+
+            if let Some(result) = collection
+                .find_one_and_update(
+                    doc! { "_id": oid, "customer_id": customer_oid },
+                    doc! {
+                        "$set": {
+                            "name": &item.name,
+                            "issuer": &item.issuer,
+                            "credential_id": &item.credential_id,
+                            "credential_url": &item.credential_url,
+                            "issue_date": issue_date,
+                            "expiry_date": expiry_date,
+                            "order": item.order,
+                            "updated_at": mongodb::bson::DateTime::now(),
+                        }
+                    },
+                    find_one_and_update_options,
+                )
+                .await?
+            {
+                updated_certifications.push(to_certification(result));
+            }
+        }
+
+        if updated_certifications.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(updated_certifications))
+        }
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Certification>> {
+        let collection = self.db.collection::<CertificationDocument>("certification");
+
+        let resp = collection
+            .find_one_and_delete(doc! {"_id":oid }, None)
+            .await?
+            .map(to_certification);
+
+        Ok(resp)
+    }
+}
+
+// transform ObjectId to String
+fn to_certification(result: CertificationDocument) -> Certification {
+    Certification {
+        id: result.id.to_string(),
+        customer_id: result.customer_id.to_string(),
+        name: result.name,
+        issuer: result.issuer,
+        credential_id: result.credential_id,
+        credential_url: result.credential_url,
+        issue_date: result.issue_date.to_string(),
+        expiry_date: result.expiry_date.map(|d| d.to_string()),
+        order: result.order,
+        created_at: result.created_at.to_string(),
+        updated_at: result.updated_at.map(|d| d.to_string()),
+    }
+}
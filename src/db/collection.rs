@@ -0,0 +1,367 @@
+use crate::models::collection::{
+    CustomCollection, CustomCollectionDocument, CustomCollectionInput, CustomEntriesInput,
+    CustomEntry, CustomEntryDocument, CustomEntryInput,
+};
+use crate::routes::traits::{CustomCollectionRepository, CustomEntryRepository};
+use crate::utils::markdown::slugify;
+use crate::utils::util::document_to_json;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, to_document, Document},
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Database,
+};
+use rocket::serde::json::Json;
+use serde_json::Value;
+
+pub struct CustomCollectionRepo {
+    pub db: Database,
+}
+
+impl CustomCollectionRepo {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+pub struct CustomEntryRepo {
+    pub db: Database,
+}
+
+impl CustomEntryRepo {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl CustomCollectionRepository for CustomCollectionRepo {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<CustomCollection>> {
+        let collection = self
+            .db
+            .collection::<CustomCollectionDocument>("custom_collection");
+
+        let filter = doc! { "customer_id": oid };
+
+        let find_options = FindOptions::builder()
+            .sort(doc! { "name": 1 })
+            .limit(limit)
+            .skip(u64::try_from((page - 1) * limit).unwrap())
+            .build();
+
+        let mut cursor = collection.find(filter, find_options).await?;
+
+        let mut resp: Vec<CustomCollection> = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            resp.push(to_collection(result));
+        }
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<CustomCollection>> {
+        let collection = self
+            .db
+            .collection::<CustomCollectionDocument>("custom_collection");
+
+        let resp = collection
+            .find_one(doc! {"_id":oid }, None)
+            .await?
+            .map(to_collection);
+
+        Ok(resp)
+    }
+
+    async fn find_by_slug(
+        &self,
+        oid: ObjectId,
+        slug: String,
+    ) -> mongodb::error::Result<Option<CustomCollection>> {
+        let collection = self
+            .db
+            .collection::<CustomCollectionDocument>("custom_collection");
+
+        let resp = collection
+            .find_one(doc! { "customer_id": oid, "slug": slug }, None)
+            .await?
+            .map(to_collection);
+
+        Ok(resp)
+    }
+
+    async fn insert(
+        &self,
+        input: Json<CustomCollectionInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let collection = self.db.collection::<Document>("custom_collection");
+
+        let created_at = mongodb::bson::DateTime::from_chrono(Utc::now());
+
+        let resp = collection
+            .insert_one(
+                doc! {
+                    "customer_id": oid,
+                    "name": &input.name,
+                    "slug": collection_slug(&input),
+                    "description": &input.description,
+                    "schema": input.schema.to_string(),
+                    "created_at": created_at,
+                    "updated_at": created_at,
+                },
+                None,
+            )
+            .await?;
+
+        Ok(resp.inserted_id.to_string())
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<CustomCollectionInput>,
+    ) -> mongodb::error::Result<Option<CustomCollection>> {
+        let collection = self
+            .db
+            .collection::<CustomCollectionDocument>("custom_collection");
+        let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let resp = collection
+            .find_one_and_update(
+                doc! {"_id":oid },
+                doc! {
+                    "$set": {
+                        "name": &input.name,
+                        "slug": collection_slug(&input),
+                        "description": &input.description,
+                        "schema": input.schema.to_string(),
+                        "updated_at": mongodb::bson::DateTime::now(),
+                    }
+                },
+                find_one_and_update_options,
+            )
+            .await?
+            .map(to_collection);
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(
+        &self,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Option<CustomCollection>> {
+        let collection = self
+            .db
+            .collection::<CustomCollectionDocument>("custom_collection");
+
+        let Some(result) = collection
+            .find_one_and_delete(doc! {"_id":oid }, None)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        // entries are useless without their schema
+        self.db
+            .collection::<Document>("custom_entry")
+            .delete_many(doc! { "collection_id": oid }, None)
+            .await?;
+
+        Ok(Some(to_collection(result)))
+    }
+}
+
+#[async_trait]
+impl CustomEntryRepository for CustomEntryRepo {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        collection_oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<CustomEntry>> {
+        let collection = self.db.collection::<CustomEntryDocument>("custom_entry");
+
+        let filter = doc! { "customer_id": oid, "collection_id": collection_oid };
+
+        let find_options = FindOptions::builder()
+            .sort(doc! { "order": -1 })
+            .limit(limit)
+            .skip(u64::try_from((page - 1) * limit).unwrap())
+            .build();
+
+        let mut cursor = collection.find(filter, find_options).await?;
+
+        let mut resp: Vec<CustomEntry> = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            resp.push(to_entry(result));
+        }
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<CustomEntry>> {
+        let collection = self.db.collection::<CustomEntryDocument>("custom_entry");
+
+        let resp = collection
+            .find_one(doc! {"_id":oid }, None)
+            .await?
+            .map(to_entry);
+
+        Ok(resp)
+    }
+
+    async fn insert(
+        &self,
+        input: Json<CustomEntryInput>,
+        oid: ObjectId,
+        collection_oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let collection = self.db.collection::<Document>("custom_entry");
+
+        let created_at = mongodb::bson::DateTime::from_chrono(Utc::now());
+
+        let resp = collection
+            .insert_one(
+                doc! {
+                    "customer_id": oid,
+                    "collection_id": collection_oid,
+                    "data": to_document(&input.data)?,
+                    "order": input.order,
+                    "created_at": created_at,
+                    "updated_at": created_at,
+                },
+                None,
+            )
+            .await?;
+
+        Ok(resp.inserted_id.to_string())
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<CustomEntryInput>,
+    ) -> mongodb::error::Result<Option<CustomEntry>> {
+        let collection = self.db.collection::<CustomEntryDocument>("custom_entry");
+        let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let resp = collection
+            .find_one_and_update(
+                doc! {"_id":oid },
+                doc! {
+                    "$set": {
+                        "data": to_document(&input.data)?,
+                        "order": input.order,
+                        "updated_at": mongodb::bson::DateTime::now(),
+                    }
+                },
+                find_one_and_update_options,
+            )
+            .await?
+            .map(to_entry);
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        collection_oid: ObjectId,
+        input: Json<Vec<CustomEntriesInput>>,
+    ) -> mongodb::error::Result<Option<Vec<CustomEntry>>> {
+        let collection = self.db.collection::<CustomEntryDocument>("custom_entry");
+        let mut updated_entries = vec![];
+
+        for item in input.iter() {
+            let oid = match ObjectId::parse_str(&item.id) {
+                Ok(oid) => oid,
+                Err(_) => continue, // skip invalid IDs
+            };
+
+            let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+                .return_document(ReturnDocument::After)
+                .build();
+
+            let mut set = doc! {
+                "order": item.order,
+                "updated_at": mongodb::bson::DateTime::now(),
+            };
+            if let Some(data) = &item.data {
+                set.insert("data", to_document(data)?);
+            }
+
+            if let Some(result) = collection
+                .find_one_and_update(
+                    doc! { "_id": oid, "collection_id": collection_oid },
+                    doc! { "$set": set },
+                    find_one_and_update_options,
+                )
+                .await?
+            {
+                updated_entries.push(to_entry(result));
+            }
+        }
+
+        if updated_entries.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(updated_entries))
+        }
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<CustomEntry>> {
+        let collection = self.db.collection::<CustomEntryDocument>("custom_entry");
+
+        let resp = collection
+            .find_one_and_delete(doc! {"_id":oid }, None)
+            .await?
+            .map(to_entry);
+
+        Ok(resp)
+    }
+}
+
+/// Slug of a collection, generated from its name when the input has none.
+pub fn collection_slug(input: &CustomCollectionInput) -> String {
+    match input.slug.as_deref().map(slugify) {
+        Some(slug) if !slug.is_empty() => slug,
+        _ => slugify(&input.name),
+    }
+}
+
+// transform ObjectId to String
+fn to_collection(result: CustomCollectionDocument) -> CustomCollection {
+    CustomCollection {
+        id: result.id.to_string(),
+        customer_id: result.customer_id.to_string(),
+        name: result.name,
+        slug: result.slug,
+        description: result.description,
+        schema: serde_json::from_str(&result.schema).unwrap_or(Value::Null),
+        created_at: result.created_at.to_string(),
+        updated_at: result.updated_at.map(|d| d.to_string()),
+    }
+}
+
+// transform ObjectId to String
+fn to_entry(result: CustomEntryDocument) -> CustomEntry {
+    CustomEntry {
+        id: result.id.to_string(),
+        customer_id: result.customer_id.to_string(),
+        collection_id: result.collection_id.to_string(),
+        data: Value::Object(document_to_json(result.data)),
+        order: result.order,
+        created_at: result.created_at.to_string(),
+        updated_at: result.updated_at.map(|d| d.to_string()),
+    }
+}
@@ -0,0 +1,152 @@
+use crate::models::contact::{
+    ContactFilter, ContactInput, ContactMessage, ContactMessageDocument, ContactMessageUpdate,
+};
+use crate::routes::traits::ContactRepository;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId},
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Database,
+};
+use rocket::serde::json::Json;
+
+pub struct ContactRepo {
+    pub db: Database,
+}
+
+impl ContactRepo {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl ContactRepository for ContactRepo {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        filter: ContactFilter,
+    ) -> mongodb::error::Result<Vec<ContactMessage>> {
+        let collection = self.db.collection::<ContactMessageDocument>("contact");
+
+        let mut document = doc! { "customer_id": oid, "archived": filter.archived };
+        if let Some(read) = filter.read {
+            document.insert("read", read);
+        }
+
+        // Newest messages first.
+        let find_options = FindOptions::builder()
+            .sort(doc! { "created_at": -1 })
+            .limit(limit)
+            .skip(u64::try_from((page - 1) * limit).unwrap())
+            .build();
+
+        let mut cursor = collection.find(document, find_options).await?;
+
+        let mut resp: Vec<ContactMessage> = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            resp.push(to_contact_message(result));
+        }
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<ContactMessage>> {
+        let collection = self.db.collection::<ContactMessageDocument>("contact");
+
+        let resp = collection
+            .find_one(doc! {"_id":oid }, None)
+            .await?
+            .map(to_contact_message);
+
+        Ok(resp)
+    }
+
+    async fn insert(
+        &self,
+        input: Json<ContactInput>,
+        oid: ObjectId,
+        ip: Option<String>,
+    ) -> mongodb::error::Result<ContactMessage> {
+        let collection = self.db.collection::<ContactMessageDocument>("contact");
+
+        let now = Utc::now();
+        let document = ContactMessageDocument {
+            id: ObjectId::new(),
+            customer_id: oid,
+            name: input.name.trim().to_string(),
+            email: input.email.trim().to_string(),
+            subject: input.subject.clone(),
+            message: input.message.clone(),
+            ip,
+            read: false,
+            archived: false,
+            created_at: now,
+            updated_at: Some(now),
+        };
+        collection.insert_one(&document, None).await?;
+
+        Ok(to_contact_message(document))
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<ContactMessageUpdate>,
+    ) -> mongodb::error::Result<Option<ContactMessage>> {
+        let collection = self.db.collection::<ContactMessageDocument>("contact");
+        let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let mut set = doc! { "updated_at": mongodb::bson::DateTime::now() };
+        if let Some(read) = input.read {
+            set.insert("read", read);
+        }
+        if let Some(archived) = input.archived {
+            set.insert("archived", archived);
+        }
+
+        let resp = collection
+            .find_one_and_update(
+                doc! {"_id":oid },
+                doc! { "$set": set },
+                find_one_and_update_options,
+            )
+            .await?
+            .map(to_contact_message);
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<ContactMessage>> {
+        let collection = self.db.collection::<ContactMessageDocument>("contact");
+
+        let resp = collection
+            .find_one_and_delete(doc! {"_id":oid }, None)
+            .await?
+            .map(to_contact_message);
+
+        Ok(resp)
+    }
+}
+
+// transform ObjectId to String
+fn to_contact_message(result: ContactMessageDocument) -> ContactMessage {
+    ContactMessage {
+        id: result.id.to_string(),
+        customer_id: result.customer_id.to_string(),
+        name: result.name,
+        email: result.email,
+        subject: result.subject,
+        message: result.message,
+        ip: result.ip,
+        read: result.read,
+        archived: result.archived,
+        created_at: result.created_at.to_string(),
+        updated_at: result.updated_at.map(|d| d.to_string()),
+    }
+}
@@ -1,9 +1,10 @@
+use crate::db::media::find_media;
 use crate::models::customer::{Customer, CustomerDocument, CustomerInput, CustomerUpdateInput};
 use crate::routes::traits::CustomerRepository;
 use bcrypt::{hash, DEFAULT_COST};
 use chrono::Utc;
 use mongodb::{
-    bson::{doc, oid::ObjectId, DateTime, Document},
+    bson::{doc, oid::ObjectId, to_bson, DateTime, Document},
     options::{FindOneAndUpdateOptions, ReturnDocument},
     Database,
 };
@@ -70,8 +71,17 @@ impl CustomerRepository for CustomerRepo {
             intro: customer_doc.intro,
             about: customer_doc.about,
             profile_picture: customer_doc.profile_picture,
+            profile_picture_id: customer_doc.profile_picture_id.map(|id| id.to_string()),
+            profile_picture_srcset: customer_doc.profile_picture_srcset,
+            profile_picture_blurhash: customer_doc.profile_picture_blurhash,
+            default_locale: customer_doc.default_locale,
+            locales: customer_doc.locales,
+            translations: customer_doc.translations,
+            site_url: customer_doc.site_url,
+            seo: customer_doc.seo,
             password: customer_doc.password.to_string(),
             created_at: customer_doc.created_at.to_string(),
+            updated_at: customer_doc.updated_at.map(|d| d.to_string()),
         };
 
         Ok(Some(customer_json))
@@ -98,8 +108,17 @@ impl CustomerRepository for CustomerRepo {
             intro: customer_doc.intro,
             about: customer_doc.about,
             profile_picture: customer_doc.profile_picture,
+            profile_picture_id: customer_doc.profile_picture_id.map(|id| id.to_string()),
+            profile_picture_srcset: customer_doc.profile_picture_srcset,
+            profile_picture_blurhash: customer_doc.profile_picture_blurhash,
+            default_locale: customer_doc.default_locale,
+            locales: customer_doc.locales,
+            translations: customer_doc.translations,
+            site_url: customer_doc.site_url,
+            seo: customer_doc.seo,
             password: customer_doc.password.to_string(),
             created_at: customer_doc.created_at.to_string(),
+            updated_at: customer_doc.updated_at.map(|d| d.to_string()),
         };
 
         Ok(Some(customer_json))
@@ -126,8 +145,17 @@ impl CustomerRepository for CustomerRepo {
             intro: customer_doc.intro,
             about: customer_doc.about,
             profile_picture: customer_doc.profile_picture,
+            profile_picture_id: customer_doc.profile_picture_id.map(|id| id.to_string()),
+            profile_picture_srcset: customer_doc.profile_picture_srcset,
+            profile_picture_blurhash: customer_doc.profile_picture_blurhash,
+            default_locale: customer_doc.default_locale,
+            locales: customer_doc.locales,
+            translations: customer_doc.translations,
+            site_url: customer_doc.site_url,
+            seo: customer_doc.seo,
             password: customer_doc.password.to_string(),
             created_at: customer_doc.created_at.to_string(),
+            updated_at: customer_doc.updated_at.map(|d| d.to_string()),
         };
 
         Ok(Some(customer_json))
@@ -157,6 +185,7 @@ impl CustomerRepository for CustomerRepo {
                     "profile_picture": &input.profile_picture,
                     "password": hashed_password,
                     "createdAt": created_at,
+                    "updatedAt": created_at,
                 },
                 None,
             )
@@ -175,23 +204,65 @@ impl CustomerRepository for CustomerRepo {
             .return_document(ReturnDocument::After)
             .build();
 
+        let Some(existing) = collection.find_one(doc! {"_id":oid }, None).await? else {
+            return Ok(None);
+        };
+
         let created_at: DateTime = DateTime::now();
+        let profile_picture_id = input
+            .profile_picture_id
+            .as_deref()
+            .and_then(|id| ObjectId::parse_str(id).ok());
+        let media = find_media(&self.db, oid, profile_picture_id).await?;
+        let profile_picture = media
+            .as_ref()
+            .map(|media| media.url.clone())
+            .or_else(|| input.profile_picture.clone());
+
+        let mut set = doc! {
+            "name": &input.name,
+            "email": &input.email,
+            "phone": &input.phone,
+            "wa_link": &input.wa_link,
+            "intro": &input.intro,
+            "about": &input.about,
+            "profile_picture": &profile_picture,
+            "createdAt": created_at,
+            "updatedAt": created_at,
+        };
+        if let Some(default_locale) = &input.default_locale {
+            set.insert("default_locale", default_locale);
+        }
+        if let Some(locales) = &input.locales {
+            set.insert("locales", locales);
+        }
+        if let Some(translations) = &input.translations {
+            set.insert("translations", to_bson(translations)?);
+        }
+        if let Some(site_url) = &input.site_url {
+            set.insert("site_url", site_url.trim().trim_end_matches('/'));
+        }
+        if let Some(seo) = &input.seo {
+            set.insert("seo", to_bson(seo)?);
+        }
+        // Without `profile_picture_id` the uploaded picture is only unlinked when
+        // `profile_picture` no longer points to it.
+        if input.profile_picture_id.is_some() || profile_picture != existing.profile_picture {
+            set.insert("profile_picture_id", profile_picture_id);
+            set.insert(
+                "profile_picture_srcset",
+                media.as_ref().and_then(|media| media.srcset.clone()),
+            );
+            set.insert(
+                "profile_picture_blurhash",
+                media.and_then(|media| media.blurhash),
+            );
+        }
 
         let Some(customer_doc) = collection
             .find_one_and_update(
                 doc! {"_id":oid },
-                doc! {
-                        "$set": {
-                            "name": &input.name,
-                            "email": &input.email,
-                            "phone": &input.phone,
-                            "wa_link": &input.wa_link,
-                            "intro": &input.intro,
-                            "about": &input.about,
-                            "profile_picture": &input.profile_picture,
-                            "createdAt": created_at,
-                        }
-                },
+                doc! { "$set": set },
                 find_one_and_update_options,
             )
             .await?
@@ -210,8 +281,17 @@ impl CustomerRepository for CustomerRepo {
             intro: customer_doc.intro,
             about: customer_doc.about,
             profile_picture: customer_doc.profile_picture,
+            profile_picture_id: customer_doc.profile_picture_id.map(|id| id.to_string()),
+            profile_picture_srcset: customer_doc.profile_picture_srcset,
+            profile_picture_blurhash: customer_doc.profile_picture_blurhash,
+            default_locale: customer_doc.default_locale,
+            locales: customer_doc.locales,
+            translations: customer_doc.translations,
+            site_url: customer_doc.site_url,
+            seo: customer_doc.seo,
             password: customer_doc.password.to_string(),
             created_at: customer_doc.created_at.to_string(),
+            updated_at: customer_doc.updated_at.map(|d| d.to_string()),
         };
 
         Ok(Some(customer_json))
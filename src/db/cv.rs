@@ -0,0 +1,102 @@
+use crate::models::cv::CvSettingsDocument;
+use crate::routes::traits::CvRepository;
+use mongodb::{
+    bson::{doc, oid::ObjectId, Document},
+    options::{FindOneAndUpdateOptions, FindOneOptions, IndexOptions, ReturnDocument},
+    Database, IndexModel,
+};
+
+// The settings live on the customer document, only they are read.
+fn projection() -> Document {
+    doc! { "_id": 1, "cv_template": 1, "cv_share_token": 1 }
+}
+
+/// Shared CVs are looked up by their token, customers without one are left out of the index.
+pub async fn ensure_indexes(db: &Database) -> mongodb::error::Result<()> {
+    db.collection::<Document>("customer")
+        .create_index(
+            IndexModel::builder()
+                .keys(doc! { "cv_share_token": 1 })
+                .options(IndexOptions::builder().unique(true).sparse(true).build())
+                .build(),
+            None,
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub struct CvRepo {
+    pub db: Database,
+}
+
+impl CvRepo {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    async fn set(
+        &self,
+        oid: ObjectId,
+        update: Document,
+    ) -> mongodb::error::Result<Option<CvSettingsDocument>> {
+        let collection = self.db.collection::<CvSettingsDocument>("customer");
+        let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+            .projection(projection())
+            .return_document(ReturnDocument::After)
+            .build();
+
+        collection
+            .find_one_and_update(doc! { "_id": oid }, update, find_one_and_update_options)
+            .await
+    }
+}
+
+#[async_trait]
+impl CvRepository for CvRepo {
+    async fn find_settings(
+        &self,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Option<CvSettingsDocument>> {
+        let collection = self.db.collection::<CvSettingsDocument>("customer");
+        let find_one_options = FindOneOptions::builder().projection(projection()).build();
+
+        collection
+            .find_one(doc! { "_id": oid }, find_one_options)
+            .await
+    }
+
+    async fn update_template(
+        &self,
+        oid: ObjectId,
+        template: &str,
+    ) -> mongodb::error::Result<Option<CvSettingsDocument>> {
+        self.set(oid, doc! { "$set": { "cv_template": template } })
+            .await
+    }
+
+    async fn update_share_token(
+        &self,
+        oid: ObjectId,
+        token: Option<&str>,
+    ) -> mongodb::error::Result<Option<CvSettingsDocument>> {
+        let update = match token {
+            Some(token) => doc! { "$set": { "cv_share_token": token } },
+            None => doc! { "$unset": { "cv_share_token": "" } },
+        };
+        self.set(oid, update).await
+    }
+
+    async fn find_customer_id_by_share_token(
+        &self,
+        token: &str,
+    ) -> mongodb::error::Result<Option<ObjectId>> {
+        let collection = self.db.collection::<CvSettingsDocument>("customer");
+        let find_one_options = FindOneOptions::builder().projection(projection()).build();
+
+        Ok(collection
+            .find_one(doc! { "cv_share_token": token }, find_one_options)
+            .await?
+            .map(|settings| settings.id))
+    }
+}
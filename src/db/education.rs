@@ -0,0 +1,212 @@
+use crate::models::education::{Education, EducationDocument, EducationInput, EducationsInput};
+use crate::routes::traits::EducationRepository;
+use crate::utils::util::parse_date;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, Document},
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Database,
+};
+use rocket::serde::json::Json;
+
+pub struct EducationRepo {
+    pub db: Database,
+}
+
+impl EducationRepo {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl EducationRepository for EducationRepo {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Education>> {
+        let collection = self.db.collection::<EducationDocument>("education");
+
+        let filter = doc! { "customer_id": oid };
+
+        let find_options = FindOptions::builder()
+            .sort(doc! { "order": -1 })
+            .limit(limit)
+            .skip(u64::try_from((page - 1) * limit).unwrap())
+            .build();
+
+        let mut cursor = collection.find(filter, find_options).await?;
+
+        let mut resp: Vec<Education> = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            resp.push(to_education(result));
+        }
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Education>> {
+        let collection = self.db.collection::<EducationDocument>("education");
+
+        let resp = collection
+            .find_one(doc! {"_id":oid }, None)
+            .await?
+            .map(to_education);
+
+        Ok(resp)
+    }
+
+    async fn insert(
+        &self,
+        input: Json<EducationInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let collection = self.db.collection::<Document>("education");
+
+        let created_at = mongodb::bson::DateTime::from_chrono(Utc::now());
+        let start_date = parse_date(Some(&input.start_date))?;
+        let end_date = parse_date(input.end_date.as_deref())?;
+
+        let resp = collection
+            .insert_one(
+                doc! {
+                    "customer_id": oid,
+                    "institution": &input.institution,
+                    "degree": &input.degree,
+                    "field": &input.field,
+                    "start_date": start_date,
+                    "end_date": end_date,
+                    "grade": &input.grade,
+                    "description": &input.description,
+                    "order": input.order,
+                    "created_at": created_at,
+                    "updated_at": created_at,
+                },
+                None,
+            )
+            .await?;
+
+        Ok(resp.inserted_id.to_string())
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<EducationInput>,
+    ) -> mongodb::error::Result<Option<Education>> {
+        let collection = self.db.collection::<EducationDocument>("education");
+        let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let start_date = parse_date(Some(&input.start_date))?;
+        let end_date = parse_date(input.end_date.as_deref())?;
+
+        let resp = collection
+            .find_one_and_update(
+                doc! {"_id":oid },
+                doc! {
+                    "$set": {
+                        "institution": &input.institution,
+                        "degree": &input.degree,
+                        "field": &input.field,
+                        "start_date": start_date,
+                        "end_date": end_date,
+                        "grade": &input.grade,
+                        "description": &input.description,
+                        "order": input.order,
+                        "updated_at": mongodb::bson::DateTime::now(),
+                    }
+                },
+                find_one_and_update_options,
+            )
+            .await?
+            .map(to_education);
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<EducationsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Education>>> {
+        let collection = self.db.collection::<EducationDocument>("education");
+        let mut updated_educations = vec![];
+
+        for item in input.iter() {
+            let oid = match ObjectId::parse_str(&item.id) {
+                Ok(oid) => oid,
+                Err(_) => continue, // skip invalid IDs
+            };
+
+            let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+                .return_document(ReturnDocument::After)
+                .build();
+
+            let start_date = parse_date(Some(&item.start_date))?;
+            let end_date = parse_date(item.end_date.as_deref())?;
+
+            if let Some(result) = collection
+                .find_one_and_update(
+                    doc! { "_id": oid, "customer_id": customer_oid },
+                    doc! {
+                        "$set": {
+                            "institution": &item.institution,
+                            "degree": &item.degree,
+                            "field": &item.field,
+                            "start_date": start_date,
+                            "end_date": end_date,
+                            "grade": &item.grade,
+                            "description": &item.description,
+                            "order": item.order,
+                            "updated_at": mongodb::bson::DateTime::now(),
+                        }
+                    },
+                    find_one_and_update_options,
+                )
+                .await?
+            {
+                updated_educations.push(to_education(result));
+            }
+        }
+
+        if updated_educations.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(updated_educations))
+        }
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Education>> {
+        let collection = self.db.collection::<EducationDocument>("education");
+
+        let resp = collection
+            .find_one_and_delete(doc! {"_id":oid }, None)
+            .await?
+            .map(to_education);
+
+        Ok(resp)
+    }
+}
+
+// transform ObjectId to String
+fn to_education(result: EducationDocument) -> Education {
+    Education {
+        id: result.id.to_string(),
+        customer_id: result.customer_id.to_string(),
+        institution: result.institution,
+        degree: result.degree,
+        field: result.field,
+        start_date: result.start_date.to_string(),
+        end_date: result.end_date.map(|d| d.to_string()),
+        grade: result.grade,
+        description: result.description,
+        order: result.order,
+        created_at: result.created_at.to_string(),
+        updated_at: result.updated_at.map(|d| d.to_string()),
+    }
+}
@@ -0,0 +1,1403 @@
+use crate::db::article::find_due_between;
+use crate::models::{
+    article::{Article, ArticleInput},
+    certification::{Certification, CertificationInput, CertificationsInput},
+    collection::{
+        CustomCollection, CustomCollectionInput, CustomEntriesInput, CustomEntry, CustomEntryInput,
+    },
+    customer::{Customer, CustomerInput, CustomerUpdateInput},
+    education::{Education, EducationInput, EducationsInput},
+    event::{ContentAction, ContentEvent},
+    experience::{Experience, ExperienceInput, ExperiencesInput},
+    project::{Project, ProjectFilter, ProjectInput, ProjectsInput},
+    service::{Service, ServiceInput, ServicesInput},
+    skill::{Skill, SkillInput, SkillsInput},
+    social::{Social, SocialInput, SocialsInput},
+    testimonial::{
+        Testimonial, TestimonialInput, TestimonialStatus, TestimonialSubmission, TestimonialsInput,
+    },
+};
+use crate::routes::traits::{
+    ArticleRepository, CertificationRepository, ContentEventListener, CustomCollectionRepository,
+    CustomEntryRepository, CustomerRepository, EducationRepository, ExperienceRepository,
+    ProjectRepository, ServiceRepository, SkillRepository, SocialRepository, TestimonialRepository,
+};
+use crate::utils::util::parse_datetime;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, to_document, Document},
+    change_stream::event::ResumeToken,
+    options::{ChangeStreamOptions, IndexOptions},
+    Database, IndexModel,
+};
+use rocket::serde::json::Json;
+use rocket::tokio::sync::broadcast;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::env;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+// How often scheduled articles are looked for.
+const PUBLISH_POLL: Duration = Duration::from_secs(60);
+// Events a slow subscriber may fall behind before it misses some.
+const BUS_CAPACITY: usize = 256;
+// Events are only kept in the log long enough to reach every replica.
+const EVENT_LOG_TTL: Duration = Duration::from_secs(60 * 60);
+
+type Listener = Arc<dyn ContentEventListener + Send + Sync>;
+
+/// Hands every change of the content to the listeners, e.g. the webhooks, and to the
+/// subscribers of the in-process bus, e.g. `GET /events`.
+/// <br />With `CONTENT_EVENTS_CHANGE_STREAM=true` the bus is fed from the `content_event`
+/// collection instead, so every replica sees the changes made through the others.
+pub struct ContentEvents {
+    listeners: RwLock<Vec<Listener>>,
+    bus: broadcast::Sender<ContentEvent>,
+    change_stream: bool,
+}
+
+impl ContentEvents {
+    pub fn new() -> Self {
+        let change_stream = env::var("CONTENT_EVENTS_CHANGE_STREAM")
+            .map(|value| value == "true")
+            .unwrap_or(false);
+
+        Self {
+            listeners: RwLock::new(vec![]),
+            bus: broadcast::channel(BUS_CAPACITY).0,
+            change_stream,
+        }
+    }
+
+    /// Whether the bus is fed from a change stream, see [`watch_event_log`].
+    pub fn change_stream(&self) -> bool {
+        self.change_stream
+    }
+
+    pub fn listen(&self, listener: Listener) {
+        self.listeners
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(listener);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ContentEvent> {
+        self.bus.subscribe()
+    }
+
+    /// Send an event to the subscribers of the bus only.
+    pub fn broadcast(&self, event: ContentEvent) {
+        // nobody listening is not an error
+        let _ = self.bus.send(event);
+    }
+
+    pub fn emit(&self, event: ContentEvent) {
+        if !self.change_stream {
+            self.broadcast(event.clone());
+        }
+
+        let listeners = self
+            .listeners
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        if listeners.is_empty() {
+            return;
+        }
+
+        // the write that caused the event does not wait for its listeners
+        rocket::tokio::spawn(async move {
+            for listener in listeners {
+                listener.on_event(&event).await;
+            }
+        });
+    }
+
+    /// Emit a change of a document, `data` is sent the way the API returns it.
+    pub fn changed<T: Serialize>(
+        &self,
+        section: &str,
+        action: ContentAction,
+        customer_id: &str,
+        id: &str,
+        data: Option<&T>,
+    ) {
+        self.changed_with_visibility(section, action, customer_id, id, data, true);
+    }
+
+    /// Emit a change of a document the public endpoints may hide, e.g. a draft article. A
+    /// hidden change is only streamed to the customer itself.
+    pub fn changed_with_visibility<T: Serialize>(
+        &self,
+        section: &str,
+        action: ContentAction,
+        customer_id: &str,
+        id: &str,
+        data: Option<&T>,
+        public: bool,
+    ) {
+        let data = data.and_then(|data| serde_json::to_value(data).ok());
+        let mut event = ContentEvent::new(section, action, customer_id, id, data);
+        event.public = public;
+        self.emit(event);
+    }
+
+    /// Emit a change of the profile, the keys of the customer are left out.
+    pub fn profile_changed(&self, customer: &Customer) {
+        let mut data = serde_json::to_value(customer).ok();
+        if let Some(Value::Object(fields)) = &mut data {
+            fields.remove("api_key");
+            fields.remove("password");
+        }
+        self.emit(ContentEvent::new(
+            "profile",
+            ContentAction::Updated,
+            &customer.id,
+            &customer.id,
+            data,
+        ));
+    }
+}
+
+impl Default for ContentEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Emit `article.published` when a scheduled article reaches its `published_at`. Articles due
+/// while the server was down are not reported.
+pub async fn watch_scheduled(database: Database, events: Arc<ContentEvents>) {
+    let mut last = Utc::now();
+    loop {
+        rocket::tokio::time::sleep(PUBLISH_POLL).await;
+
+        let now = Utc::now();
+        match find_due_between(&database, last, now).await {
+            Ok(articles) => {
+                for article in articles {
+                    events.changed(
+                        "article",
+                        ContentAction::Published,
+                        &article.customer_id,
+                        &article.id,
+                        Some(&article),
+                    );
+                }
+                last = now;
+            }
+            // the same window is looked at again next time
+            Err(error) => eprintln!("Events: failed to look for scheduled articles: {error}"),
+        }
+    }
+}
+
+/// Listener writing the events to the `content_event` collection, for [`watch_event_log`].
+pub struct EventLog {
+    db: Database,
+}
+
+impl EventLog {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl ContentEventListener for EventLog {
+    async fn on_event(&self, event: &ContentEvent) {
+        let collection = self.db.collection::<Document>("content_event");
+
+        let result = match to_document(event) {
+            Ok(mut document) => {
+                // for the TTL index
+                document.insert("created_at", mongodb::bson::DateTime::now());
+                collection.insert_one(document, None).await.map(|_| ())
+            }
+            Err(error) => Err(error.into()),
+        };
+        if let Err(error) = result {
+            eprintln!("Events: failed to log {}: {error}", event.event);
+        }
+    }
+}
+
+/// Feed the bus with the events logged by every replica. Change streams need MongoDB to run as a
+/// replica set, the stream is opened again where it stopped when it fails.
+pub async fn watch_event_log(database: Database, events: Arc<ContentEvents>) {
+    let collection = database.collection::<ContentEvent>("content_event");
+    let index = IndexModel::builder()
+        .keys(doc! { "created_at": 1 })
+        .options(IndexOptions::builder().expire_after(EVENT_LOG_TTL).build())
+        .build();
+    if let Err(error) = collection.create_index(index, None).await {
+        eprintln!("Failed to create the content event index: {error}");
+    }
+
+    let mut resume_token: Option<ResumeToken> = None;
+    loop {
+        let options = ChangeStreamOptions::builder()
+            .start_after(resume_token.clone())
+            .build();
+        let result = match collection
+            .watch([doc! { "$match": { "operationType": "insert" } }], options)
+            .await
+        {
+            Ok(mut stream) => loop {
+                match stream.try_next().await {
+                    Ok(Some(change)) => {
+                        if let Some(event) = change.full_document {
+                            events.broadcast(event);
+                        }
+                        resume_token = stream.resume_token();
+                    }
+                    Ok(None) => break Ok(()),
+                    Err(error) => break Err(error),
+                }
+            },
+            Err(error) => Err(error),
+        };
+
+        if let Err(error) = result {
+            eprintln!("Events: change stream of content_event failed: {error}");
+        }
+        rocket::tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+}
+
+/// Repository decorator that reports every write to [`ContentEvents`].
+pub struct EventedRepo<R> {
+    inner: R,
+    events: Arc<ContentEvents>,
+}
+
+impl<R> EventedRepo<R> {
+    pub fn new(inner: R, events: Arc<ContentEvents>) -> Self {
+        Self { inner, events }
+    }
+}
+
+// `insert` returns the id as `ObjectId("...")`.
+fn inserted_id(resp: &str) -> Option<ObjectId> {
+    let hex = resp
+        .strip_prefix("ObjectId(\"")
+        .and_then(|rest| rest.strip_suffix("\")"))
+        .unwrap_or(resp);
+    ObjectId::parse_str(hex).ok()
+}
+
+// An article is public once its `published_at` is past.
+fn is_public(article: &Article) -> bool {
+    article
+        .published_at
+        .as_deref()
+        .and_then(parse_datetime)
+        .is_some_and(|published_at| published_at <= Utc::now())
+}
+
+fn is_approved(testimonial: &Testimonial) -> bool {
+    testimonial.status == TestimonialStatus::Approved
+}
+
+#[async_trait]
+impl<R: ArticleRepository + Send + Sync> ArticleRepository for EventedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        published_only: bool,
+    ) -> mongodb::error::Result<Vec<Article>> {
+        self.inner.find(limit, page, oid, published_only).await
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Article>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn find_by_slug(
+        &self,
+        oid: ObjectId,
+        slug: String,
+        published_only: bool,
+    ) -> mongodb::error::Result<Option<Article>> {
+        self.inner.find_by_slug(oid, slug, published_only).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<ArticleInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        if let Some(id) = inserted_id(&resp) {
+            let created = self.inner.find_by_id(id).await.ok().flatten();
+            self.events.changed_with_visibility(
+                "article",
+                ContentAction::Created,
+                &oid.to_string(),
+                &id.to_string(),
+                created.as_ref(),
+                created.as_ref().is_some_and(is_public),
+            );
+            if let Some(article) = created.as_ref().filter(|article| is_public(article)) {
+                self.events.changed(
+                    "article",
+                    ContentAction::Published,
+                    &article.customer_id,
+                    &article.id,
+                    Some(article),
+                );
+            }
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<ArticleInput>,
+    ) -> mongodb::error::Result<Option<Article>> {
+        let was_public = self
+            .inner
+            .find_by_id(oid)
+            .await?
+            .is_some_and(|article| is_public(&article));
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(article) = &resp {
+            // unpublishing is told too, the article has to leave the public pages
+            self.events.changed_with_visibility(
+                "article",
+                ContentAction::Updated,
+                &article.customer_id,
+                &article.id,
+                Some(article),
+                was_public || is_public(article),
+            );
+            if !was_public && is_public(article) {
+                self.events.changed(
+                    "article",
+                    ContentAction::Published,
+                    &article.customer_id,
+                    &article.id,
+                    Some(article),
+                );
+            }
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Article>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(article) = &resp {
+            self.events.changed_with_visibility(
+                "article",
+                ContentAction::Deleted,
+                &article.customer_id,
+                &article.id,
+                Some(article),
+                is_public(article),
+            );
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: CertificationRepository + Send + Sync> CertificationRepository for EventedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        include_expired: bool,
+    ) -> mongodb::error::Result<Vec<Certification>> {
+        self.inner.find(limit, page, oid, include_expired).await
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Certification>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<CertificationInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        if let Some(id) = inserted_id(&resp) {
+            let created = self.inner.find_by_id(id).await.ok().flatten();
+            self.events.changed(
+                "certification",
+                ContentAction::Created,
+                &oid.to_string(),
+                &id.to_string(),
+                created.as_ref(),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<CertificationInput>,
+    ) -> mongodb::error::Result<Option<Certification>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(certification) = &resp {
+            self.events.changed(
+                "certification",
+                ContentAction::Updated,
+                &certification.customer_id,
+                &certification.id,
+                Some(certification),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<CertificationsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Certification>>> {
+        let resp = self.inner.update_many(customer_oid, input).await?;
+        for certification in resp.iter().flatten() {
+            self.events.changed(
+                "certification",
+                ContentAction::Updated,
+                &certification.customer_id,
+                &certification.id,
+                Some(certification),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Certification>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(certification) = &resp {
+            self.events.changed(
+                "certification",
+                ContentAction::Deleted,
+                &certification.customer_id,
+                &certification.id,
+                Some(certification),
+            );
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: CustomerRepository + Send + Sync> CustomerRepository for EventedRepo<R> {
+    async fn find_customer_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Customer>> {
+        self.inner.find_customer_by_id(oid).await
+    }
+
+    async fn find_customer_by_api_key(
+        &self,
+        api_key: &str,
+    ) -> mongodb::error::Result<Option<Customer>> {
+        self.inner.find_customer_by_api_key(api_key).await
+    }
+
+    async fn find_customer_by_email(
+        &self,
+        email: &str,
+    ) -> mongodb::error::Result<Option<Customer>> {
+        self.inner.find_customer_by_email(email).await
+    }
+
+    async fn insert_customer(&self, input: Json<CustomerInput>) -> mongodb::error::Result<String> {
+        self.inner.insert_customer(input).await
+    }
+
+    async fn update_customer_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<CustomerUpdateInput>,
+    ) -> mongodb::error::Result<Option<Customer>> {
+        let resp = self.inner.update_customer_by_id(oid, input).await?;
+        if let Some(customer) = &resp {
+            self.events.profile_changed(customer);
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: CustomCollectionRepository + Send + Sync> CustomCollectionRepository for EventedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<CustomCollection>> {
+        self.inner.find(limit, page, oid).await
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<CustomCollection>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn find_by_slug(
+        &self,
+        oid: ObjectId,
+        slug: String,
+    ) -> mongodb::error::Result<Option<CustomCollection>> {
+        self.inner.find_by_slug(oid, slug).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<CustomCollectionInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        if let Some(id) = inserted_id(&resp) {
+            let created = self.inner.find_by_id(id).await.ok().flatten();
+            self.events.changed(
+                "collection",
+                ContentAction::Created,
+                &oid.to_string(),
+                &id.to_string(),
+                created.as_ref(),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<CustomCollectionInput>,
+    ) -> mongodb::error::Result<Option<CustomCollection>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(collection) = &resp {
+            self.events.changed(
+                "collection",
+                ContentAction::Updated,
+                &collection.customer_id,
+                &collection.id,
+                Some(collection),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(
+        &self,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Option<CustomCollection>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(collection) = &resp {
+            self.events.changed(
+                "collection",
+                ContentAction::Deleted,
+                &collection.customer_id,
+                &collection.id,
+                Some(collection),
+            );
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: CustomEntryRepository + Send + Sync> CustomEntryRepository for EventedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        collection_oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<CustomEntry>> {
+        self.inner.find(limit, page, oid, collection_oid).await
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<CustomEntry>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<CustomEntryInput>,
+        oid: ObjectId,
+        collection_oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid, collection_oid).await?;
+        if let Some(id) = inserted_id(&resp) {
+            let created = self.inner.find_by_id(id).await.ok().flatten();
+            self.events.changed(
+                "entry",
+                ContentAction::Created,
+                &oid.to_string(),
+                &id.to_string(),
+                created.as_ref(),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<CustomEntryInput>,
+    ) -> mongodb::error::Result<Option<CustomEntry>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(entry) = &resp {
+            self.events.changed(
+                "entry",
+                ContentAction::Updated,
+                &entry.customer_id,
+                &entry.id,
+                Some(entry),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        collection_oid: ObjectId,
+        input: Json<Vec<CustomEntriesInput>>,
+    ) -> mongodb::error::Result<Option<Vec<CustomEntry>>> {
+        let resp = self.inner.update_many(collection_oid, input).await?;
+        for entry in resp.iter().flatten() {
+            self.events.changed(
+                "entry",
+                ContentAction::Updated,
+                &entry.customer_id,
+                &entry.id,
+                Some(entry),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<CustomEntry>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(entry) = &resp {
+            self.events.changed(
+                "entry",
+                ContentAction::Deleted,
+                &entry.customer_id,
+                &entry.id,
+                Some(entry),
+            );
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: EducationRepository + Send + Sync> EducationRepository for EventedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Education>> {
+        self.inner.find(limit, page, oid).await
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Education>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<EducationInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        if let Some(id) = inserted_id(&resp) {
+            let created = self.inner.find_by_id(id).await.ok().flatten();
+            self.events.changed(
+                "education",
+                ContentAction::Created,
+                &oid.to_string(),
+                &id.to_string(),
+                created.as_ref(),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<EducationInput>,
+    ) -> mongodb::error::Result<Option<Education>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(education) = &resp {
+            self.events.changed(
+                "education",
+                ContentAction::Updated,
+                &education.customer_id,
+                &education.id,
+                Some(education),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<EducationsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Education>>> {
+        let resp = self.inner.update_many(customer_oid, input).await?;
+        for education in resp.iter().flatten() {
+            self.events.changed(
+                "education",
+                ContentAction::Updated,
+                &education.customer_id,
+                &education.id,
+                Some(education),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Education>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(education) = &resp {
+            self.events.changed(
+                "education",
+                ContentAction::Deleted,
+                &education.customer_id,
+                &education.id,
+                Some(education),
+            );
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: ExperienceRepository + Send + Sync> ExperienceRepository for EventedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Experience>> {
+        self.inner.find(limit, page, oid).await
+    }
+
+    async fn find_fields(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        fields: Vec<String>,
+    ) -> mongodb::error::Result<Vec<Map<String, Value>>> {
+        self.inner.find_fields(limit, page, oid, fields).await
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Experience>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn find_by_skill(
+        &self,
+        oid: ObjectId,
+        skill_oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Experience>> {
+        self.inner.find_by_skill(oid, skill_oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<ExperienceInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        if let Some(id) = inserted_id(&resp) {
+            let created = self.inner.find_by_id(id).await.ok().flatten();
+            self.events.changed(
+                "experience",
+                ContentAction::Created,
+                &oid.to_string(),
+                &id.to_string(),
+                created.as_ref(),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<ExperienceInput>,
+    ) -> mongodb::error::Result<Option<Experience>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(experience) = &resp {
+            self.events.changed(
+                "experience",
+                ContentAction::Updated,
+                &experience.customer_id,
+                &experience.id,
+                Some(experience),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        input: Json<Vec<ExperiencesInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Experience>>> {
+        let resp = self.inner.update_many(input).await?;
+        for experience in resp.iter().flatten() {
+            self.events.changed(
+                "experience",
+                ContentAction::Updated,
+                &experience.customer_id,
+                &experience.id,
+                Some(experience),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Experience>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(experience) = &resp {
+            self.events.changed(
+                "experience",
+                ContentAction::Deleted,
+                &experience.customer_id,
+                &experience.id,
+                Some(experience),
+            );
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: ProjectRepository + Send + Sync> ProjectRepository for EventedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        filter: ProjectFilter,
+    ) -> mongodb::error::Result<Vec<Project>> {
+        self.inner.find(limit, page, oid, filter).await
+    }
+
+    async fn find_fields(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        fields: Vec<String>,
+        filter: ProjectFilter,
+    ) -> mongodb::error::Result<Vec<Map<String, Value>>> {
+        self.inner
+            .find_fields(limit, page, oid, fields, filter)
+            .await
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Project>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<ProjectInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        if let Some(id) = inserted_id(&resp) {
+            let created = self.inner.find_by_id(id).await.ok().flatten();
+            self.events.changed(
+                "project",
+                ContentAction::Created,
+                &oid.to_string(),
+                &id.to_string(),
+                created.as_ref(),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<ProjectInput>,
+    ) -> mongodb::error::Result<Option<Project>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(project) = &resp {
+            self.events.changed(
+                "project",
+                ContentAction::Updated,
+                &project.customer_id,
+                &project.id,
+                Some(project),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        input: Json<Vec<ProjectsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Project>>> {
+        let resp = self.inner.update_many(input).await?;
+        for project in resp.iter().flatten() {
+            self.events.changed(
+                "project",
+                ContentAction::Updated,
+                &project.customer_id,
+                &project.id,
+                Some(project),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Project>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(project) = &resp {
+            self.events.changed(
+                "project",
+                ContentAction::Deleted,
+                &project.customer_id,
+                &project.id,
+                Some(project),
+            );
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: ServiceRepository + Send + Sync> ServiceRepository for EventedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Service>> {
+        self.inner.find(limit, page, oid).await
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Service>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<ServiceInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        if let Some(id) = inserted_id(&resp) {
+            let created = self.inner.find_by_id(id).await.ok().flatten();
+            self.events.changed(
+                "service",
+                ContentAction::Created,
+                &oid.to_string(),
+                &id.to_string(),
+                created.as_ref(),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<ServiceInput>,
+    ) -> mongodb::error::Result<Option<Service>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(service) = &resp {
+            self.events.changed(
+                "service",
+                ContentAction::Updated,
+                &service.customer_id,
+                &service.id,
+                Some(service),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<ServicesInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Service>>> {
+        let resp = self.inner.update_many(customer_oid, input).await?;
+        for service in resp.iter().flatten() {
+            self.events.changed(
+                "service",
+                ContentAction::Updated,
+                &service.customer_id,
+                &service.id,
+                Some(service),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Service>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(service) = &resp {
+            self.events.changed(
+                "service",
+                ContentAction::Deleted,
+                &service.customer_id,
+                &service.id,
+                Some(service),
+            );
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: SkillRepository + Send + Sync> SkillRepository for EventedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Skill>> {
+        self.inner.find(limit, page, oid).await
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Skill>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<SkillInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        if let Some(id) = inserted_id(&resp) {
+            let created = self.inner.find_by_id(id).await.ok().flatten();
+            self.events.changed(
+                "skill",
+                ContentAction::Created,
+                &oid.to_string(),
+                &id.to_string(),
+                created.as_ref(),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<SkillInput>,
+    ) -> mongodb::error::Result<Option<Skill>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(skill) = &resp {
+            self.events.changed(
+                "skill",
+                ContentAction::Updated,
+                &skill.customer_id,
+                &skill.id,
+                Some(skill),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        input: Json<Vec<SkillsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Skill>>> {
+        let resp = self.inner.update_many(input).await?;
+        for skill in resp.iter().flatten() {
+            self.events.changed(
+                "skill",
+                ContentAction::Updated,
+                &skill.customer_id,
+                &skill.id,
+                Some(skill),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Skill>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(skill) = &resp {
+            self.events.changed(
+                "skill",
+                ContentAction::Deleted,
+                &skill.customer_id,
+                &skill.id,
+                Some(skill),
+            );
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: SocialRepository + Send + Sync> SocialRepository for EventedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Social>> {
+        self.inner.find(limit, page, oid).await
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Social>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<SocialInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid).await?;
+        if let Some(id) = inserted_id(&resp) {
+            let created = self.inner.find_by_id(id).await.ok().flatten();
+            self.events.changed(
+                "social",
+                ContentAction::Created,
+                &oid.to_string(),
+                &id.to_string(),
+                created.as_ref(),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<SocialInput>,
+    ) -> mongodb::error::Result<Option<Social>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(social) = &resp {
+            self.events.changed(
+                "social",
+                ContentAction::Updated,
+                &social.customer_id,
+                &social.id,
+                Some(social),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        input: Json<Vec<SocialsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Social>>> {
+        let resp = self.inner.update_many(input).await?;
+        for social in resp.iter().flatten() {
+            self.events.changed(
+                "social",
+                ContentAction::Updated,
+                &social.customer_id,
+                &social.id,
+                Some(social),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Social>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(social) = &resp {
+            self.events.changed(
+                "social",
+                ContentAction::Deleted,
+                &social.customer_id,
+                &social.id,
+                Some(social),
+            );
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl<R: TestimonialRepository + Send + Sync> TestimonialRepository for EventedRepo<R> {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        status: Option<TestimonialStatus>,
+    ) -> mongodb::error::Result<Vec<Testimonial>> {
+        self.inner.find(limit, page, oid, status).await
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Testimonial>> {
+        self.inner.find_by_id(oid).await
+    }
+
+    async fn insert(
+        &self,
+        input: Json<TestimonialSubmission>,
+        oid: ObjectId,
+        order: i32,
+    ) -> mongodb::error::Result<String> {
+        let resp = self.inner.insert(input, oid, order).await?;
+        if let Some(id) = inserted_id(&resp) {
+            let created = self.inner.find_by_id(id).await.ok().flatten();
+            self.events.changed_with_visibility(
+                "testimonial",
+                ContentAction::Created,
+                &oid.to_string(),
+                &id.to_string(),
+                created.as_ref(),
+                created.as_ref().is_some_and(is_approved),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<TestimonialInput>,
+    ) -> mongodb::error::Result<Option<Testimonial>> {
+        let resp = self.inner.update_by_id(oid, input).await?;
+        if let Some(testimonial) = &resp {
+            self.events.changed_with_visibility(
+                "testimonial",
+                ContentAction::Updated,
+                &testimonial.customer_id,
+                &testimonial.id,
+                Some(testimonial),
+                is_approved(testimonial),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_status(
+        &self,
+        oid: ObjectId,
+        status: TestimonialStatus,
+    ) -> mongodb::error::Result<Option<Testimonial>> {
+        let previous = self
+            .inner
+            .find_by_id(oid)
+            .await?
+            .map(|testimonial| testimonial.status);
+        let resp = self.inner.update_status(oid, status).await?;
+        if let Some(testimonial) = &resp {
+            // rejecting an approved testimonial takes it off the public pages
+            self.events.changed_with_visibility(
+                "testimonial",
+                ContentAction::Updated,
+                &testimonial.customer_id,
+                &testimonial.id,
+                Some(testimonial),
+                previous == Some(TestimonialStatus::Approved) || is_approved(testimonial),
+            );
+            if status == TestimonialStatus::Approved
+                && previous != Some(TestimonialStatus::Approved)
+            {
+                self.events.changed(
+                    "testimonial",
+                    ContentAction::Published,
+                    &testimonial.customer_id,
+                    &testimonial.id,
+                    Some(testimonial),
+                );
+            }
+        }
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<TestimonialsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Testimonial>>> {
+        let resp = self.inner.update_many(customer_oid, input).await?;
+        for testimonial in resp.iter().flatten() {
+            self.events.changed_with_visibility(
+                "testimonial",
+                ContentAction::Updated,
+                &testimonial.customer_id,
+                &testimonial.id,
+                Some(testimonial),
+                is_approved(testimonial),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Testimonial>> {
+        let resp = self.inner.delete_by_id(oid).await?;
+        if let Some(testimonial) = &resp {
+            self.events.changed_with_visibility(
+                "testimonial",
+                ContentAction::Deleted,
+                &testimonial.customer_id,
+                &testimonial.id,
+                Some(testimonial),
+                is_approved(testimonial),
+            );
+        }
+
+        Ok(resp)
+    }
+}
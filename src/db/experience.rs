@@ -2,14 +2,16 @@ use crate::models::experience::{
     Experience, ExperienceDocument, ExperienceInput, ExperiencesInput,
 };
 use crate::routes::traits::ExperienceRepository;
+use crate::utils::util::{document_to_json, fields_projection, parse_oids};
 use chrono::Utc;
 use futures::stream::TryStreamExt;
 use mongodb::{
-    bson::{doc, oid::ObjectId, Document},
+    bson::{doc, oid::ObjectId, to_bson, Document},
     options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
     Database,
 };
 use rocket::serde::json::Json;
+use serde_json::{Map, Value};
 
 pub struct ExperienceRepo {
     pub db: Database,
@@ -43,26 +45,41 @@ impl ExperienceRepository for ExperienceRepo {
         let mut resp: Vec<Experience> = vec![];
 
         while let Some(result) = cursor.try_next().await? {
-            let json_resp = Experience {
-                id: result.id.to_string(),
-                customer_id: result.customer_id.to_string(),
-                company: result.company,
-                work_type: result.work_type,
-                location: result.location,
-                start_date: result.start_date.to_string(),
-                end_date: Option::from(result.end_date.map(|d| d.to_string()).unwrap_or_default()),
-                position: result.position,
-                description: result.description,
-                order: result.order,
-                created_at: result.created_at.to_string(),
-            };
-
+            let json_resp = to_experience(result);
             resp.push(json_resp);
         }
 
         Ok(resp)
     }
 
+    async fn find_fields(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        fields: Vec<String>,
+    ) -> mongodb::error::Result<Vec<Map<String, Value>>> {
+        let collection = self.db.collection::<Document>("experience");
+
+        let filter = doc! { "customer_id": oid };
+
+        let find_options = FindOptions::builder()
+            .sort(doc! { "order": -1 })
+            .projection(fields_projection(&fields))
+            .limit(limit)
+            .skip(u64::try_from((page - 1) * limit).unwrap())
+            .build();
+
+        let mut cursor = collection.find(filter, find_options).await?;
+
+        let mut resp = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            resp.push(document_to_json(result));
+        }
+
+        Ok(resp)
+    }
+
     async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Experience>> {
         let collection = self.db.collection::<ExperienceDocument>("experience");
 
@@ -71,23 +88,32 @@ impl ExperienceRepository for ExperienceRepo {
         };
 
         // transform ObjectId to String
-        let resp = Experience {
-            id: result.id.to_string(),
-            customer_id: result.customer_id.to_string(),
-            company: result.company,
-            work_type: result.work_type,
-            location: result.location,
-            start_date: result.start_date.to_string(),
-            end_date: Option::from(result.end_date.map(|d| d.to_string()).unwrap_or_default()),
-            position: result.position,
-            description: result.description,
-            order: result.order,
-            created_at: result.created_at.to_string(),
-        };
+        let resp = to_experience(result);
 
         Ok(Some(resp))
     }
 
+    async fn find_by_skill(
+        &self,
+        oid: ObjectId,
+        skill_oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Experience>> {
+        let collection = self.db.collection::<ExperienceDocument>("experience");
+
+        let filter = doc! { "customer_id": oid, "skill_ids": skill_oid };
+
+        let find_options = FindOptions::builder().sort(doc! { "order": -1 }).build();
+
+        let mut cursor = collection.find(filter, find_options).await?;
+
+        let mut resp: Vec<Experience> = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            resp.push(to_experience(result));
+        }
+
+        Ok(resp)
+    }
+
     async fn insert(
         &self,
         input: Json<ExperienceInput>,
@@ -97,10 +123,10 @@ impl ExperienceRepository for ExperienceRepo {
 
         let created_at = mongodb::bson::DateTime::from_chrono(Utc::now());
         let start_date = mongodb::bson::DateTime::parse_rfc3339_str(&input.start_date).unwrap();
-        let end_date = match &input.end_date {
-            Some(s) => Some(mongodb::bson::DateTime::parse_rfc3339_str(s).unwrap()),
-            None => None,
-        };
+        let end_date = input
+            .end_date
+            .as_ref()
+            .map(|s| mongodb::bson::DateTime::parse_rfc3339_str(s).unwrap());
 
         let resp = collection
             .insert_one(
@@ -114,7 +140,11 @@ impl ExperienceRepository for ExperienceRepo {
                     "position": &input.position,
                     "description": &input.description,
                     "order": input.order,
+                    "skill_ids": parse_oids(input.skill_ids.as_deref()),
+                    "project_ids": parse_oids(input.project_ids.as_deref()),
+                    "translations": to_bson(&input.translations.clone().unwrap_or_default())?,
                     "created_at": created_at,
+                    "updated_at": created_at,
                 },
                 None,
             )
@@ -133,27 +163,10 @@ impl ExperienceRepository for ExperienceRepo {
             .return_document(ReturnDocument::After)
             .build();
 
-        let start_date = mongodb::bson::DateTime::parse_rfc3339_str(&input.start_date).unwrap();
-        let end_date = match &input.end_date {
-            Some(s) => Some(mongodb::bson::DateTime::parse_rfc3339_str(s).unwrap()),
-            None => None,
-        };
-
         let Some(result) = collection
             .find_one_and_update(
                 doc! {"_id":oid },
-                doc! {
-                    "$set": {
-                        "company": &input.company,
-                        "work_type": &input.work_type,
-                        "location": &input.location,
-                        "start_date": start_date,
-                        "end_date": end_date,
-                        "position": &input.position,
-                        "description": &input.description,
-                        "order": input.order,
-                    }
-                },
+                doc! { "$set": experience_update(&input)? },
                 find_one_and_update_options,
             )
             .await?
@@ -162,19 +175,7 @@ impl ExperienceRepository for ExperienceRepo {
         };
 
         // transform ObjectId to String
-        let resp = Experience {
-            id: result.id.to_string(),
-            customer_id: result.customer_id.to_string(),
-            company: result.company,
-            work_type: result.work_type,
-            location: result.location,
-            start_date: result.start_date.to_string(),
-            end_date: Option::from(result.end_date.map(|d| d.to_string()).unwrap_or_default()),
-            position: result.position,
-            description: result.description,
-            order: result.order,
-            created_at: result.created_at.to_string(),
-        };
+        let resp = to_experience(result);
 
         Ok(Some(resp))
     }
@@ -186,7 +187,7 @@ impl ExperienceRepository for ExperienceRepo {
         let collection = self.db.collection::<ExperienceDocument>("experience");
         let mut updated_projects = vec![];
 
-        for item in input.iter() {
+        for item in input.into_inner() {
             let oid = match ObjectId::parse_str(&item.id) {
                 Ok(oid) => oid,
                 Err(_) => continue, // skip invalid IDs
@@ -196,46 +197,15 @@ impl ExperienceRepository for ExperienceRepo {
                 .return_document(ReturnDocument::After)
                 .build();
 
-            let start_date = mongodb::bson::DateTime::parse_rfc3339_str(&item.start_date).unwrap();
-            let end_date = match &item.end_date {
-                Some(s) => Some(mongodb::bson::DateTime::parse_rfc3339_str(s).unwrap()),
-                None => None,
-            };
-
             if let Some(result) = collection
                 .find_one_and_update(
                     doc! { "_id": oid },
-                    doc! {
-                        "$set": {
-                            "company": &item.company,
-                            "work_type": &item.work_type,
-                            "location": &item.location,
-                            "start_date": start_date,
-                            "end_date": end_date,
-                            "position": &item.position,
-                            "description": &item.description,
-                            "order": item.order,
-                        }
-                    },
+                    doc! { "$set": experience_update(&ExperienceInput::from(item))? },
                     find_one_and_update_options,
                 )
                 .await?
             {
-                updated_projects.push(Experience {
-                    id: result.id.to_string(),
-                    customer_id: result.customer_id.to_string(),
-                    company: result.company,
-                    work_type: result.work_type,
-                    location: result.location,
-                    start_date: result.start_date.to_string(),
-                    end_date: Option::from(
-                        result.end_date.map(|d| d.to_string()).unwrap_or_default(),
-                    ),
-                    position: result.position,
-                    description: result.description,
-                    order: result.order,
-                    created_at: result.created_at.to_string(),
-                });
+                updated_projects.push(to_experience(result));
             }
         }
 
@@ -258,20 +228,62 @@ impl ExperienceRepository for ExperienceRepo {
         };
 
         // transform ObjectId to String
-        let resp = Experience {
-            id: result.id.to_string(),
-            customer_id: result.customer_id.to_string(),
-            company: result.company,
-            work_type: result.work_type,
-            location: result.location,
-            start_date: result.start_date.to_string(),
-            end_date: Option::from(result.end_date.map(|d| d.to_string()).unwrap_or_default()),
-            position: result.position,
-            description: result.description,
-            order: result.order,
-            created_at: result.created_at.to_string(),
-        };
+        let resp = to_experience(result);
 
         Ok(Some(resp))
     }
 }
+
+// transform ObjectId to String
+fn to_experience(result: ExperienceDocument) -> Experience {
+    Experience {
+        id: result.id.to_string(),
+        customer_id: result.customer_id.to_string(),
+        company: result.company,
+        work_type: result.work_type,
+        location: result.location,
+        start_date: result.start_date.to_string(),
+        end_date: Option::from(result.end_date.map(|d| d.to_string()).unwrap_or_default()),
+        position: result.position,
+        description: result.description,
+        order: result.order,
+        skill_ids: result.skill_ids.iter().map(|id| id.to_string()).collect(),
+        project_ids: result.project_ids.iter().map(|id| id.to_string()).collect(),
+        translations: result.translations,
+        created_at: result.created_at.to_string(),
+        updated_at: result.updated_at.map(|d| d.to_string()),
+    }
+}
+
+/// `$set` of an experience update. `skill_ids`, `project_ids` and `translations` are only set
+/// when they are sent, the dashboard does not send them.
+pub fn experience_update(input: &ExperienceInput) -> mongodb::error::Result<Document> {
+    let start_date = mongodb::bson::DateTime::parse_rfc3339_str(&input.start_date).unwrap();
+    let end_date = input
+        .end_date
+        .as_ref()
+        .map(|s| mongodb::bson::DateTime::parse_rfc3339_str(s).unwrap());
+
+    let mut set = doc! {
+        "company": &input.company,
+        "work_type": &input.work_type,
+        "location": &input.location,
+        "start_date": start_date,
+        "end_date": end_date,
+        "position": &input.position,
+        "description": &input.description,
+        "order": input.order,
+        "updated_at": mongodb::bson::DateTime::now(),
+    };
+    if let Some(skill_ids) = input.skill_ids.as_deref() {
+        set.insert("skill_ids", parse_oids(Some(skill_ids)));
+    }
+    if let Some(project_ids) = input.project_ids.as_deref() {
+        set.insert("project_ids", parse_oids(Some(project_ids)));
+    }
+    if let Some(translations) = &input.translations {
+        set.insert("translations", to_bson(translations)?);
+    }
+
+    Ok(set)
+}
@@ -0,0 +1,162 @@
+use crate::models::media::{Media, MediaDocument, MediaInput, MediaVariantUrl};
+use crate::routes::traits::MediaRepository;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, to_bson, Document},
+    options::FindOptions,
+    Database,
+};
+use std::env;
+
+pub struct MediaRepo {
+    pub db: Database,
+}
+
+impl MediaRepo {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+/// Public url of a file in the media store.
+pub fn media_url(key: &str) -> String {
+    let base_url = env::var("MEDIA_BASE_URL").unwrap_or_else(|_| "/media".to_string());
+    format!("{}/{}", base_url.trim_end_matches('/'), key)
+}
+
+/// Media of the customer referenced by `media_id`, used to fill `photo_link`/`profile_picture`
+/// and their `srcset`/blurhash.
+pub async fn find_media(
+    db: &Database,
+    customer_oid: ObjectId,
+    media_id: Option<ObjectId>,
+) -> mongodb::error::Result<Option<Media>> {
+    let Some(media_id) = media_id else {
+        return Ok(None);
+    };
+
+    let collection = db.collection::<MediaDocument>("media");
+    let resp = collection
+        .find_one(doc! {"_id": media_id, "customer_id": customer_oid }, None)
+        .await?
+        .map(to_media);
+
+    Ok(resp)
+}
+
+fn to_media(result: MediaDocument) -> Media {
+    let variants: Vec<MediaVariantUrl> = result
+        .variants
+        .iter()
+        .map(|variant| MediaVariantUrl {
+            width: variant.width,
+            height: variant.height,
+            key: variant.key.clone(),
+            url: media_url(&variant.key),
+        })
+        .collect();
+    let srcset = (!variants.is_empty()).then(|| {
+        variants
+            .iter()
+            .map(|variant| format!("{} {}w", variant.url, variant.width))
+            .collect::<Vec<String>>()
+            .join(", ")
+    });
+
+    // transform ObjectId to String
+    Media {
+        id: result.id.to_string(),
+        customer_id: result.customer_id.to_string(),
+        file_name: result.file_name,
+        url: media_url(&result.key),
+        key: result.key,
+        mime_type: result.mime_type,
+        size: result.size,
+        width: result.width,
+        height: result.height,
+        variants,
+        srcset,
+        blurhash: result.blurhash,
+        created_at: result.created_at.to_string(),
+    }
+}
+
+#[async_trait]
+impl MediaRepository for MediaRepo {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Media>> {
+        let collection = self.db.collection::<MediaDocument>("media");
+
+        let filter = doc! { "customer_id": oid };
+
+        let find_options = FindOptions::builder()
+            .sort(doc! { "created_at": -1 })
+            .limit(limit)
+            .skip(u64::try_from((page - 1) * limit).unwrap())
+            .build();
+
+        let mut cursor = collection.find(filter, find_options).await?;
+
+        let mut resp: Vec<Media> = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            resp.push(to_media(result));
+        }
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Media>> {
+        let collection = self.db.collection::<MediaDocument>("media");
+
+        let Some(result) = collection.find_one(doc! {"_id":oid }, None).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(to_media(result)))
+    }
+
+    async fn insert(&self, input: MediaInput, oid: ObjectId) -> mongodb::error::Result<String> {
+        let collection = self.db.collection::<Document>("media");
+
+        let created_at = mongodb::bson::DateTime::from_chrono(Utc::now());
+
+        let resp = collection
+            .insert_one(
+                doc! {
+                    "_id": input.id,
+                    "customer_id": oid,
+                    "file_name": &input.file_name,
+                    "key": &input.key,
+                    "mime_type": &input.mime_type,
+                    "size": input.size,
+                    "width": input.width,
+                    "height": input.height,
+                    "variants": to_bson(&input.variants)?,
+                    "blurhash": &input.blurhash,
+                    "created_at": created_at,
+                },
+                None,
+            )
+            .await?;
+
+        Ok(resp.inserted_id.to_string())
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Media>> {
+        let collection = self.db.collection::<MediaDocument>("media");
+
+        let Some(result) = collection
+            .find_one_and_delete(doc! {"_id":oid }, None)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(to_media(result)))
+    }
+}
@@ -1,13 +1,32 @@
 // use mongodb::bson::{doc, Document};
-use mongodb::{options::ClientOptions, Client, Database};
+use mongodb::{
+    bson::{doc, oid::ObjectId, Document},
+    error::{ErrorKind, WriteFailure},
+    options::{ClientOptions, FindOneOptions},
+    Client, Database,
+};
 // use rocket::fairing::AdHoc;
 use std::env;
 
+pub mod archive;
+pub mod article;
+pub mod cache;
+pub mod certification;
+pub mod collection;
+pub mod contact;
 pub mod customer;
+pub mod cv;
+pub mod education;
+pub mod events;
 pub mod experience;
+pub mod media;
 pub mod project;
+pub mod search;
+pub mod service;
 pub mod skill;
 pub mod social;
+pub mod testimonial;
+pub mod webhook;
 
 // pub fn init() -> AdHoc {
 //     AdHoc::on_ignite("Connecting to MongoDB", |rocket| async {
@@ -34,3 +53,30 @@ pub async fn connect() -> mongodb::error::Result<Database> {
 
     Ok(database)
 }
+
+/// Customer owning the document `oid` of `collection`.
+pub async fn owner_of(
+    db: &Database,
+    collection: &str,
+    oid: ObjectId,
+) -> mongodb::error::Result<Option<ObjectId>> {
+    let find_one_options = FindOneOptions::builder()
+        .projection(doc! { "customer_id": 1 })
+        .build();
+    let resp = db
+        .collection::<Document>(collection)
+        .find_one(doc! { "_id": oid }, find_one_options)
+        .await?;
+
+    Ok(resp.and_then(|document| document.get_object_id("customer_id").ok()))
+}
+
+/// Whether `error` is a write rejected by a unique index.
+pub fn is_duplicate_key(error: &mongodb::error::Error) -> bool {
+    const DUPLICATE_KEY: i32 = 11000;
+    match error.kind.as_ref() {
+        ErrorKind::Write(WriteFailure::WriteError(error)) => error.code == DUPLICATE_KEY,
+        ErrorKind::Command(error) => error.code == DUPLICATE_KEY,
+        _ => false,
+    }
+}
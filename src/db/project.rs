@@ -1,13 +1,20 @@
-use crate::models::project::{Project, ProjectDocument, ProjectInput, ProjectsInput};
+use crate::db::media::find_media;
+use crate::models::media::Media;
+use crate::models::project::{
+    Project, ProjectDocument, ProjectFilter, ProjectImage, ProjectImageDocument, ProjectImageInput,
+    ProjectInput, ProjectsInput,
+};
 use crate::routes::traits::ProjectRepository;
+use crate::utils::util::{document_to_json, fields_projection, parse_date, parse_oids};
 use chrono::Utc;
 use futures::stream::TryStreamExt;
 use mongodb::{
-    bson::{doc, oid::ObjectId, Document},
+    bson::{doc, oid::ObjectId, to_bson, Bson, Document},
     options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
     Database,
 };
 use rocket::serde::json::Json;
+use serde_json::{Map, Value};
 
 pub struct ProjectRepo {
     pub db: Database,
@@ -26,10 +33,11 @@ impl ProjectRepository for ProjectRepo {
         limit: i64,
         page: i64,
         oid: ObjectId,
+        filter: ProjectFilter,
     ) -> mongodb::error::Result<Vec<Project>> {
         let collection = self.db.collection::<ProjectDocument>("project");
 
-        let filter = doc! { "customer_id": oid };
+        let filter = filter_document(oid, &filter);
 
         let find_options = FindOptions::builder()
             .sort(doc! { "order": -1 })
@@ -42,23 +50,42 @@ impl ProjectRepository for ProjectRepo {
         let mut resp: Vec<Project> = vec![];
         while let Some(result) = cursor.try_next().await? {
             // transform ObjectId to String
-            let json_resp = Project {
-                id: result.id.to_string(),
-                customer_id: result.customer_id.to_string(),
-                name: result.name,
-                description: result.description,
-                link: result.link,
-                photo_link: result.photo_link,
-                order: result.order,
-                stack: result.stack,
-                created_at: result.created_at.to_string(),
-            };
+            let json_resp = to_project(result);
             resp.push(json_resp);
         }
 
         Ok(resp)
     }
 
+    async fn find_fields(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        fields: Vec<String>,
+        filter: ProjectFilter,
+    ) -> mongodb::error::Result<Vec<Map<String, Value>>> {
+        let collection = self.db.collection::<Document>("project");
+
+        let filter = filter_document(oid, &filter);
+
+        let find_options = FindOptions::builder()
+            .sort(doc! { "order": -1 })
+            .projection(fields_projection(&fields))
+            .limit(limit)
+            .skip(u64::try_from((page - 1) * limit).unwrap())
+            .build();
+
+        let mut cursor = collection.find(filter, find_options).await?;
+
+        let mut resp = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            resp.push(document_to_json(result));
+        }
+
+        Ok(resp)
+    }
+
     async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Project>> {
         let collection = self.db.collection::<ProjectDocument>("project");
 
@@ -67,17 +94,7 @@ impl ProjectRepository for ProjectRepo {
         };
 
         // transform ObjectId to String
-        let resp = Project {
-            id: result.id.to_string(),
-            customer_id: result.customer_id.to_string(),
-            name: result.name,
-            description: result.description,
-            link: result.link,
-            photo_link: result.photo_link,
-            order: result.order,
-            stack: result.stack,
-            created_at: result.created_at.to_string(),
-        };
+        let resp = to_project(result);
 
         Ok(Some(resp))
     }
@@ -90,6 +107,20 @@ impl ProjectRepository for ProjectRepo {
         let collection = self.db.collection::<Document>("project");
 
         let created_at = mongodb::bson::DateTime::from_chrono(Utc::now());
+        let photo_id = input
+            .photo_id
+            .as_deref()
+            .and_then(|id| ObjectId::parse_str(id).ok());
+        let media = find_media(&self.db, oid, photo_id).await?;
+        let photo_link = media
+            .as_ref()
+            .map(|media| media.url.clone())
+            .or_else(|| input.photo_link.clone());
+        let photo_srcset = media.as_ref().and_then(|media| media.srcset.clone());
+        let photo_blurhash = media.and_then(|media| media.blurhash);
+        let gallery = gallery_documents(&self.db, oid, input.gallery.as_deref()).await?;
+        let start_date = parse_date(input.start_date.as_deref())?;
+        let end_date = parse_date(input.end_date.as_deref())?;
 
         let resp = collection
             .insert_one(
@@ -98,10 +129,25 @@ impl ProjectRepository for ProjectRepo {
                     "name": &input.name,
                     "description": &input.description,
                     "link": &input.link,
-                    "photo_link": &input.photo_link,
+                    "photo_link": &photo_link,
+                    "photo_id": photo_id,
+                    "photo_srcset": &photo_srcset,
+                    "photo_blurhash": &photo_blurhash,
+                    "gallery": to_bson(&gallery)?,
+                    "links": to_bson(&input.links.clone().unwrap_or_default())?,
+                    "role": &input.role,
+                    "team_size": input.team_size,
+                    "start_date": start_date,
+                    "end_date": end_date,
+                    "featured": input.featured.unwrap_or(false),
+                    "client": &input.client,
                     "order": input.order,
                     "stack": &input.stack,
+                    "skill_ids": parse_oids(input.skill_ids.as_deref()),
+                    "translations": to_bson(&input.translations.clone().unwrap_or_default())?,
+                    "seo": to_bson(&input.seo)?,
                     "created_at": created_at,
+                    "updated_at": created_at,
                 },
                 None,
             )
@@ -120,19 +166,15 @@ impl ProjectRepository for ProjectRepo {
             .return_document(ReturnDocument::After)
             .build();
 
+        let Some(existing) = collection.find_one(doc! {"_id":oid }, None).await? else {
+            return Ok(None);
+        };
+        let set = update_document(&self.db, &existing, &input).await?;
+
         let Some(result) = collection
             .find_one_and_update(
                 doc! {"_id":oid },
-                doc! {
-                    "$set": {
-                        "name": &input.name,
-                        "description": &input.description,
-                        "link": &input.link,
-                        "photo_link": &input.photo_link,
-                        "order": input.order,
-                        "stack": &input.stack,
-                    }
-                },
+                doc! { "$set": set },
                 find_one_and_update_options,
             )
             .await?
@@ -141,17 +183,7 @@ impl ProjectRepository for ProjectRepo {
         };
 
         // transform ObjectId to String
-        let resp = Project {
-            id: result.id.to_string(),
-            customer_id: result.customer_id.to_string(),
-            name: result.name,
-            description: result.description,
-            link: result.link,
-            photo_link: result.photo_link,
-            order: result.order,
-            stack: result.stack,
-            created_at: result.created_at.to_string(),
-        };
+        let resp = to_project(result);
 
         Ok(Some(resp))
     }
@@ -163,7 +195,7 @@ impl ProjectRepository for ProjectRepo {
         let collection = self.db.collection::<ProjectDocument>("project");
         let mut updated_projects = vec![];
 
-        for item in input.iter() {
+        for item in input.into_inner() {
             let oid = match ObjectId::parse_str(&item.id) {
                 Ok(oid) => oid,
                 Err(_) => continue, // skip invalid IDs
@@ -173,34 +205,20 @@ impl ProjectRepository for ProjectRepo {
                 .return_document(ReturnDocument::After)
                 .build();
 
+            let Some(existing) = collection.find_one(doc! { "_id": oid }, None).await? else {
+                continue;
+            };
+            let set = update_document(&self.db, &existing, &ProjectInput::from(item)).await?;
+
             if let Some(result) = collection
                 .find_one_and_update(
                     doc! { "_id": oid },
-                    doc! {
-                        "$set": {
-                            "name": &item.name,
-                            "description": &item.description,
-                            "link": &item.link,
-                            "photo_link": &item.photo_link,
-                            "order": item.order,
-                            "stack": &item.stack,
-                        }
-                    },
+                    doc! { "$set": set },
                     find_one_and_update_options,
                 )
                 .await?
             {
-                updated_projects.push(Project {
-                    id: result.id.to_string(),
-                    customer_id: result.customer_id.to_string(),
-                    name: result.name,
-                    description: result.description,
-                    link: result.link,
-                    photo_link: result.photo_link,
-                    order: result.order,
-                    stack: result.stack,
-                    created_at: result.created_at.to_string(),
-                });
+                updated_projects.push(to_project(result));
             }
         }
 
@@ -223,18 +241,192 @@ impl ProjectRepository for ProjectRepo {
         };
 
         // transform ObjectId to String
-        let resp = Project {
-            id: result.id.to_string(),
-            customer_id: result.customer_id.to_string(),
-            name: result.name,
-            description: result.description,
-            link: result.link,
-            photo_link: result.photo_link,
-            order: result.order,
-            stack: result.stack,
-            created_at: result.created_at.to_string(),
-        };
+        let resp = to_project(result);
 
         Ok(Some(resp))
     }
 }
+
+// transform ObjectId to String
+fn to_project(result: ProjectDocument) -> Project {
+    Project {
+        id: result.id.to_string(),
+        customer_id: result.customer_id.to_string(),
+        name: result.name,
+        description: result.description,
+        link: result.link,
+        photo_link: result.photo_link,
+        photo_id: result.photo_id.map(|id| id.to_string()),
+        photo_srcset: result.photo_srcset,
+        photo_blurhash: result.photo_blurhash,
+        gallery: result
+            .gallery
+            .into_iter()
+            .map(|image| ProjectImage {
+                media_id: image.media_id.to_string(),
+                url: image.url,
+                srcset: image.srcset,
+                blurhash: image.blurhash,
+                caption: image.caption,
+            })
+            .collect(),
+        links: result.links,
+        role: result.role,
+        team_size: result.team_size,
+        start_date: result.start_date.map(|d| d.to_string()),
+        end_date: result.end_date.map(|d| d.to_string()),
+        featured: result.featured,
+        client: result.client,
+        order: result.order,
+        stack: result.stack,
+        skill_ids: result
+            .skill_ids
+            .into_iter()
+            .map(|id| id.to_string())
+            .collect(),
+        translations: result.translations,
+        seo: result.seo,
+        created_at: result.created_at.to_string(),
+        updated_at: result.updated_at.map(|d| d.to_string()),
+    }
+}
+
+fn filter_document(oid: ObjectId, filter: &ProjectFilter) -> Document {
+    let mut document = doc! { "customer_id": oid };
+    if let Some(featured) = filter.featured {
+        // Projects saved before `featured` existed are not featured.
+        document.insert(
+            "featured",
+            if featured {
+                Bson::Boolean(true)
+            } else {
+                Bson::Document(doc! { "$ne": true })
+            },
+        );
+    }
+    if !filter.stack.is_empty() {
+        document.insert("stack", doc! { "$all": &filter.stack });
+    }
+    if let Some(skill_id) = filter.skill_id {
+        document.insert("skill_ids", skill_id);
+    }
+
+    document
+}
+
+// Resolve the media of an update, then build its `$set`.
+async fn update_document(
+    db: &Database,
+    existing: &ProjectDocument,
+    input: &ProjectInput,
+) -> mongodb::error::Result<Document> {
+    let photo_id = input
+        .photo_id
+        .as_deref()
+        .and_then(|id| ObjectId::parse_str(id).ok());
+    let media = find_media(db, existing.customer_id, photo_id).await?;
+    let gallery = match input.gallery.as_deref() {
+        Some(gallery) => Some(gallery_documents(db, existing.customer_id, Some(gallery)).await?),
+        None => None,
+    };
+
+    project_update(input, existing.photo_link.as_deref(), media, gallery)
+}
+
+/// `$set` of a project update, `media` and `gallery` being the media resolved for the input.
+/// <br />Past the fields of the dashboard form, a field is only set when it is sent, so that a
+/// client which does not know it leaves it as it is. Without `photo_id` the uploaded photo is
+/// only unlinked when `photo_link` no longer points to it.
+pub fn project_update(
+    input: &ProjectInput,
+    stored_photo_link: Option<&str>,
+    media: Option<Media>,
+    gallery: Option<Vec<ProjectImageDocument>>,
+) -> mongodb::error::Result<Document> {
+    let photo_link = media
+        .as_ref()
+        .map(|media| media.url.clone())
+        .or_else(|| input.photo_link.clone());
+
+    let mut set = doc! {
+        "name": &input.name,
+        "description": &input.description,
+        "link": &input.link,
+        "photo_link": &photo_link,
+        "order": input.order,
+        "stack": &input.stack,
+        "updated_at": mongodb::bson::DateTime::now(),
+    };
+    if input.photo_id.is_some() || photo_link.as_deref() != stored_photo_link {
+        let photo_id = input
+            .photo_id
+            .as_deref()
+            .and_then(|id| ObjectId::parse_str(id).ok());
+        set.insert("photo_id", photo_id);
+        set.insert(
+            "photo_srcset",
+            media.as_ref().and_then(|media| media.srcset.clone()),
+        );
+        set.insert("photo_blurhash", media.and_then(|media| media.blurhash));
+    }
+    if let Some(gallery) = gallery {
+        set.insert("gallery", to_bson(&gallery)?);
+    }
+    if let Some(links) = &input.links {
+        set.insert("links", to_bson(links)?);
+    }
+    if let Some(role) = &input.role {
+        set.insert("role", role);
+    }
+    if let Some(team_size) = input.team_size {
+        set.insert("team_size", team_size);
+    }
+    if let Some(start_date) = parse_date(input.start_date.as_deref())? {
+        set.insert("start_date", start_date);
+    }
+    if let Some(end_date) = parse_date(input.end_date.as_deref())? {
+        set.insert("end_date", end_date);
+    }
+    if let Some(featured) = input.featured {
+        set.insert("featured", featured);
+    }
+    if let Some(client) = &input.client {
+        set.insert("client", client);
+    }
+    if let Some(skill_ids) = input.skill_ids.as_deref() {
+        set.insert("skill_ids", parse_oids(Some(skill_ids)));
+    }
+    if let Some(translations) = &input.translations {
+        set.insert("translations", to_bson(translations)?);
+    }
+    if let Some(seo) = &input.seo {
+        set.insert("seo", to_bson(seo)?);
+    }
+
+    Ok(set)
+}
+
+// Resolve the gallery media of the customer, unknown ids are skipped.
+async fn gallery_documents(
+    db: &Database,
+    customer_oid: ObjectId,
+    gallery: Option<&[ProjectImageInput]>,
+) -> mongodb::error::Result<Vec<ProjectImageDocument>> {
+    let mut resp = vec![];
+    for image in gallery.unwrap_or_default() {
+        let Ok(media_id) = ObjectId::parse_str(&image.media_id) else {
+            continue;
+        };
+        if let Some(media) = find_media(db, customer_oid, Some(media_id)).await? {
+            resp.push(ProjectImageDocument {
+                media_id,
+                url: media.url,
+                srcset: media.srcset,
+                blurhash: media.blurhash,
+                caption: image.caption.clone(),
+            });
+        }
+    }
+
+    Ok(resp)
+}
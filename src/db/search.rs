@@ -0,0 +1,209 @@
+use crate::models::search::{SearchHit, SearchKind};
+use crate::routes::traits::SearchRepository;
+use crate::utils::highlight::{highlight, search_terms};
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, Bson, Document},
+    options::{FindOptions, IndexOptions},
+    Database, IndexModel,
+};
+
+// Hits returned per content type, relevance drops quickly after that.
+const SOURCE_LIMIT: i64 = 50;
+// Characters of a snippet.
+const SNIPPET_WIDTH: usize = 160;
+
+// A collection searched with its text index.
+struct Source {
+    kind: SearchKind,
+    collection: &'static str,
+    // field used as the title of a hit
+    title: &'static str,
+    // indexed fields with their weight, in the order a snippet is looked for
+    fields: &'static [(&'static str, i32)],
+}
+
+const SOURCES: [Source; 6] = [
+    Source {
+        kind: SearchKind::Profile,
+        collection: "customer",
+        title: "name",
+        fields: &[("intro", 5), ("about", 1), ("name", 10)],
+    },
+    Source {
+        kind: SearchKind::Project,
+        collection: "project",
+        title: "name",
+        fields: &[
+            ("description", 1),
+            ("stack", 5),
+            ("role", 3),
+            ("client", 3),
+            ("name", 10),
+        ],
+    },
+    Source {
+        kind: SearchKind::Experience,
+        collection: "experience",
+        title: "position",
+        fields: &[
+            ("description", 1),
+            ("company", 5),
+            ("location", 1),
+            ("position", 10),
+        ],
+    },
+    Source {
+        kind: SearchKind::Skill,
+        collection: "skill",
+        title: "name",
+        fields: &[("category", 3), ("name", 10)],
+    },
+    Source {
+        kind: SearchKind::Social,
+        collection: "social",
+        title: "name",
+        fields: &[("name", 10)],
+    },
+    Source {
+        kind: SearchKind::Article,
+        collection: "article",
+        title: "title",
+        fields: &[("excerpt", 3), ("body", 1), ("tags", 5), ("title", 10)],
+    },
+];
+
+pub struct SearchRepo {
+    pub db: Database,
+}
+
+impl SearchRepo {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+/// Create the text index of every searched collection, MongoDB keeps them up to date on writes.
+pub async fn ensure_indexes(db: &Database) -> mongodb::error::Result<()> {
+    for source in &SOURCES {
+        let mut keys = Document::new();
+        // the profile is looked up by `_id`, everything else by customer
+        if source.kind != SearchKind::Profile {
+            keys.insert("customer_id", 1);
+        }
+        let mut weights = Document::new();
+        for (field, weight) in source.fields {
+            keys.insert(*field, "text");
+            weights.insert(*field, *weight);
+        }
+
+        let index = IndexModel::builder()
+            .keys(keys)
+            .options(
+                IndexOptions::builder()
+                    .name("search".to_string())
+                    .weights(weights)
+                    .build(),
+            )
+            .build();
+        db.collection::<Document>(source.collection)
+            .create_index(index, None)
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl SearchRepository for SearchRepo {
+    async fn search(
+        &self,
+        oid: ObjectId,
+        query: &str,
+        published_only: bool,
+    ) -> mongodb::error::Result<Vec<SearchHit>> {
+        let terms = search_terms(query);
+
+        let searches = SOURCES.iter().map(|source| {
+            let terms = &terms;
+            async move {
+                let mut filter = match source.kind {
+                    SearchKind::Profile => doc! { "_id": oid },
+                    _ => doc! { "customer_id": oid },
+                };
+                filter.insert("$text", doc! { "$search": query });
+                if published_only && source.kind == SearchKind::Article {
+                    filter.insert(
+                        "published_at",
+                        doc! { "$lte": mongodb::bson::DateTime::now() },
+                    );
+                }
+
+                let mut projection = doc! {
+                    "score": { "$meta": "textScore" },
+                    source.title: 1,
+                };
+                for (field, _) in source.fields {
+                    projection.insert(*field, 1);
+                }
+
+                let find_options = FindOptions::builder()
+                    .projection(projection)
+                    .sort(doc! { "score": { "$meta": "textScore" } })
+                    .limit(SOURCE_LIMIT)
+                    .build();
+
+                let mut cursor = self
+                    .db
+                    .collection::<Document>(source.collection)
+                    .find(filter, find_options)
+                    .await?;
+
+                let mut hits = vec![];
+                while let Some(result) = cursor.try_next().await? {
+                    hits.push(to_hit(source, &result, terms));
+                }
+
+                Ok::<_, mongodb::error::Error>(hits)
+            }
+        });
+
+        let mut hits: Vec<SearchHit> = futures::future::try_join_all(searches)
+            .await?
+            .into_iter()
+            .flatten()
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+        Ok(hits)
+    }
+}
+
+fn to_hit(source: &Source, result: &Document, terms: &[String]) -> SearchHit {
+    // the first field, in `source.fields` order, showing a term
+    let snippet = source.fields.iter().find_map(|(field, _)| {
+        let text = match result.get(*field)? {
+            Bson::String(text) => text.clone(),
+            Bson::Array(items) => items
+                .iter()
+                .filter_map(Bson::as_str)
+                .collect::<Vec<_>>()
+                .join(" · "),
+            _ => return None,
+        };
+        highlight(&text, terms, SNIPPET_WIDTH).map(|snippet| (field.to_string(), snippet))
+    });
+    let (field, snippet) = snippet.unzip();
+
+    SearchHit {
+        kind: source.kind,
+        id: result
+            .get_object_id("_id")
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        title: result.get_str(source.title).unwrap_or_default().to_string(),
+        field,
+        snippet,
+        score: result.get_f64("score").unwrap_or_default(),
+    }
+}
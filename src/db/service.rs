@@ -0,0 +1,237 @@
+use crate::models::service::{Service, ServiceDocument, ServiceInput, ServicesInput};
+use crate::routes::traits::ServiceRepository;
+use crate::utils::money::format_price;
+use crate::utils::util::parse_oids;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, Document},
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Database,
+};
+use rocket::serde::json::Json;
+
+// Currency of services saved without one.
+const DEFAULT_CURRENCY: &str = "USD";
+
+pub struct ServiceRepo {
+    pub db: Database,
+}
+
+impl ServiceRepo {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl ServiceRepository for ServiceRepo {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Service>> {
+        let collection = self.db.collection::<ServiceDocument>("service");
+
+        let filter = doc! { "customer_id": oid };
+
+        let find_options = FindOptions::builder()
+            .sort(doc! { "order": -1 })
+            .limit(limit)
+            .skip(u64::try_from((page - 1) * limit).unwrap())
+            .build();
+
+        let mut cursor = collection.find(filter, find_options).await?;
+
+        let mut resp: Vec<Service> = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            resp.push(to_service(result));
+        }
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Service>> {
+        let collection = self.db.collection::<ServiceDocument>("service");
+
+        let resp = collection
+            .find_one(doc! {"_id":oid }, None)
+            .await?
+            .map(to_service);
+
+        Ok(resp)
+    }
+
+    async fn insert(
+        &self,
+        input: Json<ServiceInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String> {
+        let collection = self.db.collection::<Document>("service");
+
+        let created_at = mongodb::bson::DateTime::from_chrono(Utc::now());
+
+        let resp = collection
+            .insert_one(
+                doc! {
+                    "customer_id": oid,
+                    "name": &input.name,
+                    "description": &input.description,
+                    "deliverables": input.deliverables.clone().unwrap_or_default(),
+                    "price_min": input.price_min,
+                    "price_max": input.price_max,
+                    "currency": currency(input.currency.as_deref()),
+                    "price_unit": &input.price_unit,
+                    "cta_label": &input.cta_label,
+                    "cta_link": &input.cta_link,
+                    "project_ids": parse_oids(input.project_ids.as_deref()),
+                    "order": input.order,
+                    "created_at": created_at,
+                    "updated_at": created_at,
+                },
+                None,
+            )
+            .await?;
+
+        Ok(resp.inserted_id.to_string())
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<ServiceInput>,
+    ) -> mongodb::error::Result<Option<Service>> {
+        let collection = self.db.collection::<ServiceDocument>("service");
+        let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let resp = collection
+            .find_one_and_update(
+                doc! {"_id":oid },
+                doc! {
+                    "$set": {
+                        "name": &input.name,
+                        "description": &input.description,
+                        "deliverables": input.deliverables.clone().unwrap_or_default(),
+                        "price_min": input.price_min,
+                        "price_max": input.price_max,
+                        "currency": currency(input.currency.as_deref()),
+                        "price_unit": &input.price_unit,
+                        "cta_label": &input.cta_label,
+                        "cta_link": &input.cta_link,
+                        "project_ids": parse_oids(input.project_ids.as_deref()),
+                        "order": input.order,
+                        "updated_at": mongodb::bson::DateTime::now(),
+                    }
+                },
+                find_one_and_update_options,
+            )
+            .await?
+            .map(to_service);
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<ServicesInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Service>>> {
+        let collection = self.db.collection::<ServiceDocument>("service");
+        let mut updated_services = vec![];
+
+        for item in input.iter() {
+            let oid = match ObjectId::parse_str(&item.id) {
+                Ok(oid) => oid,
+                Err(_) => continue, // skip invalid IDs
+            };
+
+            let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+                .return_document(ReturnDocument::After)
+                .build();
+
+            if let Some(result) = collection
+                .find_one_and_update(
+                    doc! { "_id": oid, "customer_id": customer_oid },
+                    doc! {
+                        "$set": {
+                            "name": &item.name,
+                            "description": &item.description,
+                            "deliverables": item.deliverables.clone().unwrap_or_default(),
+                            "price_min": item.price_min,
+                            "price_max": item.price_max,
+                            "currency": currency(item.currency.as_deref()),
+                            "price_unit": &item.price_unit,
+                            "cta_label": &item.cta_label,
+                            "cta_link": &item.cta_link,
+                            "project_ids": parse_oids(item.project_ids.as_deref()),
+                            "order": item.order,
+                            "updated_at": mongodb::bson::DateTime::now(),
+                        }
+                    },
+                    find_one_and_update_options,
+                )
+                .await?
+            {
+                updated_services.push(to_service(result));
+            }
+        }
+
+        if updated_services.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(updated_services))
+        }
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Service>> {
+        let collection = self.db.collection::<ServiceDocument>("service");
+
+        let resp = collection
+            .find_one_and_delete(doc! {"_id":oid }, None)
+            .await?
+            .map(to_service);
+
+        Ok(resp)
+    }
+}
+
+fn currency(currency: Option<&str>) -> String {
+    currency
+        .map(|currency| currency.trim().to_uppercase())
+        .filter(|currency| !currency.is_empty())
+        .unwrap_or_else(|| DEFAULT_CURRENCY.to_string())
+}
+
+// transform ObjectId to String
+fn to_service(result: ServiceDocument) -> Service {
+    Service {
+        id: result.id.to_string(),
+        customer_id: result.customer_id.to_string(),
+        price: format_price(
+            result.price_min,
+            result.price_max,
+            &result.currency,
+            result.price_unit.as_deref(),
+        ),
+        name: result.name,
+        description: result.description,
+        deliverables: result.deliverables,
+        price_min: result.price_min,
+        price_max: result.price_max,
+        currency: result.currency,
+        price_unit: result.price_unit,
+        cta_label: result.cta_label,
+        cta_link: result.cta_link,
+        project_ids: result
+            .project_ids
+            .into_iter()
+            .map(|id| id.to_string())
+            .collect(),
+        order: result.order,
+        created_at: result.created_at.to_string(),
+        updated_at: result.updated_at.map(|d| d.to_string()),
+    }
+}
@@ -1,21 +1,23 @@
-use crate::models::skill::{Skill, SkillDocument, SkillInput, SkillsInput};
+use crate::models::skill::{Skill, SkillDeletePolicy, SkillDocument, SkillInput, SkillsInput};
 use crate::routes::traits::SkillRepository;
 use chrono::Utc;
 use futures::stream::TryStreamExt;
 use mongodb::{
-    bson::{doc, oid::ObjectId, Document},
+    bson::{doc, oid::ObjectId, to_bson, Document},
     options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
     Database,
 };
 use rocket::serde::json::Json;
+use serde::de::Error as _;
 
 pub struct SkillRepo {
     pub db: Database,
+    pub delete_policy: SkillDeletePolicy,
 }
 
 impl SkillRepo {
-    pub fn new(db: Database) -> Self {
-        Self { db }
+    pub fn new(db: Database, delete_policy: SkillDeletePolicy) -> Self {
+        Self { db, delete_policy }
     }
 }
 
@@ -42,13 +44,7 @@ impl SkillRepository for SkillRepo {
         let mut resp: Vec<Skill> = vec![];
         while let Some(result) = cursor.try_next().await? {
             // transform ObjectId to String
-            let json_resp = Skill {
-                id: result.id.to_string(),
-                customer_id: result.customer_id.to_string(),
-                name: result.name,
-                order: result.order,
-                created_at: result.created_at.to_string(),
-            };
+            let json_resp = to_skill(result);
             resp.push(json_resp);
         }
 
@@ -63,13 +59,7 @@ impl SkillRepository for SkillRepo {
         };
 
         // transform ObjectId to String
-        let resp = Skill {
-            id: result.id.to_string(),
-            customer_id: result.customer_id.to_string(),
-            name: result.name,
-            order: result.order,
-            created_at: result.created_at.to_string(),
-        };
+        let resp = to_skill(result);
 
         Ok(Some(resp))
     }
@@ -88,8 +78,12 @@ impl SkillRepository for SkillRepo {
                 doc! {
                     "customer_id": oid,
                     "name": &input.name,
+                    "category": &input.category,
+                    "proficiency": to_bson(&input.proficiency)?,
+                    "years": input.years,
                     "order": input.order,
                     "created_at": created_at,
+                    "updated_at": created_at,
                 },
                 None,
             )
@@ -111,12 +105,7 @@ impl SkillRepository for SkillRepo {
         let Some(result) = collection
             .find_one_and_update(
                 doc! {"_id":oid },
-                doc! {
-                    "$set": {
-                        "name": &input.name,
-                        "order": input.order,
-                    }
-                },
+                doc! { "$set": skill_update(&input)? },
                 find_one_and_update_options,
             )
             .await?
@@ -125,13 +114,7 @@ impl SkillRepository for SkillRepo {
         };
 
         // transform ObjectId to String
-        let resp = Skill {
-            id: result.id.to_string(),
-            customer_id: result.customer_id.to_string(),
-            name: result.name,
-            order: result.order,
-            created_at: result.created_at.to_string(),
-        };
+        let resp = to_skill(result);
 
         Ok(Some(resp))
     }
@@ -143,7 +126,7 @@ impl SkillRepository for SkillRepo {
         let collection = self.db.collection::<SkillDocument>("skill");
         let mut updated_projects = vec![];
 
-        for item in input.iter() {
+        for item in input.into_inner() {
             let oid = match ObjectId::parse_str(&item.id) {
                 Ok(oid) => oid,
                 Err(_) => continue, // skip invalid IDs
@@ -156,23 +139,12 @@ impl SkillRepository for SkillRepo {
             if let Some(result) = collection
                 .find_one_and_update(
                     doc! { "_id": oid },
-                    doc! {
-                        "$set": {
-                            "name": &item.name,
-                            "order": item.order,
-                        }
-                    },
+                    doc! { "$set": skill_update(&SkillInput::from(item))? },
                     find_one_and_update_options,
                 )
                 .await?
             {
-                updated_projects.push(Skill {
-                    id: result.id.to_string(),
-                    customer_id: result.customer_id.to_string(),
-                    name: result.name,
-                    order: result.order,
-                    created_at: result.created_at.to_string(),
-                });
+                updated_projects.push(to_skill(result));
             }
         }
 
@@ -185,6 +157,21 @@ impl SkillRepository for SkillRepo {
 
     async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Skill>> {
         let collection = self.db.collection::<SkillDocument>("skill");
+        let projects = self.db.collection::<Document>("project");
+        let experiences = self.db.collection::<Document>("experience");
+
+        let used_by = doc! { "skill_ids": oid };
+        if self.delete_policy == SkillDeletePolicy::Restrict {
+            let project_count = projects.count_documents(used_by.clone(), None).await?;
+            let experience_count = experiences.count_documents(used_by.clone(), None).await?;
+            if project_count > 0 || experience_count > 0 {
+                return Err(mongodb::bson::de::Error::custom(format!(
+                    "Skill {oid} is used by {project_count} project(s) and \
+                     {experience_count} experience(s)"
+                ))
+                .into());
+            }
+        }
 
         // if you just unwrap,, when there is no document it results in 500 error.
         let Some(result) = collection
@@ -194,15 +181,53 @@ impl SkillRepository for SkillRepo {
             return Ok(None);
         };
 
+        if self.delete_policy == SkillDeletePolicy::Cascade {
+            let pull = doc! { "$pull": { "skill_ids": oid } };
+            projects
+                .update_many(used_by.clone(), pull.clone(), None)
+                .await?;
+            experiences.update_many(used_by, pull, None).await?;
+        }
+
         // transform ObjectId to String
-        let resp = Skill {
-            id: result.id.to_string(),
-            customer_id: result.customer_id.to_string(),
-            name: result.name,
-            order: result.order,
-            created_at: result.created_at.to_string(),
-        };
+        let resp = to_skill(result);
 
         Ok(Some(resp))
     }
 }
+
+// transform ObjectId to String
+fn to_skill(result: SkillDocument) -> Skill {
+    Skill {
+        id: result.id.to_string(),
+        customer_id: result.customer_id.to_string(),
+        name: result.name,
+        category: result.category,
+        proficiency: result.proficiency,
+        years: result.years,
+        order: result.order,
+        created_at: result.created_at.to_string(),
+        updated_at: result.updated_at.map(|d| d.to_string()),
+    }
+}
+
+/// `$set` of a skill update. `category`, `proficiency` and `years` are only set when they are sent,
+/// the dashboard only sends `name` and `order`.
+pub fn skill_update(input: &SkillInput) -> mongodb::error::Result<Document> {
+    let mut set = doc! {
+        "name": &input.name,
+        "order": input.order,
+        "updated_at": mongodb::bson::DateTime::now(),
+    };
+    if let Some(category) = &input.category {
+        set.insert("category", category);
+    }
+    if let Some(proficiency) = &input.proficiency {
+        set.insert("proficiency", to_bson(proficiency)?);
+    }
+    if let Some(years) = input.years {
+        set.insert("years", years);
+    }
+
+    Ok(set)
+}
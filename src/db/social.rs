@@ -49,6 +49,7 @@ impl SocialRepository for SocialRepo {
                 link: result.link,
                 order: result.order,
                 created_at: result.created_at.to_string(),
+                updated_at: result.updated_at.map(|d| d.to_string()),
             };
             resp.push(json_resp);
         }
@@ -71,6 +72,7 @@ impl SocialRepository for SocialRepo {
             link: result.link,
             order: result.order,
             created_at: result.created_at.to_string(),
+            updated_at: result.updated_at.map(|d| d.to_string()),
         };
 
         Ok(Some(resp))
@@ -93,6 +95,7 @@ impl SocialRepository for SocialRepo {
                     "link": &input.link,
                     "order": input.order,
                     "created_at": created_at,
+                    "updated_at": created_at,
                 },
                 None,
             )
@@ -119,6 +122,7 @@ impl SocialRepository for SocialRepo {
                         "name": &input.name,
                         "link": &input.link,
                         "order": input.order,
+                        "updated_at": mongodb::bson::DateTime::now(),
                     }
                 },
                 find_one_and_update_options,
@@ -136,6 +140,7 @@ impl SocialRepository for SocialRepo {
             link: result.link,
             order: result.order,
             created_at: result.created_at.to_string(),
+            updated_at: result.updated_at.map(|d| d.to_string()),
         };
 
         Ok(Some(resp))
@@ -166,6 +171,7 @@ impl SocialRepository for SocialRepo {
                             "name": &item.name,
                             "link": &item.link,
                             "order": item.order,
+                            "updated_at": mongodb::bson::DateTime::now(),
                         }
                     },
                     find_one_and_update_options,
@@ -179,6 +185,7 @@ impl SocialRepository for SocialRepo {
                     link: result.link,
                     order: result.order,
                     created_at: result.created_at.to_string(),
+                    updated_at: result.updated_at.map(|d| d.to_string()),
                 });
             }
         }
@@ -209,6 +216,7 @@ impl SocialRepository for SocialRepo {
             link: result.link,
             order: result.order,
             created_at: result.created_at.to_string(),
+            updated_at: result.updated_at.map(|d| d.to_string()),
         };
 
         Ok(Some(resp))
@@ -0,0 +1,254 @@
+use crate::models::testimonial::{
+    Testimonial, TestimonialDocument, TestimonialInput, TestimonialStatus, TestimonialSubmission,
+    TestimonialsInput,
+};
+use crate::routes::traits::TestimonialRepository;
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, to_bson, Document},
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Database,
+};
+use rocket::serde::json::Json;
+
+pub struct TestimonialRepo {
+    pub db: Database,
+}
+
+impl TestimonialRepo {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl TestimonialRepository for TestimonialRepo {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        status: Option<TestimonialStatus>,
+    ) -> mongodb::error::Result<Vec<Testimonial>> {
+        let collection = self.db.collection::<TestimonialDocument>("testimonial");
+
+        let mut filter = doc! { "customer_id": oid };
+        if let Some(status) = status {
+            filter.insert("status", to_bson(&status)?);
+        }
+
+        let find_options = FindOptions::builder()
+            .sort(doc! { "order": -1 })
+            .limit(limit)
+            .skip(u64::try_from((page - 1) * limit).unwrap())
+            .build();
+
+        let mut cursor = collection.find(filter, find_options).await?;
+
+        let mut resp: Vec<Testimonial> = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            resp.push(to_testimonial(result));
+        }
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Testimonial>> {
+        let collection = self.db.collection::<TestimonialDocument>("testimonial");
+
+        let resp = collection
+            .find_one(doc! {"_id":oid }, None)
+            .await?
+            .map(to_testimonial);
+
+        Ok(resp)
+    }
+
+    async fn insert(
+        &self,
+        input: Json<TestimonialSubmission>,
+        oid: ObjectId,
+        order: i32,
+    ) -> mongodb::error::Result<String> {
+        let collection = self.db.collection::<Document>("testimonial");
+
+        let created_at = mongodb::bson::DateTime::from_chrono(Utc::now());
+        let project_id = input
+            .project_id
+            .as_deref()
+            .and_then(|id| ObjectId::parse_str(id).ok());
+
+        let resp = collection
+            .insert_one(
+                doc! {
+                    "customer_id": oid,
+                    "author": &input.author,
+                    "role": &input.role,
+                    "company": &input.company,
+                    "avatar_link": &input.avatar_link,
+                    "quote": &input.quote,
+                    "rating": input.rating,
+                    "project_id": project_id,
+                    "status": to_bson(&TestimonialStatus::Pending)?,
+                    "order": order,
+                    "created_at": created_at,
+                    "updated_at": created_at,
+                },
+                None,
+            )
+            .await?;
+
+        Ok(resp.inserted_id.to_string())
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<TestimonialInput>,
+    ) -> mongodb::error::Result<Option<Testimonial>> {
+        let collection = self.db.collection::<TestimonialDocument>("testimonial");
+        let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let project_id = input
+            .project_id
+            .as_deref()
+            .and_then(|id| ObjectId::parse_str(id).ok());
+
+        let resp = collection
+            .find_one_and_update(
+                doc! {"_id":oid },
+                doc! {
+                    "$set": {
+                        "author": &input.author,
+                        "role": &input.role,
+                        "company": &input.company,
+                        "avatar_link": &input.avatar_link,
+                        "quote": &input.quote,
+                        "rating": input.rating,
+                        "project_id": project_id,
+                        "order": input.order,
+                        "updated_at": mongodb::bson::DateTime::now(),
+                    }
+                },
+                find_one_and_update_options,
+            )
+            .await?
+            .map(to_testimonial);
+
+        Ok(resp)
+    }
+
+    async fn update_status(
+        &self,
+        oid: ObjectId,
+        status: TestimonialStatus,
+    ) -> mongodb::error::Result<Option<Testimonial>> {
+        let collection = self.db.collection::<TestimonialDocument>("testimonial");
+        let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let resp = collection
+            .find_one_and_update(
+                doc! {"_id":oid },
+                doc! {
+                    "$set": {
+                        "status": to_bson(&status)?,
+                        "updated_at": mongodb::bson::DateTime::now(),
+                    }
+                },
+                find_one_and_update_options,
+            )
+            .await?
+            .map(to_testimonial);
+
+        Ok(resp)
+    }
+
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<TestimonialsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Testimonial>>> {
+        let collection = self.db.collection::<TestimonialDocument>("testimonial");
+        let mut updated_testimonials = vec![];
+
+        for item in input.iter() {
+            let oid = match ObjectId::parse_str(&item.id) {
+                Ok(oid) => oid,
+                Err(_) => continue, // skip invalid IDs
+            };
+
+            let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+                .return_document(ReturnDocument::After)
+                .build();
+
+            let project_id = item
+                .project_id
+                .as_deref()
+                .and_then(|id| ObjectId::parse_str(id).ok());
+
+            if let Some(result) = collection
+                .find_one_and_update(
+                    doc! { "_id": oid, "customer_id": customer_oid },
+                    doc! {
+                        "$set": {
+                            "author": &item.author,
+                            "role": &item.role,
+                            "company": &item.company,
+                            "avatar_link": &item.avatar_link,
+                            "quote": &item.quote,
+                            "rating": item.rating,
+                            "project_id": project_id,
+                            "order": item.order,
+                            "updated_at": mongodb::bson::DateTime::now(),
+                        }
+                    },
+                    find_one_and_update_options,
+                )
+                .await?
+            {
+                updated_testimonials.push(to_testimonial(result));
+            }
+        }
+
+        if updated_testimonials.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(updated_testimonials))
+        }
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Testimonial>> {
+        let collection = self.db.collection::<TestimonialDocument>("testimonial");
+
+        let resp = collection
+            .find_one_and_delete(doc! {"_id":oid }, None)
+            .await?
+            .map(to_testimonial);
+
+        Ok(resp)
+    }
+}
+
+// transform ObjectId to String
+fn to_testimonial(result: TestimonialDocument) -> Testimonial {
+    Testimonial {
+        id: result.id.to_string(),
+        customer_id: result.customer_id.to_string(),
+        author: result.author,
+        role: result.role,
+        company: result.company,
+        avatar_link: result.avatar_link,
+        quote: result.quote,
+        rating: result.rating,
+        project_id: result.project_id.map(|id| id.to_string()),
+        status: result.status,
+        order: result.order,
+        created_at: result.created_at.to_string(),
+        updated_at: result.updated_at.map(|d| d.to_string()),
+    }
+}
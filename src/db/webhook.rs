@@ -0,0 +1,416 @@
+use crate::models::event::event_matches;
+use crate::models::webhook::{
+    DeliveryAttempt, DeliveryStatus, Webhook, WebhookDelivery, WebhookDeliveryDocument,
+    WebhookDocument, WebhookInput,
+};
+use crate::routes::traits::WebhookRepository;
+use chrono::{DateTime, Utc};
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, to_bson, Document},
+    options::{FindOneAndUpdateOptions, FindOptions, IndexOptions, ReturnDocument},
+    Database, IndexModel,
+};
+use rocket::serde::json::Json;
+use std::env;
+use std::time::Duration;
+
+pub struct WebhookRepo {
+    pub db: Database,
+}
+
+impl WebhookRepo {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+/// Indexes of the delivery queue, the log is dropped after `WEBHOOK_LOG_DAYS` days (30 by default).
+pub async fn ensure_indexes(db: &Database) -> mongodb::error::Result<()> {
+    let log_days: u64 = env::var("WEBHOOK_LOG_DAYS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(30);
+
+    let deliveries = db.collection::<Document>("webhook_delivery");
+    let indexes = vec![
+        IndexModel::builder()
+            .keys(doc! { "status": 1, "next_attempt_at": 1 })
+            .build(),
+        IndexModel::builder()
+            .keys(doc! { "webhook_id": 1, "created_at": -1 })
+            .build(),
+        IndexModel::builder()
+            .keys(doc! { "created_at": 1 })
+            .options(
+                IndexOptions::builder()
+                    .expire_after(Duration::from_secs(log_days * 24 * 60 * 60))
+                    .build(),
+            )
+            .build(),
+    ];
+    deliveries.create_indexes(indexes, None).await?;
+
+    db.collection::<Document>("webhook")
+        .create_index(
+            IndexModel::builder()
+                .keys(doc! { "customer_id": 1 })
+                .build(),
+            None,
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl WebhookRepository for WebhookRepo {
+    async fn find(&self, oid: ObjectId) -> mongodb::error::Result<Vec<Webhook>> {
+        let collection = self.db.collection::<WebhookDocument>("webhook");
+
+        let find_options = FindOptions::builder()
+            .sort(doc! { "created_at": 1 })
+            .build();
+
+        let mut cursor = collection
+            .find(doc! { "customer_id": oid }, find_options)
+            .await?;
+
+        let mut resp: Vec<Webhook> = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            resp.push(to_webhook(result));
+        }
+
+        Ok(resp)
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Webhook>> {
+        let collection = self.db.collection::<WebhookDocument>("webhook");
+
+        let resp = collection
+            .find_one(doc! {"_id":oid }, None)
+            .await?
+            .map(to_webhook);
+
+        Ok(resp)
+    }
+
+    async fn find_subscribed(
+        &self,
+        oid: ObjectId,
+        event: &str,
+    ) -> mongodb::error::Result<Vec<Webhook>> {
+        let collection = self.db.collection::<WebhookDocument>("webhook");
+
+        let mut cursor = collection
+            .find(doc! { "customer_id": oid, "active": true }, None)
+            .await?;
+
+        // a customer has a handful of webhooks, the patterns are matched here
+        let mut resp: Vec<Webhook> = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            if result.events.is_empty()
+                || result
+                    .events
+                    .iter()
+                    .any(|pattern| event_matches(pattern, event))
+            {
+                resp.push(to_webhook(result));
+            }
+        }
+
+        Ok(resp)
+    }
+
+    async fn insert(
+        &self,
+        input: Json<WebhookInput>,
+        oid: ObjectId,
+        secret: &str,
+    ) -> mongodb::error::Result<Webhook> {
+        let collection = self.db.collection::<WebhookDocument>("webhook");
+
+        let now = Utc::now();
+        let document = WebhookDocument {
+            id: ObjectId::new(),
+            customer_id: oid,
+            url: input.url.trim().to_string(),
+            secret: secret.to_string(),
+            events: input.events.clone().unwrap_or_default(),
+            active: input.active.unwrap_or(true),
+            description: input.description.clone(),
+            created_at: now,
+            updated_at: Some(now),
+        };
+        collection.insert_one(&document, None).await?;
+
+        Ok(to_webhook(document))
+    }
+
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<WebhookInput>,
+    ) -> mongodb::error::Result<Option<Webhook>> {
+        let collection = self.db.collection::<WebhookDocument>("webhook");
+        let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let resp = collection
+            .find_one_and_update(
+                doc! {"_id":oid },
+                doc! {
+                    "$set": {
+                        "url": input.url.trim(),
+                        "events": input.events.clone().unwrap_or_default(),
+                        "active": input.active.unwrap_or(true),
+                        "description": &input.description,
+                        "updated_at": mongodb::bson::DateTime::now(),
+                    }
+                },
+                find_one_and_update_options,
+            )
+            .await?
+            .map(to_webhook);
+
+        Ok(resp)
+    }
+
+    async fn update_secret(
+        &self,
+        oid: ObjectId,
+        secret: &str,
+    ) -> mongodb::error::Result<Option<Webhook>> {
+        let collection = self.db.collection::<WebhookDocument>("webhook");
+        let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let resp = collection
+            .find_one_and_update(
+                doc! {"_id":oid },
+                doc! {
+                    "$set": {
+                        "secret": secret,
+                        "updated_at": mongodb::bson::DateTime::now(),
+                    }
+                },
+                find_one_and_update_options,
+            )
+            .await?
+            .map(to_webhook);
+
+        Ok(resp)
+    }
+
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Webhook>> {
+        let collection = self.db.collection::<WebhookDocument>("webhook");
+
+        let resp = collection
+            .find_one_and_delete(doc! {"_id":oid }, None)
+            .await?
+            .map(to_webhook);
+
+        if resp.is_some() {
+            self.db
+                .collection::<Document>("webhook_delivery")
+                .delete_many(doc! { "webhook_id": oid }, None)
+                .await?;
+        }
+
+        Ok(resp)
+    }
+
+    async fn enqueue(
+        &self,
+        webhook: &Webhook,
+        event: &str,
+        payload: String,
+        redelivery_of: Option<ObjectId>,
+        next_attempt_at: DateTime<Utc>,
+    ) -> mongodb::error::Result<WebhookDelivery> {
+        let collection = self
+            .db
+            .collection::<WebhookDeliveryDocument>("webhook_delivery");
+
+        let now = Utc::now();
+        let document = WebhookDeliveryDocument {
+            id: ObjectId::new(),
+            webhook_id: parse_oid(&webhook.id)?,
+            customer_id: parse_oid(&webhook.customer_id)?,
+            event: event.to_string(),
+            payload,
+            status: DeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at,
+            last_attempt_at: None,
+            response_status: None,
+            response_body: None,
+            error: None,
+            duration: None,
+            redelivery_of,
+            created_at: now,
+        };
+        collection.insert_one(&document, None).await?;
+
+        Ok(to_delivery(document))
+    }
+
+    async fn find_deliveries(
+        &self,
+        limit: i64,
+        page: i64,
+        webhook_oid: ObjectId,
+        status: Option<DeliveryStatus>,
+    ) -> mongodb::error::Result<Vec<WebhookDelivery>> {
+        let collection = self
+            .db
+            .collection::<WebhookDeliveryDocument>("webhook_delivery");
+
+        let mut filter = doc! { "webhook_id": webhook_oid };
+        if let Some(status) = status {
+            filter.insert("status", to_bson(&status)?);
+        }
+
+        // Newest deliveries first.
+        let find_options = FindOptions::builder()
+            .sort(doc! { "created_at": -1 })
+            .limit(limit)
+            .skip(u64::try_from((page - 1) * limit).unwrap())
+            .build();
+
+        let mut cursor = collection.find(filter, find_options).await?;
+
+        let mut resp: Vec<WebhookDelivery> = vec![];
+        while let Some(result) = cursor.try_next().await? {
+            resp.push(to_delivery(result));
+        }
+
+        Ok(resp)
+    }
+
+    async fn find_delivery_by_id(
+        &self,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Option<WebhookDelivery>> {
+        let collection = self
+            .db
+            .collection::<WebhookDeliveryDocument>("webhook_delivery");
+
+        let resp = collection
+            .find_one(doc! {"_id":oid }, None)
+            .await?
+            .map(to_delivery);
+
+        Ok(resp)
+    }
+
+    async fn claim_due_delivery(
+        &self,
+        lease_until: DateTime<Utc>,
+    ) -> mongodb::error::Result<Option<WebhookDelivery>> {
+        let collection = self
+            .db
+            .collection::<WebhookDeliveryDocument>("webhook_delivery");
+        let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+            .sort(doc! { "next_attempt_at": 1 })
+            .return_document(ReturnDocument::After)
+            .build();
+
+        // moving `next_attempt_at` is the lease, a sender that dies gives the delivery back
+        let resp = collection
+            .find_one_and_update(
+                doc! {
+                    "status": to_bson(&DeliveryStatus::Pending)?,
+                    "next_attempt_at": { "$lte": mongodb::bson::DateTime::now() },
+                },
+                doc! {
+                    "$set": {
+                        "next_attempt_at": mongodb::bson::DateTime::from_chrono(lease_until),
+                    }
+                },
+                find_one_and_update_options,
+            )
+            .await?
+            .map(to_delivery);
+
+        Ok(resp)
+    }
+
+    async fn record_attempt(
+        &self,
+        oid: ObjectId,
+        attempt: &DeliveryAttempt,
+        status: DeliveryStatus,
+        next_attempt_at: DateTime<Utc>,
+    ) -> mongodb::error::Result<Option<WebhookDelivery>> {
+        let collection = self
+            .db
+            .collection::<WebhookDeliveryDocument>("webhook_delivery");
+        let find_one_and_update_options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let resp = collection
+            .find_one_and_update(
+                doc! {"_id":oid },
+                doc! {
+                    "$set": {
+                        "status": to_bson(&status)?,
+                        "next_attempt_at": mongodb::bson::DateTime::from_chrono(next_attempt_at),
+                        "last_attempt_at": mongodb::bson::DateTime::now(),
+                        "response_status": attempt.response_status,
+                        "response_body": &attempt.response_body,
+                        "error": &attempt.error,
+                        "duration": attempt.duration,
+                    },
+                    "$inc": { "attempts": 1 },
+                },
+                find_one_and_update_options,
+            )
+            .await?
+            .map(to_delivery);
+
+        Ok(resp)
+    }
+}
+
+fn parse_oid(id: &str) -> mongodb::error::Result<ObjectId> {
+    ObjectId::parse_str(id).map_err(|error| mongodb::error::Error::custom(error.to_string()))
+}
+
+// transform ObjectId to String
+fn to_webhook(result: WebhookDocument) -> Webhook {
+    Webhook {
+        id: result.id.to_string(),
+        customer_id: result.customer_id.to_string(),
+        url: result.url,
+        secret: result.secret,
+        events: result.events,
+        active: result.active,
+        description: result.description,
+        created_at: result.created_at.to_string(),
+        updated_at: result.updated_at.map(|d| d.to_string()),
+    }
+}
+
+fn to_delivery(result: WebhookDeliveryDocument) -> WebhookDelivery {
+    WebhookDelivery {
+        id: result.id.to_string(),
+        webhook_id: result.webhook_id.to_string(),
+        customer_id: result.customer_id.to_string(),
+        event: result.event,
+        payload: result.payload,
+        status: result.status,
+        attempts: result.attempts,
+        next_attempt_at: result.next_attempt_at.to_string(),
+        last_attempt_at: result.last_attempt_at.map(|d| d.to_string()),
+        response_status: result.response_status,
+        response_body: result.response_body,
+        error: result.error,
+        duration: result.duration,
+        redelivery_of: result.redelivery_of.map(|id| id.to_string()),
+        created_at: result.created_at.to_string(),
+    }
+}
@@ -31,6 +31,10 @@ impl MyError {
         let reason = match code {
             400 => "Bad Request".to_string(),
             401 => "Unauthorized".to_string(),
+            403 => "Forbidden".to_string(),
+            404 => "Not Found".to_string(),
+            413 => "Payload Too Large".to_string(),
+            429 => "Too Many Requests".to_string(),
             _ => "Error".to_string(),
         };
         MyError {
@@ -80,6 +84,24 @@ pub fn unauthorized_response(gen: &mut OpenApiGenerator) -> okapi::openapi3::Res
     }
 }
 
+pub fn not_found_response(gen: &mut OpenApiGenerator) -> okapi::openapi3::Response {
+    let schema = gen.json_schema::<MyError>();
+    okapi::openapi3::Response {
+        description: "\
+        # 404 Not Found\n\
+        The document does not exist or belongs to another customer. \
+        "
+        .to_owned(),
+        content: okapi::map! {
+            "application/json".to_owned() => MediaType {
+                schema: Some(schema),
+                ..Default::default()
+            }
+        },
+        ..Default::default()
+    }
+}
+
 impl<'r> rocket::response::Responder<'r, 'static> for MyError {
     fn respond_to(self, _: &'r rocket::Request<'_>) -> rocket::response::Result<'static> {
         // Convert object to json
@@ -98,6 +120,7 @@ impl OpenApiResponderInner for MyError {
         Ok(Responses {
             responses: okapi::map! {
                 "400".to_owned() => RefOr::Object(bad_request_response(gen)),
+                "404".to_owned() => RefOr::Object(not_found_response(gen)),
                 // Note: 401 is already declared for ApiKey. so this is not essential.
                 // "401".to_owned() => RefOr::Object(unauthorized_response(gen)),
             },
@@ -88,6 +88,6 @@ impl Fairing for Cors {
         ));
         response.set_header(Header::new("Access-Control-Allow-Credentials", "true"));
         response.set_header(Header::new("Access-Control-Max-Age", "3600"));
-        response.set_header(Header::new("Vary", "Origin"));
+        response.adjoin_header(Header::new("Vary", "Origin"));
     }
 }
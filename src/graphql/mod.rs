@@ -0,0 +1,499 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, EmptySubscription, Object, Schema};
+use mongodb::bson::oid::ObjectId;
+use rocket::serde::json::Json;
+
+use crate::{
+    models::{
+        article::Article,
+        certification::Certification,
+        customer::{Customer, CustomerUpdateInput},
+        education::Education,
+        experience::{Experience, ExperienceInput},
+        project::{Project, ProjectFilter, ProjectInput},
+        service::Service,
+        skill::{Skill, SkillInput},
+        social::{Social, SocialInput},
+        testimonial::{Testimonial, TestimonialStatus},
+    },
+    request_guards::basic::{ApiKey, ClientApiKey},
+    routes::traits::{
+        ArticleRepository, CertificationRepository, CustomerRepository, EducationRepository,
+        ExperienceRepository, ProjectRepository, ServiceRepository, SkillRepository,
+        SocialRepository, TestimonialRepository,
+    },
+    utils::{
+        i18n::{validate_locales, validate_translations, Translatable},
+        seo::{validate_seo, validate_site_url},
+    },
+    Container,
+};
+
+pub type PortfolioSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+type ArticleService = Arc<dyn ArticleRepository + Send + Sync>;
+type CertificationService = Arc<dyn CertificationRepository + Send + Sync>;
+type CustomerService = Arc<dyn CustomerRepository + Send + Sync>;
+type EducationService = Arc<dyn EducationRepository + Send + Sync>;
+type ExperienceService = Arc<dyn ExperienceRepository + Send + Sync>;
+type ProjectService = Arc<dyn ProjectRepository + Send + Sync>;
+type ServiceService = Arc<dyn ServiceRepository + Send + Sync>;
+type SkillService = Arc<dyn SkillRepository + Send + Sync>;
+type SocialService = Arc<dyn SocialRepository + Send + Sync>;
+type TestimonialService = Arc<dyn TestimonialRepository + Send + Sync>;
+
+/// Build the GraphQL schema on top of the repositories registered in the container.
+pub fn build_schema(container: &Container) -> PortfolioSchema {
+    let mut builder = Schema::build(QueryRoot, MutationRoot, EmptySubscription);
+
+    if let Some(service) = container.get::<ArticleService>() {
+        builder = builder.data(service.clone());
+    }
+    if let Some(service) = container.get::<CertificationService>() {
+        builder = builder.data(service.clone());
+    }
+    if let Some(service) = container.get::<CustomerService>() {
+        builder = builder.data(service.clone());
+    }
+    if let Some(service) = container.get::<EducationService>() {
+        builder = builder.data(service.clone());
+    }
+    if let Some(service) = container.get::<ExperienceService>() {
+        builder = builder.data(service.clone());
+    }
+    if let Some(service) = container.get::<ProjectService>() {
+        builder = builder.data(service.clone());
+    }
+    if let Some(service) = container.get::<ServiceService>() {
+        builder = builder.data(service.clone());
+    }
+    if let Some(service) = container.get::<SkillService>() {
+        builder = builder.data(service.clone());
+    }
+    if let Some(service) = container.get::<SocialService>() {
+        builder = builder.data(service.clone());
+    }
+
+    if let Some(service) = container.get::<TestimonialService>() {
+        builder = builder.data(service.clone());
+    }
+
+    builder.finish()
+}
+
+fn parse_oid(id: &str) -> async_graphql::Result<ObjectId> {
+    ObjectId::parse_str(id).map_err(|_| "Invalid id format.".into())
+}
+
+// Customer id of the dashboard user (Bearer JWT).
+fn admin_oid(ctx: &Context<'_>) -> async_graphql::Result<ObjectId> {
+    let key = ctx
+        .data_opt::<ApiKey>()
+        .ok_or("A Bearer JWT is required for this operation")?;
+
+    ObjectId::parse_str(&key.0.sub).map_err(|_| "Invalid user id format.".into())
+}
+
+// Errors like a missing document when it belongs to another customer.
+fn check_owner(customer_id: Option<String>, oid: ObjectId, id: &str) -> async_graphql::Result<()> {
+    match customer_id {
+        Some(customer_id) if customer_id == oid.to_string() => Ok(()),
+        _ => Err(format!("Not found with id {id}").into()),
+    }
+}
+
+// Customer id of the dashboard user, or of the portfolio owning the X-API-KEY.
+async fn reader_oid(ctx: &Context<'_>) -> async_graphql::Result<ObjectId> {
+    if ctx.data_opt::<ApiKey>().is_some() {
+        return admin_oid(ctx);
+    }
+
+    let client_key = ctx
+        .data_opt::<ClientApiKey>()
+        .ok_or("A Bearer JWT or an X-API-KEY header is required")?;
+
+    let customer_repo = ctx.data::<CustomerService>()?;
+    match customer_repo
+        .find_customer_by_api_key(&client_key.0)
+        .await?
+    {
+        Some(customer_doc) => parse_oid(&customer_doc.id),
+        None => Err(format!("Customer not found with api key {}", client_key.0).into()),
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn customer(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Customer>> {
+        let oid = reader_oid(ctx).await?;
+        let customer_repo = ctx.data::<CustomerService>()?;
+
+        Ok(customer_repo.find_customer_by_id(oid).await?)
+    }
+
+    async fn projects(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 100)] limit: i64,
+        #[graphql(default = 1)] page: i64,
+        featured: Option<bool>,
+        #[graphql(default)] stack: Vec<String>,
+    ) -> async_graphql::Result<Vec<Project>> {
+        let oid = reader_oid(ctx).await?;
+        let project_repo = ctx.data::<ProjectService>()?;
+        let filter = ProjectFilter {
+            featured,
+            stack,
+            ..Default::default()
+        };
+
+        Ok(project_repo.find(limit, page, oid, filter).await?)
+    }
+
+    async fn skills(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 100)] limit: i64,
+        #[graphql(default = 1)] page: i64,
+    ) -> async_graphql::Result<Vec<Skill>> {
+        let oid = reader_oid(ctx).await?;
+        let skill_repo = ctx.data::<SkillService>()?;
+
+        Ok(skill_repo.find(limit, page, oid).await?)
+    }
+
+    async fn socials(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 100)] limit: i64,
+        #[graphql(default = 1)] page: i64,
+    ) -> async_graphql::Result<Vec<Social>> {
+        let oid = reader_oid(ctx).await?;
+        let social_repo = ctx.data::<SocialService>()?;
+
+        Ok(social_repo.find(limit, page, oid).await?)
+    }
+
+    async fn experiences(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 100)] limit: i64,
+        #[graphql(default = 1)] page: i64,
+    ) -> async_graphql::Result<Vec<Experience>> {
+        let oid = reader_oid(ctx).await?;
+        let experience_repo = ctx.data::<ExperienceService>()?;
+
+        Ok(experience_repo.find(limit, page, oid).await?)
+    }
+
+    async fn educations(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 100)] limit: i64,
+        #[graphql(default = 1)] page: i64,
+    ) -> async_graphql::Result<Vec<Education>> {
+        let oid = reader_oid(ctx).await?;
+        let education_repo = ctx.data::<EducationService>()?;
+
+        Ok(education_repo.find(limit, page, oid).await?)
+    }
+
+    async fn certifications(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 100)] limit: i64,
+        #[graphql(default = 1)] page: i64,
+        #[graphql(default = false)] include_expired: bool,
+    ) -> async_graphql::Result<Vec<Certification>> {
+        let oid = reader_oid(ctx).await?;
+        let certification_repo = ctx.data::<CertificationService>()?;
+
+        Ok(certification_repo
+            .find(limit, page, oid, include_expired)
+            .await?)
+    }
+
+    /// Approved testimonials.
+    async fn testimonials(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 100)] limit: i64,
+        #[graphql(default = 1)] page: i64,
+    ) -> async_graphql::Result<Vec<Testimonial>> {
+        let oid = reader_oid(ctx).await?;
+        let testimonial_repo = ctx.data::<TestimonialService>()?;
+
+        Ok(testimonial_repo
+            .find(limit, page, oid, Some(TestimonialStatus::Approved))
+            .await?)
+    }
+
+    /// Services offered, with their formatted price.
+    async fn services(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 100)] limit: i64,
+        #[graphql(default = 1)] page: i64,
+    ) -> async_graphql::Result<Vec<Service>> {
+        let oid = reader_oid(ctx).await?;
+        let service_repo = ctx.data::<ServiceService>()?;
+
+        Ok(service_repo.find(limit, page, oid).await?)
+    }
+
+    /// Published articles, drafts are also returned to the dashboard user.
+    async fn articles(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 100)] limit: i64,
+        #[graphql(default = 1)] page: i64,
+    ) -> async_graphql::Result<Vec<Article>> {
+        let oid = reader_oid(ctx).await?;
+        let article_repo = ctx.data::<ArticleService>()?;
+        let published_only = ctx.data_opt::<ApiKey>().is_none();
+
+        Ok(article_repo.find(limit, page, oid, published_only).await?)
+    }
+
+    async fn article(
+        &self,
+        ctx: &Context<'_>,
+        slug: String,
+    ) -> async_graphql::Result<Option<Article>> {
+        let oid = reader_oid(ctx).await?;
+        let article_repo = ctx.data::<ArticleService>()?;
+        let published_only = ctx.data_opt::<ApiKey>().is_none();
+
+        Ok(article_repo.find_by_slug(oid, slug, published_only).await?)
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn update_customer(
+        &self,
+        ctx: &Context<'_>,
+        input: CustomerUpdateInput,
+    ) -> async_graphql::Result<Option<Customer>> {
+        let oid = admin_oid(ctx)?;
+        let customer_repo = ctx.data::<CustomerService>()?;
+
+        validate_locales(input.default_locale.as_deref(), input.locales.as_deref())?;
+        validate_translations(input.translations.as_ref(), Customer::FIELDS)?;
+        validate_site_url(input.site_url.as_deref())?;
+        validate_seo(input.seo.as_ref())?;
+
+        if let Some(existing) = customer_repo.find_customer_by_email(&input.email).await? {
+            if existing.id != oid.to_string() {
+                return Err(format!("Customer with email {} already exists", input.email).into());
+            }
+        }
+
+        Ok(customer_repo
+            .update_customer_by_id(oid, Json(input))
+            .await?)
+    }
+
+    async fn create_project(
+        &self,
+        ctx: &Context<'_>,
+        mut input: ProjectInput,
+    ) -> async_graphql::Result<String> {
+        let oid = admin_oid(ctx)?;
+        let project_repo = ctx.data::<ProjectService>()?;
+
+        validate_translations(input.translations.as_ref(), Project::FIELDS)?;
+        validate_seo(input.seo.as_ref())?;
+
+        if let Some(last) = project_repo
+            .find(1, 1, oid, ProjectFilter::default())
+            .await?
+            .first()
+        {
+            input.order = last.order + 1;
+        }
+
+        Ok(project_repo.insert(Json(input), oid).await?)
+    }
+
+    async fn update_project(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        input: ProjectInput,
+    ) -> async_graphql::Result<Option<Project>> {
+        let oid = admin_oid(ctx)?;
+        let project_repo = ctx.data::<ProjectService>()?;
+
+        validate_translations(input.translations.as_ref(), Project::FIELDS)?;
+        validate_seo(input.seo.as_ref())?;
+
+        let doc_oid = parse_oid(&id)?;
+        let existing = project_repo.find_by_id(doc_oid).await?;
+        check_owner(existing.map(|item| item.customer_id), oid, &id)?;
+
+        Ok(project_repo.update_by_id(doc_oid, Json(input)).await?)
+    }
+
+    async fn delete_project(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<Project>> {
+        let oid = admin_oid(ctx)?;
+        let project_repo = ctx.data::<ProjectService>()?;
+
+        let doc_oid = parse_oid(&id)?;
+        let existing = project_repo.find_by_id(doc_oid).await?;
+        check_owner(existing.map(|item| item.customer_id), oid, &id)?;
+
+        Ok(project_repo.delete_by_id(doc_oid).await?)
+    }
+
+    async fn create_skill(
+        &self,
+        ctx: &Context<'_>,
+        mut input: SkillInput,
+    ) -> async_graphql::Result<String> {
+        let oid = admin_oid(ctx)?;
+        let skill_repo = ctx.data::<SkillService>()?;
+
+        if let Some(last) = skill_repo.find(1, 1, oid).await?.first() {
+            input.order = last.order + 1;
+        }
+
+        Ok(skill_repo.insert(Json(input), oid).await?)
+    }
+
+    async fn update_skill(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        input: SkillInput,
+    ) -> async_graphql::Result<Option<Skill>> {
+        let oid = admin_oid(ctx)?;
+        let skill_repo = ctx.data::<SkillService>()?;
+
+        let doc_oid = parse_oid(&id)?;
+        let existing = skill_repo.find_by_id(doc_oid).await?;
+        check_owner(existing.map(|item| item.customer_id), oid, &id)?;
+
+        Ok(skill_repo.update_by_id(doc_oid, Json(input)).await?)
+    }
+
+    async fn delete_skill(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<Skill>> {
+        let oid = admin_oid(ctx)?;
+        let skill_repo = ctx.data::<SkillService>()?;
+
+        let doc_oid = parse_oid(&id)?;
+        let existing = skill_repo.find_by_id(doc_oid).await?;
+        check_owner(existing.map(|item| item.customer_id), oid, &id)?;
+
+        Ok(skill_repo.delete_by_id(doc_oid).await?)
+    }
+
+    async fn create_social(
+        &self,
+        ctx: &Context<'_>,
+        mut input: SocialInput,
+    ) -> async_graphql::Result<String> {
+        let oid = admin_oid(ctx)?;
+        let social_repo = ctx.data::<SocialService>()?;
+
+        if let Some(last) = social_repo.find(1, 1, oid).await?.first() {
+            input.order = last.order + 1;
+        }
+
+        Ok(social_repo.insert(Json(input), oid).await?)
+    }
+
+    async fn update_social(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        input: SocialInput,
+    ) -> async_graphql::Result<Option<Social>> {
+        let oid = admin_oid(ctx)?;
+        let social_repo = ctx.data::<SocialService>()?;
+
+        let doc_oid = parse_oid(&id)?;
+        let existing = social_repo.find_by_id(doc_oid).await?;
+        check_owner(existing.map(|item| item.customer_id), oid, &id)?;
+
+        Ok(social_repo.update_by_id(doc_oid, Json(input)).await?)
+    }
+
+    async fn delete_social(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<Social>> {
+        let oid = admin_oid(ctx)?;
+        let social_repo = ctx.data::<SocialService>()?;
+
+        let doc_oid = parse_oid(&id)?;
+        let existing = social_repo.find_by_id(doc_oid).await?;
+        check_owner(existing.map(|item| item.customer_id), oid, &id)?;
+
+        Ok(social_repo.delete_by_id(doc_oid).await?)
+    }
+
+    async fn create_experience(
+        &self,
+        ctx: &Context<'_>,
+        mut input: ExperienceInput,
+    ) -> async_graphql::Result<String> {
+        let oid = admin_oid(ctx)?;
+        let experience_repo = ctx.data::<ExperienceService>()?;
+
+        validate_translations(input.translations.as_ref(), Experience::FIELDS)?;
+
+        if let Some(last) = experience_repo.find(1, 1, oid).await?.first() {
+            input.order = last.order + 1;
+        }
+
+        Ok(experience_repo.insert(Json(input), oid).await?)
+    }
+
+    async fn update_experience(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        input: ExperienceInput,
+    ) -> async_graphql::Result<Option<Experience>> {
+        let oid = admin_oid(ctx)?;
+        let experience_repo = ctx.data::<ExperienceService>()?;
+
+        validate_translations(input.translations.as_ref(), Experience::FIELDS)?;
+
+        let doc_oid = parse_oid(&id)?;
+        let existing = experience_repo.find_by_id(doc_oid).await?;
+        check_owner(existing.map(|item| item.customer_id), oid, &id)?;
+
+        Ok(experience_repo.update_by_id(doc_oid, Json(input)).await?)
+    }
+
+    async fn delete_experience(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<Experience>> {
+        let oid = admin_oid(ctx)?;
+        let experience_repo = ctx.data::<ExperienceService>()?;
+
+        let doc_oid = parse_oid(&id)?;
+        let existing = experience_repo.find_by_id(doc_oid).await?;
+        check_owner(existing.map(|item| item.customer_id), oid, &id)?;
+
+        Ok(experience_repo.delete_by_id(doc_oid).await?)
+    }
+}
@@ -2,7 +2,12 @@
 extern crate rocket;
 
 use dotenv::dotenv;
-use rocket::fs::FileServer;
+use mongodb::Database;
+use rocket::{
+    fairing::AdHoc,
+    fs::{FileServer, Options},
+    Build, Rocket,
+};
 use rocket_okapi::{
     openapi_get_routes,
     swagger_ui::{make_swagger_ui, SwaggerUIConfig},
@@ -13,12 +18,17 @@ use std::{
     sync::Arc,
 };
 
+mod cli;
 mod db;
 mod errors;
 mod fairings;
+mod graphql;
 mod models;
+mod notifications;
 mod request_guards;
+mod responders;
 mod routes;
+mod storage;
 mod utils;
 
 pub struct Container {
@@ -49,57 +59,319 @@ impl Default for Container {
     }
 }
 
-#[launch]
-async fn rocket() -> _ {
-    dotenv().ok();
+// Repositories and services, shared by the server and the command line.
+fn build_container(database: &Database) -> Container {
     let mut container = Container::new();
 
-    let database = db::connect().await.unwrap();
+    let content_cache = Arc::new(db::cache::ContentCache::new());
+    let content_events = Arc::new(db::events::ContentEvents::new());
+
+    let article_repo = db::article::ArticleRepo::new(database.clone());
+    let article_service: Arc<dyn routes::traits::ArticleRepository + Send + Sync> =
+        Arc::new(db::cache::CachedRepo::new(
+            db::events::EventedRepo::new(article_repo, content_events.clone()),
+            content_cache.clone(),
+        ));
+
+    let certification_repo = db::certification::CertificationRepo::new(database.clone());
+    let certification_service: Arc<dyn routes::traits::CertificationRepository + Send + Sync> =
+        Arc::new(db::cache::CachedRepo::new(
+            db::events::EventedRepo::new(certification_repo, content_events.clone()),
+            content_cache.clone(),
+        ));
+
+    let collection_repo = db::collection::CustomCollectionRepo::new(database.clone());
+    let collection_service: Arc<dyn routes::traits::CustomCollectionRepository + Send + Sync> =
+        Arc::new(db::cache::CachedRepo::new(
+            db::events::EventedRepo::new(collection_repo, content_events.clone()),
+            content_cache.clone(),
+        ));
+
+    let entry_repo = db::collection::CustomEntryRepo::new(database.clone());
+    let entry_service: Arc<dyn routes::traits::CustomEntryRepository + Send + Sync> =
+        Arc::new(db::cache::CachedRepo::new(
+            db::events::EventedRepo::new(entry_repo, content_events.clone()),
+            content_cache.clone(),
+        ));
 
     let customer_repo = db::customer::CustomerRepo::new(database.clone());
     let customer_service: Arc<dyn routes::traits::CustomerRepository + Send + Sync> =
-        Arc::new(customer_repo);
+        Arc::new(db::cache::CachedRepo::new(
+            db::events::EventedRepo::new(customer_repo, content_events.clone()),
+            content_cache.clone(),
+        ));
+
+    let education_repo = db::education::EducationRepo::new(database.clone());
+    let education_service: Arc<dyn routes::traits::EducationRepository + Send + Sync> =
+        Arc::new(db::cache::CachedRepo::new(
+            db::events::EventedRepo::new(education_repo, content_events.clone()),
+            content_cache.clone(),
+        ));
 
     let experience_repo = db::experience::ExperienceRepo::new(database.clone());
     let experience_service: Arc<dyn routes::traits::ExperienceRepository + Send + Sync> =
-        Arc::new(experience_repo);
+        Arc::new(db::cache::CachedRepo::new(
+            db::events::EventedRepo::new(experience_repo, content_events.clone()),
+            content_cache.clone(),
+        ));
 
     let project_repo = db::project::ProjectRepo::new(database.clone());
     let project_service: Arc<dyn routes::traits::ProjectRepository + Send + Sync> =
-        Arc::new(project_repo);
+        Arc::new(db::cache::CachedRepo::new(
+            db::events::EventedRepo::new(project_repo, content_events.clone()),
+            content_cache.clone(),
+        ));
+
+    let service_repo = db::service::ServiceRepo::new(database.clone());
+    let service_service: Arc<dyn routes::traits::ServiceRepository + Send + Sync> =
+        Arc::new(db::cache::CachedRepo::new(
+            db::events::EventedRepo::new(service_repo, content_events.clone()),
+            content_cache.clone(),
+        ));
 
-    let skill_repo = db::skill::SkillRepo::new(database.clone());
+    let skill_repo = db::skill::SkillRepo::new(
+        database.clone(),
+        models::skill::SkillDeletePolicy::from_env(),
+    );
     let skill_service: Arc<dyn routes::traits::SkillRepository + Send + Sync> =
-        Arc::new(skill_repo);
+        Arc::new(db::cache::CachedRepo::new(
+            db::events::EventedRepo::new(skill_repo, content_events.clone()),
+            content_cache.clone(),
+        ));
 
     let social_repo = db::social::SocialRepo::new(database.clone());
     let social_service: Arc<dyn routes::traits::SocialRepository + Send + Sync> =
-        Arc::new(social_repo);
+        Arc::new(db::cache::CachedRepo::new(
+            db::events::EventedRepo::new(social_repo, content_events.clone()),
+            content_cache.clone(),
+        ));
+
+    let testimonial_repo = db::testimonial::TestimonialRepo::new(database.clone());
+    let testimonial_service: Arc<dyn routes::traits::TestimonialRepository + Send + Sync> =
+        Arc::new(db::cache::CachedRepo::new(
+            db::events::EventedRepo::new(testimonial_repo, content_events.clone()),
+            content_cache.clone(),
+        ));
 
+    container.register(article_service);
+    container.register(certification_service);
+    container.register(collection_service);
+    container.register(entry_service);
     container.register(customer_service);
+    container.register(education_service);
     container.register(experience_service);
     container.register(project_service);
+    container.register(service_service);
     container.register(skill_service);
     container.register(social_service);
+    container.register(testimonial_service);
+    container.register(content_cache);
+
+    let media_repo = db::media::MediaRepo::new(database.clone());
+    let media_service: Arc<dyn routes::traits::MediaRepository + Send + Sync> =
+        Arc::new(media_repo);
+
+    container.register(media_service);
+    container.register(storage::from_env());
+
+    let contact_repo = db::contact::ContactRepo::new(database.clone());
+    let contact_service: Arc<dyn routes::traits::ContactRepository + Send + Sync> =
+        Arc::new(contact_repo);
+
+    container.register(contact_service);
+    container.register(notifications::from_env());
+
+    let archive_service: Arc<dyn routes::traits::ArchiveRepository + Send + Sync> =
+        Arc::new(db::archive::ArchiveRepo::new(database.clone()));
+
+    container.register(archive_service);
+
+    let cv_service: Arc<dyn routes::traits::CvRepository + Send + Sync> =
+        Arc::new(db::cv::CvRepo::new(database.clone()));
+
+    container.register(cv_service);
+
+    let search_service: Arc<dyn routes::traits::SearchRepository + Send + Sync> =
+        Arc::new(db::search::SearchRepo::new(database.clone()));
+
+    container.register(search_service);
+
+    let webhook_service: Arc<dyn routes::traits::WebhookRepository + Send + Sync> =
+        Arc::new(db::webhook::WebhookRepo::new(database.clone()));
+    let deliveries = Arc::new(notifications::delivery::Deliveries::new(
+        webhook_service.clone(),
+    ));
+    content_events.listen(deliveries.clone());
+    if content_events.change_stream() {
+        content_events.listen(Arc::new(db::events::EventLog::new(database.clone())));
+    }
+
+    container.register(webhook_service);
+    container.register(deliveries);
+    container.register(content_events);
+
+    container
+}
+
+#[rocket::main]
+async fn main() {
+    dotenv().ok();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        let database = db::connect().await.unwrap();
+        if let Err(error) = cli::run(&args, &build_container(&database)).await {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // an error is reported when dropped, like `#[launch]` does
+    let _ = rocket().launch().await;
+}
+
+// Connects to the database on ignition, registers the services and starts the
+// background tasks.
+fn services() -> AdHoc {
+    AdHoc::on_ignite("Connecting to MongoDB", |rocket| async {
+        let database = match db::connect().await {
+            Ok(database) => database,
+            Err(error) => panic!("Cannot connect to instance:: {:?}", error),
+        };
+        let container = build_container(&database);
+
+        // text indexes are built in the background, searches fail until they exist
+        let search_database = database.clone();
+        rocket::tokio::spawn(async move {
+            if let Err(error) = db::search::ensure_indexes(&search_database).await {
+                eprintln!("Failed to create the search indexes: {error}");
+            }
+        });
+
+        let article_database = database.clone();
+        rocket::tokio::spawn(async move {
+            if let Err(error) = db::article::ensure_indexes(&article_database).await {
+                eprintln!("Failed to create the article indexes: {error}");
+            }
+        });
+
+        let webhook_database = database.clone();
+        rocket::tokio::spawn(async move {
+            if let Err(error) = db::webhook::ensure_indexes(&webhook_database).await {
+                eprintln!("Failed to create the webhook indexes: {error}");
+            }
+        });
+
+        let cv_database = database.clone();
+        rocket::tokio::spawn(async move {
+            if let Err(error) = db::cv::ensure_indexes(&cv_database).await {
+                eprintln!("Failed to create the CV indexes: {error}");
+            }
+        });
+        if let Some(deliveries) = container.get::<Arc<notifications::delivery::Deliveries>>() {
+            rocket::tokio::spawn(deliveries.clone().run());
+        }
+        if let Some(events) = container.get::<Arc<db::events::ContentEvents>>() {
+            rocket::tokio::spawn(db::events::watch_scheduled(
+                database.clone(),
+                events.clone(),
+            ));
+            if events.change_stream() {
+                rocket::tokio::spawn(db::events::watch_event_log(
+                    database.clone(),
+                    events.clone(),
+                ));
+            }
+        }
+
+        let schema = graphql::build_schema(&container);
+
+        rocket.manage(container).manage(schema)
+    })
+}
+
+fn rocket() -> Rocket<Build> {
+    dotenv().ok();
 
     rocket::build()
         // .attach(db::init())
-        .manage(container)
+        .attach(services())
+        .manage(responders::cache::CacheControl::new())
+        .manage(utils::spam::ContactProtection::new())
+        .manage(utils::cv::CvTemplates::from_env())
         .attach(fairings::cors::Cors::new())
-        // Serve React frontend from /var/www/html (as copied in Dockerfile)
-        .mount("/", FileServer::from("public"))
+        // Serve React frontend from /var/www/html (as copied in Dockerfile), the API runs
+        // without it
+        .mount(
+            "/",
+            FileServer::new("public", Options::Index | Options::Missing),
+        )
         .mount(
             "/api",
             openapi_get_routes![
                 routes::index,
+                routes::archive::export,
+                routes::archive::import,
+                routes::article::get,
+                routes::article::get_all,
+                routes::article::get_by_slug,
+                routes::article::get_by_id,
+                routes::article::post,
+                routes::article::patch_by_id,
+                routes::article::delete_by_id,
+                routes::article::render,
                 routes::auth::login,
                 routes::auth::register,
+                routes::cache::get_stats,
+                routes::certification::get,
+                routes::certification::get_all,
+                routes::certification::get_by_id,
+                routes::certification::post,
+                routes::certification::patch_by_id,
+                routes::certification::patch_many,
+                routes::certification::delete_by_id,
+                routes::collection::get,
+                routes::collection::get_all,
+                routes::collection::get_by_id,
+                routes::collection::post,
+                routes::collection::patch_by_id,
+                routes::collection::delete_by_id,
+                routes::collection::get_entries,
+                routes::collection::get_public_entries,
+                routes::collection::get_entry,
+                routes::collection::post_entry,
+                routes::collection::patch_entry,
+                routes::collection::patch_entries,
+                routes::collection::delete_entry,
+                routes::contact::token,
+                routes::contact::send,
+                routes::contact::get_all,
+                routes::contact::get_by_id,
+                routes::contact::patch_by_id,
+                routes::contact::delete_by_id,
                 // routes::customer::get_customers,
                 routes::customer::get_customer_by_id,
                 routes::customer::get_customer_profile,
+                routes::customer::get_customer_vcard,
+                routes::customer::get_customer_json_ld,
                 // routes::customer::post_customer,
                 routes::customer::patch_customer_by_id,
                 // routes::customer::delete_customer_by_id,
+                routes::cv::get_html,
+                routes::cv::get_pdf,
+                routes::cv::get_settings,
+                routes::cv::patch_settings,
+                routes::cv::post_share,
+                routes::cv::delete_share,
+                routes::education::get,
+                routes::education::get_all,
+                routes::education::get_by_id,
+                routes::education::post,
+                routes::education::patch_by_id,
+                routes::education::patch_many,
+                routes::education::delete_by_id,
+                routes::events::get,
                 routes::experience::get,
                 routes::experience::get_all,
                 routes::experience::get_by_id,
@@ -107,6 +379,13 @@ async fn rocket() -> _ {
                 routes::experience::patch_by_id,
                 routes::experience::patch_many,
                 routes::experience::delete_by_id,
+                routes::media::get_all,
+                routes::media::get_by_id,
+                routes::media::post,
+                routes::graphql::post,
+                routes::graphql::graphiql,
+                routes::media::delete_by_id,
+                routes::portfolio::get,
                 routes::project::get,
                 routes::project::get_all,
                 routes::project::get_by_id,
@@ -114,9 +393,28 @@ async fn rocket() -> _ {
                 routes::project::patch_by_id,
                 routes::project::patch_many,
                 routes::project::delete_by_id,
+                routes::resume::get,
+                routes::resume::import,
+                routes::search::get,
+                routes::service::get,
+                routes::service::get_all,
+                routes::service::get_by_id,
+                routes::service::post,
+                routes::service::patch_by_id,
+                routes::service::patch_many,
+                routes::service::delete_by_id,
+                routes::seo::sitemap,
+                routes::seo::robots,
+                routes::seo::atom,
+                routes::seo::rss,
+                routes::seo::json,
+                routes::seo::meta,
+                routes::site::build,
                 routes::skill::get,
                 routes::skill::get_all,
                 routes::skill::get_by_id,
+                routes::skill::get_projects,
+                routes::skill::get_experiences,
                 routes::skill::post,
                 routes::skill::patch_by_id,
                 routes::skill::patch_many,
@@ -128,9 +426,30 @@ async fn rocket() -> _ {
                 routes::social::patch_by_id,
                 routes::social::patch_many,
                 routes::social::delete_by_id,
+                routes::testimonial::get,
+                routes::testimonial::get_all,
+                routes::testimonial::get_by_id,
+                routes::testimonial::submit,
+                routes::testimonial::patch_by_id,
+                routes::testimonial::approve,
+                routes::testimonial::reject,
+                routes::testimonial::patch_many,
+                routes::testimonial::delete_by_id,
+                routes::translation::get_missing,
+                routes::webhook::get_all,
+                routes::webhook::get_by_id,
+                routes::webhook::post,
+                routes::webhook::patch_by_id,
+                routes::webhook::delete_by_id,
+                routes::webhook::rotate_secret,
+                routes::webhook::ping,
+                routes::webhook::get_deliveries,
+                routes::webhook::get_delivery,
+                routes::webhook::redeliver,
             ],
         )
         .mount("/api", routes![routes::all_options_handler])
+        .mount("/media", routes![routes::media::serve])
         .mount(
             "/api-docs",
             make_swagger_ui(&SwaggerUIConfig {
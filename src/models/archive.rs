@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use mongodb::bson::{oid::ObjectId, Document};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// `format` of every archive, anything else is rejected on import.
+pub const ARCHIVE_FORMAT: &str = "portfolio-cms";
+/// Version of the archive layout written by this build, older ones are migrated on import.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Complete portfolio of a customer, stored as `portfolio.json` at the root of the ZIP archive.
+/// <br />Documents are in relaxed extended JSON so ids and dates survive the round trip.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct PortfolioArchive {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    /// customer the archive was exported from
+    pub customer_id: String,
+    /// customer fields, without credentials
+    pub profile: Map<String, Value>,
+    /// documents of every section, in their display order
+    pub sections: BTreeMap<String, Vec<Value>>,
+}
+
+/// How an archive is applied to the portfolio.
+#[derive(
+    Debug, Serialize, Deserialize, JsonSchema, FromFormField, Clone, Copy, PartialEq, Eq, Default,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    /// add and update documents, keep the ones missing from the archive
+    #[default]
+    Merge,
+    /// the portfolio ends up holding exactly the archive
+    Replace,
+}
+
+/// Changes an import makes to a section.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct SectionDiff {
+    pub section: String,
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub deleted: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct ImportReport {
+    pub mode: ImportMode,
+    /// nothing was written
+    pub dry_run: bool,
+    /// version of the archive before migration
+    pub version: u32,
+    /// whether the profile fields change
+    pub profile_updated: bool,
+    pub sections: Vec<SectionDiff>,
+    /// documents that received a new id, because they do not exist in this portfolio
+    pub remapped: usize,
+    /// media files written to the media store
+    pub media_files: usize,
+}
+
+/// Writes computed from an archive, applied once the media files are stored.
+#[derive(Debug, Clone)]
+pub struct ImportPlan {
+    pub report: ImportReport,
+    /// customer fields to set, empty when unchanged
+    pub profile: Document,
+    pub sections: Vec<SectionPlan>,
+    /// (key in the archive, key in the media store)
+    pub media_files: Vec<(String, String)>,
+    /// media store keys of deleted media
+    pub deleted_files: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SectionPlan {
+    pub collection: &'static str,
+    /// documents inserted or replaced
+    pub documents: Vec<Document>,
+    pub deleted: Vec<ObjectId>,
+}
@@ -0,0 +1,115 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::models::seo::Seo;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArticleDocument {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub customer_id: ObjectId,
+    pub title: String,
+    /// unique per customer
+    pub slug: String,
+    /// Markdown source
+    pub body: String,
+    /// sanitized HTML rendered from `body`
+    pub html: String,
+    pub excerpt: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub cover_link: Option<String>,
+    /// media used as the cover, `cover_link` then points to it
+    pub cover_id: Option<ObjectId>,
+    pub cover_srcset: Option<String>,
+    pub cover_blurhash: Option<String>,
+    /// minutes
+    pub reading_time: i32,
+    /// drafts have no publish date
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub seo: Option<Seo>,
+    /// createdAt
+    #[serde(
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime",
+        rename = "created_at"
+    )]
+    pub created_at: DateTime<Utc>,
+    /// updatedAt
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, SimpleObject, Clone)]
+pub struct Article {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub customer_id: String,
+    pub title: String,
+    pub slug: String,
+    /// Markdown source
+    pub body: String,
+    /// sanitized HTML rendered from `body`
+    pub html: String,
+    pub excerpt: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub cover_link: Option<String>,
+    pub cover_id: Option<String>,
+    /// `srcset` of the cover when it is an uploaded media
+    pub cover_srcset: Option<String>,
+    /// blurhash placeholder of the cover
+    pub cover_blurhash: Option<String>,
+    /// estimated reading time in minutes
+    pub reading_time: i32,
+    pub published_at: Option<String>,
+    /// meta tags of the article page
+    pub seo: Option<Seo>,
+    /// createdAt
+    pub created_at: String,
+    /// updatedAt
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct ArticleInput {
+    pub title: String,
+    /// generated from the title when empty
+    pub slug: Option<String>,
+    /// Markdown
+    pub body: String,
+    pub excerpt: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub cover_link: Option<String>,
+    /// id of an uploaded media, takes precedence over `cover_link`
+    pub cover_id: Option<String>,
+    /// RFC 3339 date, e.g. `2024-01-01T00:00:00Z`. The article stays a draft without it
+    /// and is only public once the date is reached.
+    pub published_at: Option<String>,
+    /// meta tags of the article page
+    pub seo: Option<Seo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct MarkdownInput {
+    /// Markdown
+    pub body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct RenderedMarkdown {
+    /// sanitized HTML
+    pub html: String,
+    /// estimated reading time in minutes
+    pub reading_time: i32,
+}
@@ -0,0 +1,14 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct CacheStats {
+    /// lookups answered from the cache
+    pub hits: u64,
+    /// lookups that went to the database
+    pub misses: u64,
+    pub entries: u64,
+    pub max_entries: u64,
+    /// time to live of an entry, in seconds
+    pub ttl: u64,
+}
@@ -0,0 +1,83 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CertificationDocument {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub customer_id: ObjectId,
+    pub name: String,
+    pub issuer: String,
+    pub credential_id: Option<String>,
+    pub credential_url: Option<String>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub issue_date: DateTime<Utc>,
+    /// certifications without one never expire
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub expiry_date: Option<DateTime<Utc>>,
+    pub order: i32,
+    /// createdAt
+    #[serde(
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime",
+        rename = "created_at"
+    )]
+    pub created_at: DateTime<Utc>,
+    /// updatedAt
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, SimpleObject, Clone)]
+pub struct Certification {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub customer_id: String,
+    pub name: String,
+    pub issuer: String,
+    pub credential_id: Option<String>,
+    pub credential_url: Option<String>,
+    pub issue_date: String,
+    pub expiry_date: Option<String>,
+    pub order: i32,
+    /// createdAt
+    pub created_at: String,
+    /// updatedAt
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct CertificationInput {
+    pub name: String,
+    pub issuer: String,
+    pub credential_id: Option<String>,
+    pub credential_url: Option<String>,
+    /// RFC 3339 date, e.g. `2023-03-01T00:00:00Z`
+    pub issue_date: String,
+    /// RFC 3339 date, e.g. `2026-03-01T00:00:00Z`
+    pub expiry_date: Option<String>,
+    pub order: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct CertificationsInput {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub name: String,
+    pub issuer: String,
+    pub credential_id: Option<String>,
+    pub credential_url: Option<String>,
+    pub issue_date: String,
+    pub expiry_date: Option<String>,
+    pub order: i32,
+}
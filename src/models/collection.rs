@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use mongodb::bson::{oid::ObjectId, Document};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomCollectionDocument {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub customer_id: ObjectId,
+    pub name: String,
+    /// unique per customer, used in the entry urls
+    pub slug: String,
+    pub description: Option<String>,
+    /// JSON Schema of the entries, kept as text so keywords like `$ref` can be stored
+    pub schema: String,
+    /// createdAt
+    #[serde(
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime",
+        rename = "created_at"
+    )]
+    pub created_at: DateTime<Utc>,
+    /// updatedAt
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Section defined by a customer, e.g. awards or talks.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct CustomCollection {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub customer_id: String,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    /// JSON Schema every entry is validated against
+    pub schema: Value,
+    /// createdAt
+    pub created_at: String,
+    /// updatedAt
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct CustomCollectionInput {
+    pub name: String,
+    /// generated from the name when empty
+    pub slug: Option<String>,
+    pub description: Option<String>,
+    /// JSON Schema of an `object`, e.g.
+    /// `{"type": "object", "properties": {"title": {"type": "string"}}, "required": ["title"]}`
+    pub schema: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomEntryDocument {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub customer_id: ObjectId,
+    pub collection_id: ObjectId,
+    pub data: Document,
+    pub order: i32,
+    /// createdAt
+    #[serde(
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime",
+        rename = "created_at"
+    )]
+    pub created_at: DateTime<Utc>,
+    /// updatedAt
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct CustomEntry {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub customer_id: String,
+    pub collection_id: String,
+    /// fields described by the collection schema
+    pub data: Value,
+    pub order: i32,
+    /// createdAt
+    pub created_at: String,
+    /// updatedAt
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct CustomEntryInput {
+    /// validated against the collection schema
+    pub data: Value,
+    pub order: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct CustomEntriesInput {
+    #[serde(rename = "_id")]
+    pub id: String,
+    /// left untouched when not given, e.g. to only reorder
+    pub data: Option<Value>,
+    pub order: i32,
+}
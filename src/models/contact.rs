@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContactMessageDocument {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub customer_id: ObjectId,
+    pub name: String,
+    pub email: String,
+    pub subject: Option<String>,
+    pub message: String,
+    /// address the message was sent from
+    pub ip: Option<String>,
+    pub read: bool,
+    pub archived: bool,
+    /// createdAt
+    #[serde(
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime",
+        rename = "created_at"
+    )]
+    pub created_at: DateTime<Utc>,
+    /// updatedAt
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct ContactMessage {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub customer_id: String,
+    pub name: String,
+    pub email: String,
+    pub subject: Option<String>,
+    pub message: String,
+    pub ip: Option<String>,
+    pub read: bool,
+    pub archived: bool,
+    /// createdAt
+    pub created_at: String,
+    /// updatedAt
+    pub updated_at: Option<String>,
+}
+
+/// Message sent through the contact form of a portfolio.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct ContactInput {
+    pub name: String,
+    pub email: String,
+    pub subject: Option<String>,
+    pub message: String,
+    /// honeypot, keep this field hidden from humans and empty
+    #[serde(default)]
+    pub website: Option<String>,
+    /// token returned by `GET /contact/token` when the form is shown
+    pub token: String,
+    /// proof-of-work nonce, required when the token has a difficulty
+    pub nonce: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct ContactToken {
+    pub token: String,
+    /// leading zero bits `sha256("{token}:{nonce}")` must have, 0 when no proof-of-work is asked
+    pub difficulty: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct ContactMessageUpdate {
+    pub read: Option<bool>,
+    pub archived: Option<bool>,
+}
+
+/// Filters of the inbox listing.
+#[derive(Debug, Default, Clone)]
+pub struct ContactFilter {
+    pub read: Option<bool>,
+    pub archived: bool,
+}
@@ -1,8 +1,12 @@
+use async_graphql::{InputObject, SimpleObject};
 use chrono::{DateTime, Utc};
 use mongodb::bson::oid::ObjectId;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::models::seo::Seo;
+use crate::utils::i18n::Translations;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CustomerDocument {
     /// Document Id
@@ -17,6 +21,29 @@ pub struct CustomerDocument {
     pub intro: Option<String>,
     pub about: Option<String>,
     pub profile_picture: Option<String>,
+    /// media used as the profile picture, `profile_picture` then points to it
+    #[serde(default)]
+    pub profile_picture_id: Option<ObjectId>,
+    #[serde(default)]
+    pub profile_picture_srcset: Option<String>,
+    /// blurhash placeholder of the profile picture
+    #[serde(default)]
+    pub profile_picture_blurhash: Option<String>,
+    /// locale of `intro` and `about`, `en` when not set
+    #[serde(default)]
+    pub default_locale: Option<String>,
+    /// other locales of the portfolio, tried in this order when a translation is missing
+    #[serde(default)]
+    pub locales: Vec<String>,
+    /// `intro` and `about` in other locales
+    #[serde(default)]
+    pub translations: Translations,
+    /// absolute url of the portfolio site, e.g. `https://jane.dev`
+    #[serde(default)]
+    pub site_url: Option<String>,
+    /// meta tags of the home page
+    #[serde(default)]
+    pub seo: Option<Seo>,
     pub password: String,
     /// createdAt
     #[serde(
@@ -24,9 +51,16 @@ pub struct CustomerDocument {
         rename = "createdAt"
     )]
     pub created_at: DateTime<Utc>,
+    /// updatedAt
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime",
+        rename = "updatedAt"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, SimpleObject, Clone)]
 pub struct Customer {
     /// Document Id
     #[serde(rename = "_id")]
@@ -40,11 +74,30 @@ pub struct Customer {
     pub intro: Option<String>,
     pub about: Option<String>,
     pub profile_picture: Option<String>,
+    pub profile_picture_id: Option<String>,
+    /// `srcset` of the profile picture when it is an uploaded media
+    pub profile_picture_srcset: Option<String>,
+    /// blurhash placeholder of the profile picture
+    pub profile_picture_blurhash: Option<String>,
+    /// locale of `intro` and `about`, `en` when not set
+    pub default_locale: Option<String>,
+    /// other locales of the portfolio, tried in this order when a translation is missing
+    pub locales: Vec<String>,
+    /// `intro` and `about` in other locales
+    pub translations: Translations,
+    /// absolute url of the portfolio site, e.g. `https://jane.dev`
+    pub site_url: Option<String>,
+    /// meta tags of the home page
+    pub seo: Option<Seo>,
     #[serde(skip_serializing)]
+    #[graphql(skip)]
     pub password: String,
     /// createdAt
     #[serde(rename = "createdAt")]
     pub created_at: String,
+    /// updatedAt
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
@@ -61,7 +114,7 @@ pub struct CustomerInput {
     pub password: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, InputObject, Clone)]
 pub struct CustomerUpdateInput {
     /// customer name
     pub name: String,
@@ -71,4 +124,17 @@ pub struct CustomerUpdateInput {
     pub intro: Option<String>,
     pub about: Option<String>,
     pub profile_picture: Option<String>,
+    /// id of an uploaded media, takes precedence over `profile_picture`
+    pub profile_picture_id: Option<String>,
+    /// locale of `intro` and `about`, e.g. `en`
+    pub default_locale: Option<String>,
+    /// other locales of the portfolio in fallback order, e.g. `["id"]`
+    pub locales: Option<Vec<String>>,
+    /// `intro` and `about` by locale, e.g. `{"id": {"intro": "Halo"}}`
+    pub translations: Option<Translations>,
+    /// absolute url of the portfolio site, e.g. `https://jane.dev`. Links of the sitemap, the
+    /// feeds and the meta tags start with it.
+    pub site_url: Option<String>,
+    /// meta tags of the home page
+    pub seo: Option<Seo>,
 }
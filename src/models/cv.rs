@@ -0,0 +1,80 @@
+use mongodb::bson::oid::ObjectId;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Template used when the customer did not pick one.
+pub const DEFAULT_CV_TEMPLATE: &str = "classic";
+
+/// CV fields of a customer document.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CvSettingsDocument {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    #[serde(default)]
+    pub cv_template: Option<String>,
+    #[serde(default)]
+    pub cv_share_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct CvSettings {
+    /// template of `GET /cv.html` and `GET /cv.pdf`
+    pub template: String,
+    /// templates to pick from
+    pub templates: Vec<String>,
+    /// token opening the CV without an `X-API-KEY`, missing while sharing is off
+    pub share_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct CvSettingsInput {
+    /// one of `templates`
+    pub template: String,
+}
+
+/// Everything a CV template renders, already translated.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct Cv {
+    pub name: String,
+    pub email: String,
+    pub phone: Option<String>,
+    pub intro: Option<String>,
+    pub about: Option<String>,
+    pub picture: Option<String>,
+    pub links: Vec<CvLink>,
+    pub experiences: Vec<CvEntry>,
+    pub educations: Vec<CvEntry>,
+    pub projects: Vec<CvEntry>,
+    pub skills: Vec<CvSkillGroup>,
+    /// locale of the content, for `<html lang>`
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CvLink {
+    pub name: String,
+    pub url: String,
+}
+
+/// An experience, an education or a project.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct CvEntry {
+    /// position, degree or project name
+    pub title: String,
+    /// company, institution or client
+    pub subtitle: Option<String>,
+    pub location: Option<String>,
+    /// e.g. `Mar 2021 – Present`
+    pub period: Option<String>,
+    pub url: Option<String>,
+    pub summary: Option<String>,
+    pub highlights: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CvSkillGroup {
+    /// missing for skills without a category
+    pub category: Option<String>,
+    pub skills: Vec<String>,
+}
@@ -0,0 +1,89 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EducationDocument {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub customer_id: ObjectId,
+    pub institution: String,
+    pub degree: String,
+    /// field of study
+    pub field: Option<String>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub start_date: DateTime<Utc>,
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub end_date: Option<DateTime<Utc>>,
+    pub grade: Option<String>,
+    pub description: Option<Vec<String>>,
+    pub order: i32,
+    /// createdAt
+    #[serde(
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime",
+        rename = "created_at"
+    )]
+    pub created_at: DateTime<Utc>,
+    /// updatedAt
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, SimpleObject, Clone)]
+pub struct Education {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub customer_id: String,
+    pub institution: String,
+    pub degree: String,
+    /// field of study
+    pub field: Option<String>,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub grade: Option<String>,
+    pub description: Option<Vec<String>>,
+    pub order: i32,
+    /// createdAt
+    pub created_at: String,
+    /// updatedAt
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct EducationInput {
+    pub institution: String,
+    pub degree: String,
+    /// field of study
+    pub field: Option<String>,
+    /// RFC 3339 date, e.g. `2018-09-01T00:00:00Z`
+    pub start_date: String,
+    /// RFC 3339 date, e.g. `2022-06-30T00:00:00Z`
+    pub end_date: Option<String>,
+    pub grade: Option<String>,
+    pub description: Option<Vec<String>>,
+    pub order: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct EducationsInput {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub institution: String,
+    pub degree: String,
+    pub field: Option<String>,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub grade: Option<String>,
+    pub description: Option<Vec<String>>,
+    pub order: i32,
+}
@@ -0,0 +1,125 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Sections changes are reported for, `profile` being the customer itself.
+pub const EVENT_SECTIONS: [&str; 12] = [
+    "profile",
+    "project",
+    "skill",
+    "social",
+    "experience",
+    "article",
+    "education",
+    "certification",
+    "testimonial",
+    "service",
+    "collection",
+    "entry",
+];
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentAction {
+    Created,
+    Updated,
+    Deleted,
+    /// an article reached its publish date, or a testimonial was approved
+    Published,
+}
+
+impl ContentAction {
+    pub const ALL: [ContentAction; 4] = [
+        ContentAction::Created,
+        ContentAction::Updated,
+        ContentAction::Deleted,
+        ContentAction::Published,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentAction::Created => "created",
+            ContentAction::Updated => "updated",
+            ContentAction::Deleted => "deleted",
+            ContentAction::Published => "published",
+        }
+    }
+}
+
+/// A change of the content of a portfolio.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct ContentEvent {
+    /// `<section>.<action>`, e.g. `project.updated`
+    pub event: String,
+    pub section: String,
+    pub action: ContentAction,
+    pub customer_id: String,
+    /// id of the changed document
+    pub id: String,
+    /// the document after the change, or the removed one for `deleted`
+    pub data: Option<Value>,
+    /// whether the document is shown through the public endpoints, e.g. not a draft article or
+    /// a pending testimonial; other events only reach the customer itself
+    #[serde(default)]
+    pub public: bool,
+    /// RFC 3339
+    pub occurred_at: String,
+}
+
+impl ContentEvent {
+    pub fn new(
+        section: &str,
+        action: ContentAction,
+        customer_id: &str,
+        id: &str,
+        data: Option<Value>,
+    ) -> Self {
+        Self {
+            event: format!("{section}.{}", action.as_str()),
+            section: section.to_string(),
+            action,
+            customer_id: customer_id.to_string(),
+            id: id.to_string(),
+            data,
+            public: true,
+            occurred_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Whether `event` is selected by `pattern`: `*`, `project.*`, `*.deleted` or an exact name.
+pub fn event_matches(pattern: &str, event: &str) -> bool {
+    if pattern == "*" || pattern == event {
+        return true;
+    }
+    let (Some((pattern_section, pattern_action)), Some((section, action))) =
+        (pattern.split_once('.'), event.split_once('.'))
+    else {
+        return false;
+    };
+    (pattern_section == "*" || pattern_section == section)
+        && (pattern_action == "*" || pattern_action == action)
+}
+
+/// Check event patterns, the error is the message to send back.
+pub fn validate_event_patterns(patterns: &[String]) -> Result<(), String> {
+    for pattern in patterns {
+        if pattern == "*" {
+            continue;
+        }
+        let valid = pattern.split_once('.').is_some_and(|(section, action)| {
+            (section == "*" || EVENT_SECTIONS.contains(&section))
+                && (action == "*"
+                    || ContentAction::ALL
+                        .iter()
+                        .any(|known| known.as_str() == action))
+        });
+        if !valid {
+            return Err(format!(
+                "Unknown event {pattern}, expected `<section>.<action>` with a section among {} and an action among created, updated, deleted and published, or `*`",
+                EVENT_SECTIONS.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
@@ -1,8 +1,11 @@
+use async_graphql::{InputObject, SimpleObject};
 use chrono::{DateTime, Utc};
 use mongodb::bson::oid::ObjectId;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::utils::i18n::Translations;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ExperienceDocument {
     /// Document Id
@@ -19,15 +22,48 @@ pub struct ExperienceDocument {
     pub position: String,
     pub description: Option<Vec<String>>,
     pub order: i32,
+    /// skills used in the position
+    #[serde(default)]
+    pub skill_ids: Vec<ObjectId>,
+    /// projects made in the position
+    #[serde(default)]
+    pub project_ids: Vec<ObjectId>,
+    /// `position` and `description` in other locales
+    #[serde(default)]
+    pub translations: Translations,
     /// createdAt
     #[serde(
         with = "bson::serde_helpers::chrono_datetime_as_bson_datetime",
         rename = "created_at"
     )]
     pub created_at: DateTime<Utc>,
+    /// updatedAt
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+/// Fields that can be selected with `?fields=`, `_id` is always returned.
+pub const EXPERIENCE_FIELDS: [&str; 14] = [
+    "customer_id",
+    "company",
+    "work_type",
+    "location",
+    "start_date",
+    "end_date",
+    "position",
+    "description",
+    "order",
+    "skill_ids",
+    "project_ids",
+    "translations",
+    "created_at",
+    "updated_at",
+];
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, SimpleObject, Clone)]
 pub struct Experience {
     /// Document Id
     #[serde(rename = "_id")]
@@ -41,11 +77,19 @@ pub struct Experience {
     pub position: String,
     pub description: Option<Vec<String>>,
     pub order: i32,
+    /// skills used in the position
+    pub skill_ids: Vec<String>,
+    /// projects made in the position
+    pub project_ids: Vec<String>,
+    /// `position` and `description` in other locales
+    pub translations: Translations,
     /// createdAt
     pub created_at: String,
+    /// updatedAt
+    pub updated_at: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, InputObject, Clone)]
 pub struct ExperienceInput {
     pub company: String,
     pub work_type: String,
@@ -55,6 +99,13 @@ pub struct ExperienceInput {
     pub position: String,
     pub description: Option<Vec<String>>,
     pub order: i32,
+    /// ids of skills of the customer used in the position
+    pub skill_ids: Option<Vec<String>>,
+    /// ids of projects of the customer made in the position
+    pub project_ids: Option<Vec<String>>,
+    /// `position` and `description` by locale, `description` with one item per line,
+    /// e.g. `{"id": {"position": "Pengembang"}}`
+    pub translations: Option<Translations>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
@@ -69,4 +120,29 @@ pub struct ExperiencesInput {
     pub position: String,
     pub description: Option<Vec<String>>,
     pub order: i32,
+    /// ids of skills of the customer used in the position
+    pub skill_ids: Option<Vec<String>>,
+    /// ids of projects of the customer made in the position
+    pub project_ids: Option<Vec<String>>,
+    /// `position` and `description` by locale, `description` with one item per line,
+    /// e.g. `{"id": {"position": "Pengembang"}}`
+    pub translations: Option<Translations>,
+}
+
+impl From<ExperiencesInput> for ExperienceInput {
+    fn from(item: ExperiencesInput) -> Self {
+        Self {
+            company: item.company,
+            work_type: item.work_type,
+            location: item.location,
+            start_date: item.start_date,
+            end_date: item.end_date,
+            position: item.position,
+            description: item.description,
+            order: item.order,
+            skill_ids: item.skill_ids,
+            project_ids: item.project_ids,
+            translations: item.translations,
+        }
+    }
 }
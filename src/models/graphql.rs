@@ -0,0 +1,32 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A GraphQL query or mutation.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQLRequest {
+    pub query: String,
+    /// the operation to run when `query` holds several
+    pub operation_name: Option<String>,
+    /// a JSON object
+    pub variables: Option<Value>,
+}
+
+impl From<GraphQLRequest> for async_graphql::Request {
+    fn from(input: GraphQLRequest) -> Self {
+        let mut request = async_graphql::Request::new(input.query);
+        if let Some(operation_name) = input.operation_name {
+            request = request.operation_name(operation_name);
+        }
+        if let Some(variables) = input.variables {
+            request = request.variables(async_graphql::Variables::from_json(variables));
+        }
+        request
+    }
+}
+
+/// The result of a GraphQL request, `data` and `errors` as the GraphQL spec describes them.
+#[derive(Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct GraphQLResponse(#[schemars(with = "Value")] pub async_graphql::Response);
@@ -0,0 +1,118 @@
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use rocket::fs::TempFile;
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, ObjectValidation, Schema, SchemaObject},
+    JsonSchema,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MediaDocument {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub customer_id: ObjectId,
+    /// name of the uploaded file
+    pub file_name: String,
+    /// location of the file in the media store
+    pub key: String,
+    pub mime_type: String,
+    /// size in bytes
+    pub size: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    /// resized WebP or JPEG copies of an image
+    #[serde(default)]
+    pub variants: Vec<MediaVariant>,
+    /// blurhash placeholder of an image
+    #[serde(default)]
+    pub blurhash: Option<String>,
+    /// createdAt
+    #[serde(
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime",
+        rename = "created_at"
+    )]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MediaVariant {
+    pub width: i32,
+    pub height: i32,
+    pub key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct MediaVariantUrl {
+    pub width: i32,
+    pub height: i32,
+    pub key: String,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct Media {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub customer_id: String,
+    pub file_name: String,
+    pub key: String,
+    /// public url of the file
+    pub url: String,
+    pub mime_type: String,
+    pub size: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub variants: Vec<MediaVariantUrl>,
+    /// ready to use `srcset` attribute built from the variants
+    pub srcset: Option<String>,
+    pub blurhash: Option<String>,
+    /// createdAt
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct MediaInput {
+    pub id: ObjectId,
+    pub file_name: String,
+    pub key: String,
+    pub mime_type: String,
+    pub size: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub variants: Vec<MediaVariant>,
+    pub blurhash: Option<String>,
+}
+
+#[derive(FromForm)]
+pub struct MediaUpload<'r> {
+    pub file: TempFile<'r>,
+}
+
+// `TempFile` has no schema, the form is documented as a single binary `file` field.
+impl JsonSchema for MediaUpload<'_> {
+    fn schema_name() -> String {
+        "MediaUpload".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        let file = SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            format: Some("binary".to_string()),
+            ..Default::default()
+        };
+        SchemaObject {
+            instance_type: Some(InstanceType::Object.into()),
+            object: Some(Box::new(ObjectValidation {
+                properties: [("file".to_string(), file.into())].into_iter().collect(),
+                required: ["file".to_string()].into_iter().collect(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
@@ -1,7 +1,28 @@
-pub mod customer;
+pub mod archive;
+pub mod article;
 pub mod auth;
+pub mod cache;
+pub mod certification;
+pub mod collection;
+pub mod contact;
+pub mod customer;
+pub mod cv;
+pub mod education;
+pub mod event;
 pub mod experience;
+pub mod graphql;
+pub mod media;
+pub mod portfolio;
 pub mod project;
+pub mod response;
+pub mod resume;
+pub mod search;
+pub mod seo;
+pub mod service;
+pub mod site;
 pub mod skill;
 pub mod social;
-pub mod response;
+pub mod sparse;
+pub mod testimonial;
+pub mod translation;
+pub mod webhook;
@@ -0,0 +1,35 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    article::Article, certification::Certification, customer::Customer, education::Education,
+    experience::Experience, project::Project, service::Service, skill::Skill, social::Social,
+    testimonial::Testimonial,
+};
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct Portfolio {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Customer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projects: Option<Vec<Project>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skills: Option<Vec<Skill>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socials: Option<Vec<Social>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub experiences: Option<Vec<Experience>>,
+    /// published articles only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub articles: Option<Vec<Article>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub educations: Option<Vec<Education>>,
+    /// certifications that have not expired
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub certifications: Option<Vec<Certification>>,
+    /// approved testimonials only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub testimonials: Option<Vec<Testimonial>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub services: Option<Vec<Service>>,
+}
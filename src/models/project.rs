@@ -1,8 +1,12 @@
+use async_graphql::{Enum, InputObject, SimpleObject};
 use chrono::{DateTime, Utc};
 use mongodb::bson::oid::ObjectId;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::models::seo::Seo;
+use crate::utils::i18n::Translations;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProjectDocument {
     /// Document Id
@@ -13,17 +17,91 @@ pub struct ProjectDocument {
     pub description: Option<String>,
     pub link: Option<String>,
     pub photo_link: Option<String>,
+    /// media used as the photo, `photo_link` then points to it
+    #[serde(default)]
+    pub photo_id: Option<ObjectId>,
+    #[serde(default)]
+    pub photo_srcset: Option<String>,
+    /// blurhash placeholder of the photo
+    #[serde(default)]
+    pub photo_blurhash: Option<String>,
+    /// ordered images of the project
+    #[serde(default)]
+    pub gallery: Vec<ProjectImageDocument>,
+    #[serde(default)]
+    pub links: Vec<ProjectLink>,
+    /// role of the customer in the project
+    #[serde(default)]
+    pub role: Option<String>,
+    #[serde(default)]
+    pub team_size: Option<i32>,
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub start_date: Option<DateTime<Utc>>,
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub end_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub featured: bool,
+    /// client the project was made for
+    #[serde(default)]
+    pub client: Option<String>,
     pub order: i32,
     pub stack: Option<Vec<String>>,
+    /// skills used in the project
+    #[serde(default)]
+    pub skill_ids: Vec<ObjectId>,
+    /// `name` and `description` in other locales
+    #[serde(default)]
+    pub translations: Translations,
+    #[serde(default)]
+    pub seo: Option<Seo>,
     /// createdAt
     #[serde(
         with = "bson::serde_helpers::chrono_datetime_as_bson_datetime",
         rename = "created_at"
     )]
     pub created_at: DateTime<Utc>,
+    /// updatedAt
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+/// Fields that can be selected with `?fields=`, `_id` is always returned.
+pub const PROJECT_FIELDS: [&str; 23] = [
+    "customer_id",
+    "name",
+    "description",
+    "link",
+    "photo_link",
+    "photo_id",
+    "photo_srcset",
+    "photo_blurhash",
+    "gallery",
+    "links",
+    "role",
+    "team_size",
+    "start_date",
+    "end_date",
+    "featured",
+    "client",
+    "order",
+    "stack",
+    "skill_ids",
+    "translations",
+    "seo",
+    "created_at",
+    "updated_at",
+];
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, SimpleObject, Clone)]
 pub struct Project {
     /// Document Id
     #[serde(rename = "_id")]
@@ -33,20 +111,63 @@ pub struct Project {
     pub description: Option<String>,
     pub link: Option<String>,
     pub photo_link: Option<String>,
+    pub photo_id: Option<String>,
+    /// `srcset` of the photo when it is an uploaded media
+    pub photo_srcset: Option<String>,
+    /// blurhash placeholder of the photo
+    pub photo_blurhash: Option<String>,
+    /// ordered images of the project
+    pub gallery: Vec<ProjectImage>,
+    pub links: Vec<ProjectLink>,
+    /// role of the customer in the project
+    pub role: Option<String>,
+    pub team_size: Option<i32>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub featured: bool,
+    /// client the project was made for
+    pub client: Option<String>,
     pub order: i32,
     pub stack: Option<Vec<String>>,
+    /// skills used in the project
+    pub skill_ids: Vec<String>,
+    /// `name` and `description` in other locales
+    pub translations: Translations,
+    /// meta tags of the project page
+    pub seo: Option<Seo>,
     /// createdAt
     pub created_at: String,
+    /// updatedAt
+    pub updated_at: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, InputObject, Clone)]
 pub struct ProjectInput {
     pub name: String,
     pub description: Option<String>,
     pub link: Option<String>,
     pub photo_link: Option<String>,
+    /// id of an uploaded media, takes precedence over `photo_link`
+    pub photo_id: Option<String>,
+    /// ordered images of the project
+    pub gallery: Option<Vec<ProjectImageInput>>,
+    pub links: Option<Vec<ProjectLink>>,
+    pub role: Option<String>,
+    pub team_size: Option<i32>,
+    /// RFC 3339 date, e.g. `2023-01-01T00:00:00Z`
+    pub start_date: Option<String>,
+    /// RFC 3339 date, e.g. `2023-06-01T00:00:00Z`
+    pub end_date: Option<String>,
+    pub featured: Option<bool>,
+    pub client: Option<String>,
     pub order: i32,
     pub stack: Option<Vec<String>>,
+    /// ids of skills of the customer used in the project
+    pub skill_ids: Option<Vec<String>>,
+    /// `name` and `description` by locale, e.g. `{"id": {"name": "Situs portofolio"}}`
+    pub translations: Option<Translations>,
+    /// meta tags of the project page
+    pub seo: Option<Seo>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
@@ -57,6 +178,108 @@ pub struct ProjectsInput {
     pub description: Option<String>,
     pub link: Option<String>,
     pub photo_link: Option<String>,
+    /// id of an uploaded media, takes precedence over `photo_link`
+    pub photo_id: Option<String>,
+    /// ordered images of the project
+    pub gallery: Option<Vec<ProjectImageInput>>,
+    pub links: Option<Vec<ProjectLink>>,
+    pub role: Option<String>,
+    pub team_size: Option<i32>,
+    /// RFC 3339 date, e.g. `2023-01-01T00:00:00Z`
+    pub start_date: Option<String>,
+    /// RFC 3339 date, e.g. `2023-06-01T00:00:00Z`
+    pub end_date: Option<String>,
+    pub featured: Option<bool>,
+    pub client: Option<String>,
     pub order: i32,
     pub stack: Option<Vec<String>>,
+    /// ids of skills of the customer used in the project
+    pub skill_ids: Option<Vec<String>>,
+    /// `name` and `description` by locale, e.g. `{"id": {"name": "Situs portofolio"}}`
+    pub translations: Option<Translations>,
+    /// meta tags of the project page
+    pub seo: Option<Seo>,
+}
+
+impl From<ProjectsInput> for ProjectInput {
+    fn from(item: ProjectsInput) -> Self {
+        Self {
+            name: item.name,
+            description: item.description,
+            link: item.link,
+            photo_link: item.photo_link,
+            photo_id: item.photo_id,
+            gallery: item.gallery,
+            links: item.links,
+            role: item.role,
+            team_size: item.team_size,
+            start_date: item.start_date,
+            end_date: item.end_date,
+            featured: item.featured,
+            client: item.client,
+            order: item.order,
+            stack: item.stack,
+            skill_ids: item.skill_ids,
+            translations: item.translations,
+            seo: item.seo,
+        }
+    }
+}
+
+/// Kind of a project link.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Enum, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectLinkKind {
+    Repository,
+    Demo,
+    CaseStudy,
+    AppStore,
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, SimpleObject, InputObject, Clone)]
+#[graphql(input_name = "ProjectLinkInput")]
+pub struct ProjectLink {
+    pub kind: ProjectLinkKind,
+    pub url: String,
+    /// text shown instead of the url
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectImageDocument {
+    pub media_id: ObjectId,
+    pub url: String,
+    #[serde(default)]
+    pub srcset: Option<String>,
+    #[serde(default)]
+    pub blurhash: Option<String>,
+    #[serde(default)]
+    pub caption: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, SimpleObject, Clone)]
+pub struct ProjectImage {
+    pub media_id: String,
+    pub url: String,
+    pub srcset: Option<String>,
+    pub blurhash: Option<String>,
+    pub caption: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, InputObject, Clone)]
+pub struct ProjectImageInput {
+    /// id of an uploaded media
+    pub media_id: String,
+    pub caption: Option<String>,
+}
+
+/// Filters of the project listings.
+#[derive(Debug, Default, Clone)]
+pub struct ProjectFilter {
+    pub featured: Option<bool>,
+    /// only projects using every one of these
+    pub stack: Vec<String>,
+    /// only projects using this skill
+    pub skill_id: Option<ObjectId>,
 }
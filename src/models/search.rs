@@ -0,0 +1,64 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Kind of content a search hit points to.
+#[derive(
+    Debug, Serialize, Deserialize, JsonSchema, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    Profile,
+    Project,
+    Experience,
+    Skill,
+    Social,
+    Article,
+}
+
+impl SearchKind {
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "profile" => Some(Self::Profile),
+            "project" => Some(Self::Project),
+            "experience" => Some(Self::Experience),
+            "skill" => Some(Self::Skill),
+            "social" => Some(Self::Social),
+            "article" => Some(Self::Article),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct SearchHit {
+    #[serde(rename = "type")]
+    pub kind: SearchKind,
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    /// name, title or position of the document
+    pub title: String,
+    /// field the snippet was taken from
+    pub field: Option<String>,
+    /// excerpt around the first match, HTML escaped with the matches in `<mark>`
+    pub snippet: Option<String>,
+    /// relevance, higher is better
+    pub score: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct SearchFacet {
+    #[serde(rename = "type")]
+    pub kind: SearchKind,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct SearchResult {
+    pub query: String,
+    /// hits matching `types`, over every page
+    pub total: usize,
+    /// hits per type, not restricted by `types`
+    pub facets: Vec<SearchFacet>,
+    pub hits: Vec<SearchHit>,
+}
@@ -0,0 +1,33 @@
+use async_graphql::{InputObject, SimpleObject};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Search engine and social sharing overrides of a page, every field falls back to the content.
+#[derive(
+    Debug, Default, Serialize, Deserialize, JsonSchema, SimpleObject, InputObject, Clone, PartialEq,
+)]
+#[graphql(input_name = "SeoInput")]
+pub struct Seo {
+    /// `<title>` of the page
+    pub meta_title: Option<String>,
+    pub meta_description: Option<String>,
+    /// absolute url when the page is published elsewhere first
+    pub canonical_url: Option<String>,
+    /// absolute url of the image shown when the page is shared
+    pub og_image: Option<String>,
+}
+
+/// Meta tags of a page of the portfolio site.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct PageMeta {
+    /// path below `site_url`, e.g. `/projects/my-app/`
+    pub path: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub canonical_url: String,
+    pub image: Option<String>,
+    /// `og:type`, `website`, `profile` or `article`
+    pub og_type: String,
+    /// `<title>`, `<meta>` and `<link>` tags to paste into `<head>`
+    pub html: String,
+}
@@ -0,0 +1,102 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ServiceDocument {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub customer_id: ObjectId,
+    pub name: String,
+    pub description: Option<String>,
+    /// what the client gets, e.g. `Design mockups`
+    pub deliverables: Vec<String>,
+    pub price_min: Option<f64>,
+    pub price_max: Option<f64>,
+    /// ISO 4217 code
+    pub currency: String,
+    /// e.g. `hour`, `day` or `project`
+    pub price_unit: Option<String>,
+    /// call-to-action button
+    pub cta_label: Option<String>,
+    pub cta_link: Option<String>,
+    /// projects made with this service
+    pub project_ids: Vec<ObjectId>,
+    pub order: i32,
+    /// createdAt
+    #[serde(
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime",
+        rename = "created_at"
+    )]
+    pub created_at: DateTime<Utc>,
+    /// updatedAt
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, SimpleObject, Clone)]
+pub struct Service {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub customer_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub deliverables: Vec<String>,
+    pub price_min: Option<f64>,
+    pub price_max: Option<f64>,
+    /// ISO 4217 code
+    pub currency: String,
+    pub price_unit: Option<String>,
+    /// formatted price range, e.g. `€1,500 – €3,000 / project`
+    pub price: Option<String>,
+    pub cta_label: Option<String>,
+    pub cta_link: Option<String>,
+    pub project_ids: Vec<String>,
+    pub order: i32,
+    /// createdAt
+    pub created_at: String,
+    /// updatedAt
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct ServiceInput {
+    pub name: String,
+    pub description: Option<String>,
+    pub deliverables: Option<Vec<String>>,
+    pub price_min: Option<f64>,
+    pub price_max: Option<f64>,
+    /// ISO 4217 code, `USD` when not given
+    pub currency: Option<String>,
+    /// e.g. `hour`, `day` or `project`
+    pub price_unit: Option<String>,
+    pub cta_label: Option<String>,
+    pub cta_link: Option<String>,
+    /// ids of related projects of the customer
+    pub project_ids: Option<Vec<String>>,
+    pub order: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct ServicesInput {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub deliverables: Option<Vec<String>>,
+    pub price_min: Option<f64>,
+    pub price_max: Option<f64>,
+    pub currency: Option<String>,
+    pub price_unit: Option<String>,
+    pub cta_label: Option<String>,
+    pub cta_link: Option<String>,
+    pub project_ids: Option<Vec<String>>,
+    pub order: i32,
+}
@@ -0,0 +1,54 @@
+use serde::Serialize;
+
+use crate::models::{experience::Experience, project::Project, skill::Skill, social::Social};
+
+/// What a theme template is rendered with.
+/// <br />Pages also get `page`, their path, and `project.html` the `project` it renders.
+#[derive(Debug, Serialize, Clone)]
+pub struct SiteContent {
+    pub profile: SiteProfile,
+    pub projects: Vec<SiteProject>,
+    pub experiences: Vec<SiteExperience>,
+    pub skills: Vec<Skill>,
+    pub socials: Vec<Social>,
+    /// absolute url of the site without a trailing slash, e.g. `https://jane.dev`
+    pub base_url: String,
+    /// path of `base_url`, prefixes every link, e.g. `/portfolio` or an empty string
+    pub base_path: String,
+    pub locale: String,
+    /// RFC 3339
+    pub generated_at: String,
+}
+
+/// Public fields of the customer.
+#[derive(Debug, Serialize, Clone)]
+pub struct SiteProfile {
+    pub name: String,
+    pub email: String,
+    pub phone: Option<String>,
+    pub wa_link: Option<String>,
+    pub intro: Option<String>,
+    pub about: Option<String>,
+    pub profile_picture: Option<String>,
+    pub profile_picture_srcset: Option<String>,
+    pub profile_picture_blurhash: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SiteProject {
+    #[serde(flatten)]
+    pub project: Project,
+    pub slug: String,
+    /// path of the project page
+    pub url: String,
+    /// e.g. `Mar 2021 – Present`
+    pub period: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SiteExperience {
+    #[serde(flatten)]
+    pub experience: Experience,
+    /// e.g. `Mar 2021 – Present`
+    pub period: Option<String>,
+}
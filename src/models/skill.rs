@@ -1,7 +1,9 @@
+use async_graphql::{Enum, InputObject, SimpleObject};
 use chrono::{DateTime, Utc};
 use mongodb::bson::oid::ObjectId;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::env;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SkillDocument {
@@ -10,6 +12,14 @@ pub struct SkillDocument {
     pub id: ObjectId,
     pub customer_id: ObjectId,
     pub name: String,
+    /// e.g. `Languages` or `Frameworks`
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub proficiency: Option<SkillProficiency>,
+    /// years of use
+    #[serde(default)]
+    pub years: Option<i32>,
     pub order: i32,
     /// createdAt
     #[serde(
@@ -17,23 +27,40 @@ pub struct SkillDocument {
         rename = "created_at"
     )]
     pub created_at: DateTime<Utc>,
+    /// updatedAt
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, SimpleObject, Clone)]
 pub struct Skill {
     /// Document Id
     #[serde(rename = "_id")]
     pub id: String,
     pub customer_id: String,
     pub name: String,
+    /// e.g. `Languages` or `Frameworks`
+    pub category: Option<String>,
+    pub proficiency: Option<SkillProficiency>,
+    /// years of use
+    pub years: Option<i32>,
     pub order: i32,
     /// createdAt
     pub created_at: String,
+    /// updatedAt
+    pub updated_at: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, InputObject, Clone)]
 pub struct SkillInput {
     pub name: String,
+    pub category: Option<String>,
+    pub proficiency: Option<SkillProficiency>,
+    /// years of use
+    pub years: Option<i32>,
     pub order: i32,
 }
 
@@ -42,5 +69,53 @@ pub struct SkillsInput {
     #[serde(rename = "_id")]
     pub id: String,
     pub name: String,
+    pub category: Option<String>,
+    pub proficiency: Option<SkillProficiency>,
+    pub years: Option<i32>,
     pub order: i32,
 }
+
+impl From<SkillsInput> for SkillInput {
+    fn from(item: SkillsInput) -> Self {
+        Self {
+            name: item.name,
+            category: item.category,
+            proficiency: item.proficiency,
+            years: item.years,
+            order: item.order,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Enum, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillProficiency {
+    Beginner,
+    Intermediate,
+    Advanced,
+    Expert,
+}
+
+/// What happens to the projects and experiences using a skill when it is deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkillDeletePolicy {
+    /// refuse to delete a skill that is still used
+    Restrict,
+    /// remove the skill from the projects and experiences using it
+    Cascade,
+}
+
+impl SkillDeletePolicy {
+    /// Read from `SKILL_DELETE_POLICY`, `restrict` (default) or `cascade`.
+    /// <br />An unknown value falls back to `restrict`, which never loses a link.
+    pub fn from_env() -> Self {
+        match env::var("SKILL_DELETE_POLICY").as_deref() {
+            Ok("cascade") => Self::Cascade,
+            Ok("restrict") | Err(_) => Self::Restrict,
+            Ok(other) => {
+                eprintln!("SKILL_DELETE_POLICY {other} is not supported, using restrict.");
+                Self::Restrict
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use async_graphql::{InputObject, SimpleObject};
 use chrono::{DateTime, Utc};
 use mongodb::bson::oid::ObjectId;
 use schemars::JsonSchema;
@@ -18,9 +19,15 @@ pub struct SocialDocument {
         rename = "created_at"
     )]
     pub created_at: DateTime<Utc>,
+    /// updatedAt
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, SimpleObject, Clone)]
 pub struct Social {
     /// Document Id
     #[serde(rename = "_id")]
@@ -31,9 +38,11 @@ pub struct Social {
     pub order: i32,
     /// createdAt
     pub created_at: String,
+    /// updatedAt
+    pub updated_at: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, InputObject, Clone)]
 pub struct SocialInput {
     pub name: String,
     pub link: String,
@@ -0,0 +1,28 @@
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{Schema, SchemaObject},
+    JsonSchema,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A DTO, or only the fields of it requested with `?fields=`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Sparse<T> {
+    Full(T),
+    Partial(Map<String, Value>),
+}
+
+impl<T: JsonSchema> JsonSchema for Sparse<T> {
+    fn schema_name() -> String {
+        format!("Sparse_{}", T::schema_name())
+    }
+
+    // Same shape as `T`, except that no field is required anymore.
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema: SchemaObject = T::json_schema(gen).into_object();
+        schema.object().required.clear();
+        schema.into()
+    }
+}
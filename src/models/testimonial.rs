@@ -0,0 +1,118 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Moderation state of a testimonial, only approved ones are public.
+#[derive(
+    Debug, Serialize, Deserialize, JsonSchema, Enum, FromFormField, Clone, Copy, PartialEq, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TestimonialStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TestimonialDocument {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub customer_id: ObjectId,
+    pub author: String,
+    pub role: Option<String>,
+    pub company: Option<String>,
+    pub avatar_link: Option<String>,
+    pub quote: String,
+    /// 1 to 5
+    pub rating: Option<i32>,
+    pub project_id: Option<ObjectId>,
+    pub status: TestimonialStatus,
+    pub order: i32,
+    /// createdAt
+    #[serde(
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime",
+        rename = "created_at"
+    )]
+    pub created_at: DateTime<Utc>,
+    /// updatedAt
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, SimpleObject, Clone)]
+pub struct Testimonial {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub customer_id: String,
+    pub author: String,
+    pub role: Option<String>,
+    pub company: Option<String>,
+    pub avatar_link: Option<String>,
+    pub quote: String,
+    /// 1 to 5
+    pub rating: Option<i32>,
+    /// project the testimonial is about
+    pub project_id: Option<String>,
+    pub status: TestimonialStatus,
+    pub order: i32,
+    /// createdAt
+    pub created_at: String,
+    /// updatedAt
+    pub updated_at: Option<String>,
+}
+
+/// Testimonial sent by a visitor of the portfolio, it stays pending until approved.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct TestimonialSubmission {
+    pub author: String,
+    pub role: Option<String>,
+    pub company: Option<String>,
+    pub avatar_link: Option<String>,
+    pub quote: String,
+    /// 1 to 5
+    pub rating: Option<i32>,
+    /// id of a project of the portfolio
+    pub project_id: Option<String>,
+    /// honeypot, keep this field hidden from humans and empty
+    #[serde(default)]
+    pub website: Option<String>,
+    /// token returned by `GET /contact/token` when the form is shown
+    pub token: String,
+    /// proof-of-work nonce, required when the token has a difficulty
+    pub nonce: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct TestimonialInput {
+    pub author: String,
+    pub role: Option<String>,
+    pub company: Option<String>,
+    pub avatar_link: Option<String>,
+    pub quote: String,
+    /// 1 to 5
+    pub rating: Option<i32>,
+    /// id of a project of the portfolio
+    pub project_id: Option<String>,
+    pub order: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct TestimonialsInput {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub author: String,
+    pub role: Option<String>,
+    pub company: Option<String>,
+    pub avatar_link: Option<String>,
+    pub quote: String,
+    pub rating: Option<i32>,
+    pub project_id: Option<String>,
+    pub order: i32,
+}
@@ -0,0 +1,15 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Fields of a document with no translation in a locale.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct MissingTranslation {
+    /// `profile`, `projects` or `experiences`
+    pub section: String,
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub locale: String,
+    /// fields with a value in the default locale but none in `locale`
+    pub fields: Vec<String>,
+}
@@ -0,0 +1,157 @@
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use rocket::FromFormField;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WebhookDocument {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub customer_id: ObjectId,
+    pub url: String,
+    /// key of the HMAC-SHA256 signatures
+    pub secret: String,
+    /// event patterns, every event when empty
+    pub events: Vec<String>,
+    pub active: bool,
+    pub description: Option<String>,
+    /// createdAt
+    #[serde(
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime",
+        rename = "created_at"
+    )]
+    pub created_at: DateTime<Utc>,
+    /// updatedAt
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct Webhook {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub customer_id: String,
+    pub url: String,
+    /// key of the `X-Webhook-Signature` header
+    pub secret: String,
+    /// event patterns, every event when empty
+    pub events: Vec<String>,
+    pub active: bool,
+    pub description: Option<String>,
+    /// createdAt
+    pub created_at: String,
+    /// updatedAt
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct WebhookInput {
+    /// http(s) url the events are posted to
+    pub url: String,
+    /// `<section>.<action>` patterns, e.g. `["project.*", "*.deleted", "article.published"]`.
+    /// Every event when empty or missing.
+    pub events: Option<Vec<String>>,
+    /// `true` when missing
+    pub active: Option<bool>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, FromFormField, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// waiting for its first or next attempt
+    Pending,
+    Succeeded,
+    /// every attempt failed
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WebhookDeliveryDocument {
+    /// Document Id
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub webhook_id: ObjectId,
+    pub customer_id: ObjectId,
+    pub event: String,
+    /// JSON body, kept as sent so redeliveries carry the same content
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub next_attempt_at: DateTime<Utc>,
+    #[serde(
+        default,
+        with = "crate::utils::util::option_chrono_datetime_as_bson_datetime"
+    )]
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    /// HTTP status of the last attempt
+    pub response_status: Option<i32>,
+    /// start of the body of the last response
+    pub response_body: Option<String>,
+    /// why the last attempt failed
+    pub error: Option<String>,
+    /// milliseconds the last attempt took
+    pub duration: Option<i64>,
+    /// delivery this one was sent again from
+    pub redelivery_of: Option<ObjectId>,
+    /// createdAt
+    #[serde(
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime",
+        rename = "created_at"
+    )]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct WebhookDelivery {
+    /// Document Id, also the `X-Webhook-Id` header
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub webhook_id: String,
+    pub customer_id: String,
+    pub event: String,
+    /// JSON body
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    /// when a pending delivery is tried next
+    pub next_attempt_at: String,
+    pub last_attempt_at: Option<String>,
+    /// HTTP status of the last attempt
+    pub response_status: Option<i32>,
+    /// start of the body of the last response
+    pub response_body: Option<String>,
+    /// why the last attempt failed
+    pub error: Option<String>,
+    /// milliseconds the last attempt took
+    pub duration: Option<i64>,
+    /// delivery this one was sent again from
+    pub redelivery_of: Option<String>,
+    /// createdAt
+    pub created_at: String,
+}
+
+/// Outcome of one attempt of a delivery.
+#[derive(Debug, Clone)]
+pub struct DeliveryAttempt {
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub duration: i64,
+}
+
+impl DeliveryAttempt {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+            && self
+                .response_status
+                .is_some_and(|status| (200..300).contains(&status))
+    }
+}
@@ -0,0 +1,426 @@
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use mongodb::bson::oid::ObjectId;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::redirect::Policy;
+use reqwest::Url;
+use rocket::tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::env;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::models::{
+    event::ContentEvent,
+    webhook::{DeliveryAttempt, DeliveryStatus, Webhook, WebhookDelivery},
+};
+use crate::routes::traits::{ContentEventListener, WebhookRepository};
+
+type HmacSha256 = Hmac<Sha256>;
+
+// A claimed delivery is handed out again after this, in case its sender died.
+const LEASE: Duration = Duration::from_secs(60);
+// The queue is looked at this often when nothing wakes the sender.
+const POLL: Duration = Duration::from_secs(15);
+const TIMEOUT: Duration = Duration::from_secs(10);
+// Deliveries sent at once, and at once to a single host so a slow one cannot hold them all.
+const CONCURRENCY: usize = 16;
+const HOST_CONCURRENCY: usize = 2;
+const MAX_BACKOFF: Duration = Duration::from_secs(6 * 60 * 60);
+// Characters of a response kept in the log.
+const RESPONSE_LENGTH: usize = 1000;
+
+/// Queues the content events for the webhooks subscribed to them and sends the queue, retrying
+/// failed deliveries with an exponential backoff.
+pub struct Deliveries {
+    repo: Arc<dyn WebhookRepository + Send + Sync>,
+    client: reqwest::Client,
+    wake: Notify,
+    max_attempts: i32,
+    retry_base: Duration,
+    slots: Arc<Semaphore>,
+    hosts: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+impl Deliveries {
+    /// `WEBHOOK_MAX_ATTEMPTS` (8 by default) attempts, `WEBHOOK_RETRY_BASE` seconds (30 by
+    /// default) before the first retry, doubling after that.
+    pub fn new(repo: Arc<dyn WebhookRepository + Send + Sync>) -> Self {
+        let max_attempts = env::var("WEBHOOK_MAX_ATTEMPTS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(8);
+        let retry_base = env::var("WEBHOOK_RETRY_BASE")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(30);
+
+        Self {
+            repo,
+            // Webhook urls are picked by customers, they must not reach the server's network:
+            // hosts only resolve to public addresses, and redirects are not followed since
+            // they could point anywhere.
+            client: reqwest::Client::builder()
+                .timeout(TIMEOUT)
+                .redirect(Policy::none())
+                .no_proxy()
+                .dns_resolver(Arc::new(PublicResolver))
+                .build()
+                .expect("the webhook client builds"),
+            wake: Notify::new(),
+            max_attempts: max_attempts.max(1),
+            retry_base: Duration::from_secs(retry_base),
+            slots: Arc::new(Semaphore::new(CONCURRENCY)),
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Have the sender look at the queue now.
+    pub fn wake(&self) {
+        self.wake.notify_one();
+    }
+
+    /// Send the due deliveries until the server stops, `CONCURRENCY` at a time.
+    pub async fn run(self: Arc<Self>) {
+        loop {
+            let slot = self
+                .slots
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("the semaphore is never closed");
+            match self.repo.claim_due_delivery(Utc::now() + LEASE).await {
+                Ok(Some(delivery)) => {
+                    let deliveries = self.clone();
+                    rocket::tokio::spawn(async move {
+                        deliveries.attempt(delivery, true).await;
+                        drop(slot);
+                    });
+                    continue;
+                }
+                Ok(None) => {}
+                Err(error) => eprintln!("Webhook: failed to read the queue: {error}"),
+            }
+            drop(slot);
+
+            rocket::tokio::select! {
+                _ = self.wake.notified() => {}
+                _ = rocket::tokio::time::sleep(POLL) => {}
+            }
+        }
+    }
+
+    /// Queue a `ping` for the webhook and send it at once, it is not retried.
+    pub async fn ping(&self, webhook: &Webhook) -> mongodb::error::Result<Option<WebhookDelivery>> {
+        let payload = serde_json::json!({
+            "event": "ping",
+            "webhook_id": webhook.id,
+            "customer_id": webhook.customer_id,
+            "occurred_at": Utc::now().to_rfc3339(),
+        });
+        // leased from the start, the sender leaves it alone
+        let delivery = self
+            .repo
+            .enqueue(
+                webhook,
+                "ping",
+                payload.to_string(),
+                None,
+                Utc::now() + LEASE,
+            )
+            .await?;
+
+        Ok(self.attempt(delivery, false).await)
+    }
+
+    async fn attempt(&self, delivery: WebhookDelivery, retry: bool) -> Option<WebhookDelivery> {
+        let Ok(oid) = ObjectId::parse_str(&delivery.id) else {
+            return None;
+        };
+        let webhook = match ObjectId::parse_str(&delivery.webhook_id) {
+            Ok(webhook_oid) => self.repo.find_by_id(webhook_oid).await.ok().flatten(),
+            Err(_) => None,
+        };
+
+        let attempt = match &webhook {
+            Some(webhook) if webhook.active || delivery.event == "ping" => {
+                // A queued delivery to a busy host is left alone, its lease hands it out again.
+                let _host_slot = self.host_slot(&webhook.url, !retry).await?;
+                send(&self.client, webhook, &delivery).await
+            }
+            _ => DeliveryAttempt {
+                response_status: None,
+                response_body: None,
+                error: Some("The webhook is inactive or removed".to_string()),
+                duration: 0,
+            },
+        };
+
+        let attempts = delivery.attempts + 1;
+        let now = Utc::now();
+        let (status, next_attempt_at) = if attempt.succeeded() {
+            (DeliveryStatus::Succeeded, now)
+        } else if !retry || webhook.is_none() || attempts >= self.max_attempts {
+            (DeliveryStatus::Failed, now)
+        } else {
+            let delay = backoff(self.retry_base, attempts);
+            (
+                DeliveryStatus::Pending,
+                now + chrono::Duration::from_std(delay).unwrap_or_default(),
+            )
+        };
+
+        match self
+            .repo
+            .record_attempt(oid, &attempt, status, next_attempt_at)
+            .await
+        {
+            Ok(resp) => resp,
+            Err(error) => {
+                eprintln!(
+                    "Webhook: failed to record delivery {}: {error}",
+                    delivery.id
+                );
+                None
+            }
+        }
+    }
+
+    // Permit to send to the host of `url`, None when it is busy and `wait` is false.
+    async fn host_slot(&self, url: &str, wait: bool) -> Option<OwnedSemaphorePermit> {
+        let host = Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_lowercase))
+            .unwrap_or_default();
+        let slots = {
+            let mut hosts = self.hosts.lock().unwrap();
+            // Forget the hosts nobody is sending to.
+            hosts.retain(|_, slots| Arc::strong_count(slots) > 1);
+            hosts
+                .entry(host)
+                .or_insert_with(|| Arc::new(Semaphore::new(HOST_CONCURRENCY)))
+                .clone()
+        };
+
+        if wait {
+            slots.acquire_owned().await.ok()
+        } else {
+            slots.try_acquire_owned().ok()
+        }
+    }
+}
+
+#[async_trait]
+impl ContentEventListener for Deliveries {
+    async fn on_event(&self, event: &ContentEvent) {
+        let Ok(oid) = ObjectId::parse_str(&event.customer_id) else {
+            return;
+        };
+        let webhooks = match self.repo.find_subscribed(oid, &event.event).await {
+            Ok(webhooks) => webhooks,
+            Err(error) => {
+                eprintln!(
+                    "Webhook: failed to find the webhooks of {}: {error}",
+                    event.event
+                );
+                return;
+            }
+        };
+        if webhooks.is_empty() {
+            return;
+        }
+
+        let Ok(payload) = serde_json::to_string(event) else {
+            return;
+        };
+        for webhook in &webhooks {
+            if let Err(error) = self
+                .repo
+                .enqueue(webhook, &event.event, payload.clone(), None, Utc::now())
+                .await
+            {
+                eprintln!(
+                    "Webhook: failed to queue {} for webhook {}: {error}",
+                    event.event, webhook.id
+                );
+            }
+        }
+        self.wake();
+    }
+}
+
+/// `sha256=` and the hex HMAC-SHA256 of `<timestamp>.<body>` keyed with the secret of the webhook.
+pub fn signature(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{timestamp}.{body}").as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Wait before the attempt following attempt `attempts`: `base`, then doubling, at most 6 hours.
+pub fn backoff(base: Duration, attempts: i32) -> Duration {
+    let exponent = u32::try_from(attempts.saturating_sub(1))
+        .unwrap_or(0)
+        .min(20);
+    base.saturating_mul(2u32.pow(exponent)).min(MAX_BACKOFF)
+}
+
+/// Whether `ip` is reachable on the internet, false for loopback, private, link-local (e.g. the
+/// 169.254.169.254 metadata service) and other reserved ranges.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ipv4(ip),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        // shared address space of carrier-grade NAT
+        || (a == 100 && (64..128).contains(&b))
+        // IETF protocol assignments
+        || (a == 192 && b == 0 && c == 0)
+        // benchmarking
+        || (a == 198 && (b == 18 || b == 19))
+        // reserved
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let segments = ip.segments();
+    let [first, second, ..] = segments;
+    // IPv4 in the last 32 bits, or the 32 bits after the 6to4 prefix
+    let embedded = |high: u16, low: u16| {
+        let [a, b] = high.to_be_bytes();
+        let [c, d] = low.to_be_bytes();
+        Ipv4Addr::new(a, b, c, d)
+    };
+
+    if ip.is_unspecified() || ip.is_loopback() || ip.is_multicast() {
+        return false;
+    }
+    // IPv4-compatible `::a.b.c.d` and NAT64 `64:ff9b::/96` reach the embedded IPv4 address
+    if segments[..6] == [0; 6] || segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        return is_public_ipv4(embedded(segments[6], segments[7]));
+    }
+    // 6to4 `2002::/16`
+    if first == 0x2002 {
+        return is_public_ipv4(embedded(segments[1], segments[2]));
+    }
+    !(
+        // local-use NAT64 `64:ff9b:1::/48`
+        (first == 0x64 && second == 0xff9b)
+        // discard-only
+        || (first == 0x0100 && segments[1..4] == [0; 3])
+        // Teredo, the IPv4 address of the tunnel is obfuscated
+        || (first == 0x2001 && second == 0)
+        // unique local
+        || (first & 0xfe00) == 0xfc00
+        // link-local
+        || (first & 0xffc0) == 0xfe80
+        // deprecated site-local
+        || (first & 0xffc0) == 0xfec0
+        // documentation
+        || (first == 0x2001 && second == 0x0db8)
+    )
+}
+
+/// Whether the host of `url` is an address that is not public, names are checked once resolved.
+pub fn is_private_url(url: &str) -> bool {
+    let Some(host) = Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+    else {
+        return false;
+    };
+    match host.trim_start_matches('[').trim_end_matches(']').parse() {
+        Ok(ip) => !is_public_ip(ip),
+        Err(_) => host.eq_ignore_ascii_case("localhost"),
+    }
+}
+
+// Resolves webhook hosts to their public addresses only.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = rocket::tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(
+                    format!("{} does not resolve to a public address", name.as_str()).into(),
+                );
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+async fn send(
+    client: &reqwest::Client,
+    webhook: &Webhook,
+    delivery: &WebhookDelivery,
+) -> DeliveryAttempt {
+    let timestamp = Utc::now().timestamp();
+    let started = Instant::now();
+
+    // Addresses in the url are not resolved, so they are checked here.
+    if is_private_url(&webhook.url) {
+        return DeliveryAttempt {
+            response_status: None,
+            response_body: None,
+            error: Some("The url does not point to a public address".to_string()),
+            duration: 0,
+        };
+    }
+
+    let result = client
+        .post(&webhook.url)
+        .header("Content-Type", "application/json")
+        .header("User-Agent", "portfolio-cms-webhook")
+        .header("X-Webhook-Id", &delivery.id)
+        .header("X-Webhook-Event", &delivery.event)
+        .header("X-Webhook-Timestamp", timestamp.to_string())
+        .header(
+            "X-Webhook-Signature",
+            signature(&webhook.secret, timestamp, &delivery.payload),
+        )
+        .body(delivery.payload.clone())
+        .send()
+        .await;
+
+    let (response_status, response_body, error) = match result {
+        Ok(response) => {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let body: String = body.chars().take(RESPONSE_LENGTH).collect();
+            let error = (!status.is_success()).then(|| format!("Responded with {status}"));
+            (Some(i32::from(status.as_u16())), Some(body), error)
+        }
+        Err(error) => (None, None, Some(error.to_string())),
+    };
+
+    DeliveryAttempt {
+        response_status,
+        response_body,
+        error,
+        duration: i64::try_from(started.elapsed().as_millis()).unwrap_or(i64::MAX),
+    }
+}
@@ -0,0 +1,15 @@
+use crate::models::contact::ContactMessage;
+use crate::routes::traits::ContactNotifier;
+
+/// Notifier writing a line to stderr for every new message.
+pub struct LogNotifier;
+
+#[async_trait]
+impl ContactNotifier for LogNotifier {
+    async fn notify(&self, message: &ContactMessage) {
+        eprintln!(
+            "Contact: new message {} for customer {} from {}",
+            message.id, message.customer_id, message.email
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use std::env;
+use std::sync::Arc;
+
+use crate::routes::traits::ContactNotifier;
+
+pub mod delivery;
+pub mod log;
+pub mod webhook;
+
+/// Post new contact messages to `CONTACT_WEBHOOK_URL` when it is set, log them otherwise.
+pub fn from_env() -> Arc<dyn ContactNotifier + Send + Sync> {
+    match env::var("CONTACT_WEBHOOK_URL") {
+        Ok(url) if !url.is_empty() => Arc::new(webhook::WebhookNotifier::new(url)),
+        _ => Arc::new(log::LogNotifier),
+    }
+}
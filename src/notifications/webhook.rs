@@ -0,0 +1,48 @@
+use serde_json::json;
+use std::time::Duration;
+
+use crate::models::contact::ContactMessage;
+use crate::routes::traits::ContactNotifier;
+
+// A hung relay must not keep notification tasks around.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Notifier posting every new message as JSON to a webhook, e.g. a chat or mail relay.
+pub struct WebhookNotifier {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            client: reqwest::Client::builder()
+                .timeout(TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ContactNotifier for WebhookNotifier {
+    async fn notify(&self, message: &ContactMessage) {
+        let body = json!({ "event": "contact.created", "message": message });
+
+        // A failing webhook must not lose the message, it is already in the inbox.
+        let result = self
+            .client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        if let Err(error) = result {
+            eprintln!(
+                "Contact: webhook failed for message {}: {error}",
+                message.id
+            );
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::convert::Infallible;
+
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket_okapi::okapi::openapi3::{Object, Parameter, ParameterValue};
+use rocket_okapi::{
+    gen::OpenApiGenerator,
+    request::{OpenApiFromRequest, RequestHeaderInput},
+};
+
+use crate::utils::i18n::parse_accept_language;
+
+/// Locales of the `Accept-Language` header, most preferred first, empty without the header.
+#[derive(Debug, Default)]
+pub struct AcceptLanguage(pub Vec<String>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AcceptLanguage {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let locales = req
+            .headers()
+            .get_one("Accept-Language")
+            .map(parse_accept_language)
+            .unwrap_or_default();
+
+        Outcome::Success(AcceptLanguage(locales))
+    }
+}
+
+impl<'a> OpenApiFromRequest<'a> for AcceptLanguage {
+    fn from_request_input(
+        gen: &mut OpenApiGenerator,
+        _name: String,
+        _required: bool,
+    ) -> rocket_okapi::Result<RequestHeaderInput> {
+        Ok(RequestHeaderInput::Parameter(Parameter {
+            name: "Accept-Language".to_owned(),
+            location: "header".to_owned(),
+            description: Some(
+                "Preferred locales of the content, `?locale=` takes precedence".to_owned(),
+            ),
+            required: false,
+            deprecated: false,
+            allow_empty_value: false,
+            value: ParameterValue::Schema {
+                style: None,
+                explode: None,
+                allow_reserved: false,
+                schema: gen.json_schema::<String>(),
+                example: None,
+                examples: None,
+            },
+            extensions: Object::default(),
+        }))
+    }
+}
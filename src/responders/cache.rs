@@ -0,0 +1,271 @@
+use chrono::{DateTime, Utc};
+use rocket::http::{ContentType, Header, Status};
+use rocket::response::{self, Responder};
+use rocket::serde::json::Json;
+use rocket::{Request, Response};
+use rocket_okapi::okapi::openapi3::{RefOr, Response as OpenApiResponse, Responses};
+use rocket_okapi::{gen::OpenApiGenerator, response::OpenApiResponderInner, OpenApiError};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::env;
+use std::io::Cursor;
+
+use crate::models::{
+    article::Article,
+    certification::Certification,
+    collection::{CustomCollection, CustomEntry},
+    customer::Customer,
+    education::Education,
+    experience::Experience,
+    portfolio::Portfolio,
+    project::Project,
+    service::Service,
+    skill::Skill,
+    social::Social,
+    sparse::Sparse,
+    testimonial::Testimonial,
+};
+use crate::utils::util::parse_datetime;
+
+const HTTP_DATE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// `Cache-Control` policy applied to every cached public response.
+pub struct CacheControl {
+    max_age: u64,
+    stale_while_revalidate: u64,
+}
+
+impl CacheControl {
+    pub fn new() -> Self {
+        let max_age = env::var("CACHE_MAX_AGE")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(60);
+        let stale_while_revalidate = env::var("CACHE_STALE_WHILE_REVALIDATE")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(300);
+
+        Self {
+            max_age,
+            stale_while_revalidate,
+        }
+    }
+
+    fn header_value(&self) -> String {
+        format!(
+            "public, max-age={}, stale-while-revalidate={}",
+            self.max_age, self.stale_while_revalidate
+        )
+    }
+}
+
+impl Default for CacheControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Content that knows when it was last changed.
+pub trait LastModified {
+    fn last_modified(&self) -> Option<DateTime<Utc>>;
+}
+
+/// JSON response with `ETag`, `Last-Modified` and `Cache-Control` headers.
+/// <br />Answers `304 Not Modified` when the client already holds the same content, going by
+/// the `ETag` only.
+pub struct Cached<T>(pub Json<T>);
+
+impl<'r, T: Serialize + LastModified> Responder<'r, 'static> for Cached<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let body = serde_json::to_string(&self.0 .0).map_err(|_| Status::InternalServerError)?;
+        let etag = format!("\"{}\"", hex::encode(Sha256::digest(body.as_bytes())));
+        let last_modified = self.0.last_modified();
+
+        let cache_control = match req.rocket().state::<CacheControl>() {
+            Some(cache_control) => cache_control.header_value(),
+            None => CacheControl::new().header_value(),
+        };
+
+        let mut builder = Response::build();
+        builder
+            .header(Header::new("ETag", etag.clone()))
+            .header(Header::new("Cache-Control", cache_control))
+            .header(Header::new("Vary", "X-API-KEY, Accept-Language"));
+        if let Some(last_modified) = last_modified {
+            builder.header(Header::new(
+                "Last-Modified",
+                last_modified.format(HTTP_DATE_FORMAT).to_string(),
+            ));
+        }
+
+        if is_not_modified(req, &etag) {
+            return builder.status(Status::NotModified).ok();
+        }
+
+        builder
+            .sized_body(body.len(), Cursor::new(body))
+            .header(ContentType::JSON)
+            .ok()
+    }
+}
+
+impl<T: Serialize + LastModified> OpenApiResponderInner for Cached<T>
+where
+    Json<T>: OpenApiResponderInner,
+{
+    fn responses(gen: &mut OpenApiGenerator) -> Result<Responses, OpenApiError> {
+        let mut responses = Json::<T>::responses(gen)?;
+        responses.responses.insert(
+            "304".to_owned(),
+            RefOr::Object(OpenApiResponse {
+                description: "\
+                # 304 Not Modified\n\
+                The content matches the `If-None-Match` header. \
+                "
+                .to_owned(),
+                ..Default::default()
+            }),
+        );
+        Ok(responses)
+    }
+}
+
+// Only the ETag is compared: `Last-Modified` comes from the newest item returned, a deleted item
+// does not move it, so `If-Modified-Since` could answer 304 for a list that lost an item.
+fn is_not_modified(req: &Request<'_>, etag: &str) -> bool {
+    req.headers()
+        .get_one("If-None-Match")
+        .is_some_and(|if_none_match| {
+            if_none_match
+                .split(',')
+                .map(|tag| tag.trim())
+                .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag)
+        })
+}
+
+fn newest(created_at: &str, updated_at: &Option<String>) -> Option<DateTime<Utc>> {
+    let created_at = parse_datetime(created_at);
+    let updated_at = updated_at.as_deref().and_then(parse_datetime);
+    created_at.max(updated_at)
+}
+
+impl<T: LastModified> LastModified for Vec<T> {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        self.iter().filter_map(|item| item.last_modified()).max()
+    }
+}
+
+impl<T: LastModified> LastModified for Option<T> {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        self.as_ref().and_then(|item| item.last_modified())
+    }
+}
+
+impl LastModified for Article {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        newest(&self.created_at, &self.updated_at)
+    }
+}
+
+impl LastModified for Certification {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        newest(&self.created_at, &self.updated_at)
+    }
+}
+
+impl LastModified for CustomCollection {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        newest(&self.created_at, &self.updated_at)
+    }
+}
+
+impl LastModified for CustomEntry {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        newest(&self.created_at, &self.updated_at)
+    }
+}
+
+impl LastModified for Customer {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        newest(&self.created_at, &self.updated_at)
+    }
+}
+
+impl LastModified for Education {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        newest(&self.created_at, &self.updated_at)
+    }
+}
+
+impl LastModified for Experience {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        newest(&self.created_at, &self.updated_at)
+    }
+}
+
+impl LastModified for Project {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        newest(&self.created_at, &self.updated_at)
+    }
+}
+
+impl LastModified for Service {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        newest(&self.created_at, &self.updated_at)
+    }
+}
+
+impl LastModified for Skill {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        newest(&self.created_at, &self.updated_at)
+    }
+}
+
+impl LastModified for Social {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        newest(&self.created_at, &self.updated_at)
+    }
+}
+
+impl LastModified for Testimonial {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        newest(&self.created_at, &self.updated_at)
+    }
+}
+
+impl LastModified for Portfolio {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        [
+            self.profile.last_modified(),
+            self.projects.last_modified(),
+            self.skills.last_modified(),
+            self.socials.last_modified(),
+            self.experiences.last_modified(),
+            self.articles.last_modified(),
+            self.educations.last_modified(),
+            self.certifications.last_modified(),
+            self.testimonials.last_modified(),
+            self.services.last_modified(),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+    }
+}
+
+impl<T: LastModified> LastModified for Sparse<T> {
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Sparse::Full(item) => item.last_modified(),
+            Sparse::Partial(fields) => {
+                let created_at = fields.get("created_at").and_then(Value::as_str);
+                let updated_at = fields.get("updated_at").and_then(Value::as_str);
+                created_at
+                    .and_then(parse_datetime)
+                    .max(updated_at.and_then(parse_datetime))
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use rocket::http::{ContentType, Header};
+use rocket::response::{self, Responder};
+use rocket::{Request, Response};
+use rocket_okapi::okapi::openapi3::{MediaType, RefOr, Response as OpenApiResponse, Responses};
+use rocket_okapi::{gen::OpenApiGenerator, response::OpenApiResponderInner, OpenApiError};
+use std::io::Cursor;
+
+/// File sent with `Content-Disposition: attachment`, browsers save it as `file_name`.
+pub struct Download {
+    pub file_name: String,
+    pub content_type: ContentType,
+    pub bytes: Vec<u8>,
+}
+
+impl<'r> Responder<'r, 'static> for Download {
+    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'static> {
+        // keep the header value a plain quoted string
+        let file_name: String = self
+            .file_name
+            .chars()
+            .filter(|c| c.is_ascii_graphic() && *c != '"' && *c != '\\' || *c == ' ')
+            .collect();
+
+        Response::build()
+            .header(self.content_type)
+            .header(Header::new(
+                "Content-Disposition",
+                format!("attachment; filename=\"{file_name}\""),
+            ))
+            .sized_body(self.bytes.len(), Cursor::new(self.bytes))
+            .ok()
+    }
+}
+
+impl OpenApiResponderInner for Download {
+    fn responses(_gen: &mut OpenApiGenerator) -> Result<Responses, OpenApiError> {
+        let mut responses = Responses::default();
+        let mut response = OpenApiResponse {
+            description: "File download".to_owned(),
+            ..Default::default()
+        };
+        response
+            .content
+            .insert("application/octet-stream".to_owned(), MediaType::default());
+        responses
+            .responses
+            .insert("200".to_owned(), RefOr::Object(response));
+        Ok(responses)
+    }
+}
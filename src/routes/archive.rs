@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use super::traits::{ArchiveRepository, MediaStore};
+use mongodb::bson::oid::ObjectId;
+use rocket::{
+    data::{Data, Limits, ToByteUnit},
+    http::ContentType,
+    serde::json::Json,
+    State,
+};
+use rocket_okapi::openapi;
+use serde_json::Value;
+
+use crate::{
+    db::{cache::ContentCache, events::ContentEvents},
+    errors::response::MyError,
+    models::{
+        archive::{ImportMode, ImportReport},
+        event::{ContentAction, ContentEvent},
+    },
+    request_guards::basic::ApiKey,
+    responders::download::Download,
+    utils::archive::{read_archive, write_zip},
+};
+
+/// download the whole portfolio of the signed in customer as a versioned archive.
+/// <br />`format=zip`, the default, holds `portfolio.json` and the uploaded media under `media/`,
+/// `format=json` only `portfolio.json`.
+#[openapi(tag = "Archive")]
+#[get("/export?<format>")]
+pub async fn export(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    format: Option<&str>,
+) -> Result<Download, MyError> {
+    let with_media = match format.unwrap_or("zip") {
+        "zip" => true,
+        "json" => false,
+        format => {
+            return Err(MyError::build(
+                400,
+                Some(format!("Unknown format {format}, expected zip or json")),
+            ))
+        }
+    };
+
+    let oid = key_oid(&key)?;
+
+    let archive_repo = archive_repo(container)?;
+
+    let archive = match archive_repo.export(oid).await {
+        Ok(Some(archive)) => archive,
+        Ok(None) => {
+            return Err(MyError::build(
+                400,
+                Some(format!("Customer not found with _id {oid}")),
+            ))
+        }
+        Err(error) => return Err(MyError::build(400, Some(error.to_string()))),
+    };
+
+    let file_name = format!(
+        "portfolio-{}",
+        archive.exported_at.get(..10).unwrap_or_default()
+    );
+    if !with_media {
+        let bytes = serde_json::to_vec_pretty(&archive)
+            .map_err(|error| MyError::build(500, Some(error.to_string())))?;
+        return Ok(Download {
+            file_name: format!("{file_name}.json"),
+            content_type: ContentType::JSON,
+            bytes,
+        });
+    }
+
+    let media_store = media_store(container)?;
+
+    let mut media_files = vec![];
+    for media in archive.sections.get("media").into_iter().flatten() {
+        let variants = media["variants"].as_array().into_iter().flatten();
+        let keys = std::iter::once(&media["key"])
+            .chain(variants.map(|variant| &variant["key"]))
+            .filter_map(Value::as_str);
+        for key in keys {
+            // a file missing from the store is left out, its document still is exported
+            if let Ok(Some(bytes)) = media_store.get(key).await {
+                media_files.push((key.to_string(), bytes));
+            }
+        }
+    }
+
+    let bytes = rocket::tokio::task::spawn_blocking(move || write_zip(&archive, media_files))
+        .await
+        .map_err(|error| MyError::build(500, Some(error.to_string())))?
+        .map_err(|error| MyError::build(500, Some(error)))?;
+
+    Ok(Download {
+        file_name: format!("{file_name}.zip"),
+        content_type: ContentType::ZIP,
+        bytes,
+    })
+}
+
+/// restore an archive made by `GET /export`, as a ZIP file or a bare `portfolio.json`.
+/// <br />`mode=merge`, the default, adds and updates content, `mode=replace` also deletes what is
+/// missing from the archive. With `dry_run=true` only the changes are reported.
+/// <br />Documents keep their id when it belongs to this portfolio, articles and collections are
+/// also matched by slug, everything else gets a new id and references are rewritten.
+/// <br />The archive may be as large as the `archive` limit of `Rocket.toml`, 256 MiB by default.
+#[openapi(tag = "Archive")]
+#[post("/import?<mode>&<dry_run>", data = "<data>")]
+pub async fn import(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    limits: &Limits,
+    mode: Option<ImportMode>,
+    dry_run: Option<bool>,
+    data: Data<'_>,
+) -> Result<Json<ImportReport>, MyError> {
+    let mode = mode.unwrap_or_default();
+    let dry_run = dry_run.unwrap_or(false);
+
+    // the other routes keep the small default `bytes` limit
+    let limit = limits.get("archive").unwrap_or(256.mebibytes());
+    let data = data
+        .open(limit)
+        .into_bytes()
+        .await
+        .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+    if !data.is_complete() {
+        return Err(MyError::build(
+            413,
+            Some("The archive is larger than the archive limit".to_string()),
+        ));
+    }
+
+    let oid = key_oid(&key)?;
+
+    let archive_repo = archive_repo(container)?;
+
+    let (archive, version, mut archive_files) =
+        rocket::tokio::task::spawn_blocking(move || read_archive(&data.into_inner()))
+            .await
+            .map_err(|error| MyError::build(500, Some(error.to_string())))?
+            .map_err(|error| MyError::build(400, Some(error)))?;
+
+    let mut plan = archive_repo
+        .plan_import(oid, archive, version, mode)
+        .await
+        .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+
+    let media_files: Vec<_> = plan
+        .media_files
+        .iter()
+        .filter_map(|(archive_key, key)| Some((key.clone(), archive_files.remove(archive_key)?)))
+        .collect();
+    plan.report.media_files = media_files.len();
+
+    if dry_run {
+        return Ok(Json(plan.report));
+    }
+
+    let media_store = media_store(container)?;
+
+    // files first, so the documents never point to a missing file
+    for (key, bytes) in media_files {
+        media_store
+            .put(&key, bytes)
+            .await
+            .map_err(|error| MyError::build(500, Some(error.to_string())))?;
+    }
+
+    let applied = archive_repo.apply_import(oid, &plan).await;
+
+    if let Some(cache) = container.get::<Arc<ContentCache>>() {
+        cache.invalidate_customer(&oid.to_string());
+    }
+    applied.map_err(|error| MyError::build(400, Some(error.to_string())))?;
+
+    // a single event for the whole portfolio, the import bypasses the repositories
+    if let Some(events) = container.get::<Arc<ContentEvents>>() {
+        let customer_id = oid.to_string();
+        events.emit(ContentEvent::new(
+            "profile",
+            ContentAction::Updated,
+            &customer_id,
+            &customer_id,
+            None,
+        ));
+    }
+
+    for key in &plan.deleted_files {
+        let _ = media_store.delete(key).await;
+    }
+
+    plan.report.dry_run = false;
+
+    Ok(Json(plan.report))
+}
+
+fn key_oid(key: &ApiKey) -> Result<ObjectId, MyError> {
+    ObjectId::parse_str(&key.0.sub)
+        .map_err(|_| MyError::build(400, Some("Invalid user id format.".to_string())))
+}
+
+fn archive_repo(
+    container: &State<crate::Container>,
+) -> Result<&Arc<dyn ArchiveRepository + Send + Sync>, MyError> {
+    container
+        .get::<Arc<dyn ArchiveRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))
+}
+
+fn media_store(
+    container: &State<crate::Container>,
+) -> Result<&Arc<dyn MediaStore + Send + Sync>, MyError> {
+    container
+        .get::<Arc<dyn MediaStore + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))
+}
@@ -0,0 +1,230 @@
+use std::sync::Arc;
+
+use super::{client_oid, traits::ArticleRepository};
+use mongodb::bson::oid::ObjectId;
+use rocket::{serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use crate::{
+    db::{article::article_slug, is_duplicate_key},
+    errors::response::MyError,
+    models::article::{Article, ArticleInput, MarkdownInput, RenderedMarkdown},
+    request_guards::basic::{ApiKey, ClientApiKey},
+    responders::cache::Cached,
+    utils::{
+        markdown::{reading_time, render_markdown},
+        seo::validate_seo,
+    },
+};
+
+/// get every article of the signed in customer, drafts included.
+#[openapi(tag = "Article")]
+#[get("/article?<limit>&<page>")]
+pub async fn get_all(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    limit: Option<i64>,
+    page: Option<i64>,
+) -> Result<Json<Vec<Article>>, MyError> {
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let claims = &key.0; // Access the Claims struct
+    let id = &claims.sub;
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let article_repo = container
+        .get::<Arc<dyn ArticleRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match article_repo.find(limit, page, oid, false).await {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// get the published articles of the portfolio owning the api key, newest first.
+#[openapi(tag = "Article")]
+#[get("/articles?<limit>&<page>")]
+pub async fn get(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    limit: Option<i64>,
+    page: Option<i64>,
+) -> Result<Cached<Vec<Article>>, MyError> {
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let oid = client_oid(container, &client_key).await?;
+
+    let article_repo = container
+        .get::<Arc<dyn ArticleRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match article_repo.find(limit, page, oid, true).await {
+        Ok(resp) => Ok(Cached(Json(resp))),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// get a published article of the portfolio owning the api key by its slug.
+#[openapi(tag = "Article")]
+#[get("/articles/<slug>")]
+pub async fn get_by_slug(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    slug: &str,
+) -> Result<Cached<Article>, MyError> {
+    let oid = client_oid(container, &client_key).await?;
+
+    let article_repo = container
+        .get::<Arc<dyn ArticleRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match article_repo.find_by_slug(oid, slug.to_string(), true).await {
+        Ok(Some(resp)) => Ok(Cached(Json(resp))),
+        Ok(None) | Err(_) => Err(MyError::build(
+            404,
+            Some(format!("Article not found with slug {slug}")),
+        )),
+    }
+}
+
+#[openapi(tag = "Article")]
+#[get("/article/<id>")]
+pub async fn get_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+) -> Result<Json<Article>, MyError> {
+    let (_, article) = owned_article(container, &key, id).await?;
+
+    Ok(Json(article))
+}
+
+/// create an article, the slug is generated from the title when it is not given.
+#[openapi(tag = "Article")]
+#[post("/article", data = "<input>")]
+pub async fn post(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    input: Json<ArticleInput>,
+) -> Result<Json<String>, MyError> {
+    let claims = &key.0; // Access the Claims struct
+    let id = &claims.sub;
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let article_repo = container
+        .get::<Arc<dyn ArticleRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    validate_seo(input.seo.as_ref()).map_err(|message| MyError::build(400, Some(message)))?;
+
+    let slug = article_slug(&input);
+    match article_repo.insert(input, oid).await {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) if is_duplicate_key(&error) => Err(MyError::build(
+            400,
+            Some(format!("Article with slug {slug} already exists")),
+        )),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Article")]
+#[patch("/article/<id>", data = "<input>")]
+pub async fn patch_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+    input: Json<ArticleInput>,
+) -> Result<Json<Article>, MyError> {
+    let (oid, _) = owned_article(container, &key, id).await?;
+
+    let article_repo = container
+        .get::<Arc<dyn ArticleRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    validate_seo(input.seo.as_ref()).map_err(|message| MyError::build(400, Some(message)))?;
+
+    let slug = article_slug(&input);
+    match article_repo.update_by_id(oid, input).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) => Err(MyError::build(
+            400,
+            Some(format!("Article not found with id {}", &id)),
+        )),
+        Err(error) if is_duplicate_key(&error) => Err(MyError::build(
+            400,
+            Some(format!("Article with slug {slug} already exists")),
+        )),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Article")]
+#[delete("/article/<id>")]
+pub async fn delete_by_id(
+    container: &State<crate::Container>,
+    id: &str,
+    key: ApiKey,
+) -> Result<Json<Article>, MyError> {
+    let (oid, _) = owned_article(container, &key, id).await?;
+
+    let article_repo = container
+        .get::<Arc<dyn ArticleRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match article_repo.delete_by_id(oid).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Article not found with _id {}", &id)),
+        )),
+    }
+}
+
+/// render Markdown to sanitized HTML without saving it, e.g. for an editor preview.
+#[openapi(tag = "Article")]
+#[post("/article/render", data = "<input>")]
+pub async fn render(_key: ApiKey, input: Json<MarkdownInput>) -> Json<RenderedMarkdown> {
+    Json(RenderedMarkdown {
+        html: render_markdown(&input.body),
+        reading_time: reading_time(&input.body),
+    })
+}
+
+// Drafts are private, only their author may see or change them.
+async fn owned_article(
+    container: &State<crate::Container>,
+    key: &ApiKey,
+    id: &str,
+) -> Result<(ObjectId, Article), MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid id format.".to_string())));
+    };
+
+    let article_repo = container
+        .get::<Arc<dyn ArticleRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match article_repo.find_by_id(oid).await {
+        Ok(Some(article)) if article.customer_id == key.0.sub => Ok((oid, article)),
+        Ok(_) | Err(_) => Err(MyError::build(
+            404,
+            Some(format!("Article not found with _id {}", &id)),
+        )),
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use rocket::{serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use crate::{
+    db::cache::ContentCache, errors::response::MyError, models::cache::CacheStats,
+    request_guards::basic::ApiKey, utils::util::is_admin,
+};
+
+/// get hit/miss counters of the in-process content cache
+/// <br />The counters cover every customer, only the ones in `ADMIN_CUSTOMER_IDS` may read them.
+#[openapi(tag = "Cache")]
+#[get("/cache/stats")]
+pub async fn get_stats(
+    container: &State<crate::Container>,
+    key: ApiKey,
+) -> Result<Json<CacheStats>, MyError> {
+    if !is_admin(&key.0.sub) {
+        return Err(MyError::build(
+            403,
+            Some("Only an admin may read the cache stats.".to_string()),
+        ));
+    }
+
+    let content_cache = container
+        .get::<Arc<ContentCache>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    Ok(Json(content_cache.stats()))
+}
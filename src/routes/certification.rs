@@ -0,0 +1,288 @@
+use std::sync::Arc;
+
+use super::traits::{CertificationRepository, CustomerRepository};
+use mongodb::bson::{doc, oid::ObjectId};
+use rocket::{response::status::BadRequest, serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use crate::{
+    errors::response::MyError,
+    models::{
+        certification::{Certification, CertificationInput, CertificationsInput},
+        response::MessageResponse,
+    },
+    request_guards::basic::{ApiKey, ClientApiKey},
+    responders::cache::Cached,
+};
+
+/// get every certification of the signed in customer, expired ones included.
+#[openapi(tag = "Certification")]
+#[get("/certification?<limit>&<page>")]
+pub async fn get_all(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    limit: Option<i64>,
+    page: Option<i64>,
+) -> Result<Json<Vec<Certification>>, MyError> {
+    // Error handling
+    // This is also valid when strict checking is necessary.
+    // if limit < 0 {
+    //     return Err(BadRequest(Some(Json(MessageResponse {
+    //         message: "limit cannot be less than 0".to_string(),
+    //     }))));
+    // }
+    // if !page.is_none() && page.unwrap() < 1 {
+    //     return Err(BadRequest(Some(Json(MessageResponse {
+    //         message: "page cannot be less than 1".to_string(),
+    //     }))));
+    // }
+
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let claims = &key.0; // Access the Claims struct
+    let id = &claims.sub;
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let certification_repo = container
+        .get::<Arc<dyn CertificationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match certification_repo.find(limit, page, oid, true).await {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// get the certifications of the portfolio owning the api key.
+/// <br />Expired certifications are hidden unless `include_expired=true`.
+#[openapi(tag = "Certification")]
+#[get("/certifications?<limit>&<page>&<include_expired>")]
+pub async fn get(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    limit: Option<i64>,
+    page: Option<i64>,
+    include_expired: Option<bool>,
+) -> Result<Cached<Vec<Certification>>, MyError> {
+    // Error handling
+    // This is also valid when strict checking is necessary.
+    // if limit < 0 {
+    //     return Err(BadRequest(Some(Json(MessageResponse {
+    //         message: "limit cannot be less than 0".to_string(),
+    //     }))));
+    // }
+    // if !page.is_none() && page.unwrap() < 1 {
+    //     return Err(BadRequest(Some(Json(MessageResponse {
+    //         message: "page cannot be less than 1".to_string(),
+    //     }))));
+    // }
+
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let certification_repo = container
+        .get::<Arc<dyn CertificationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let customer_repo = container
+        .get::<Arc<dyn CustomerRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match customer_repo.find_customer_by_api_key(&client_key.0).await {
+        Ok(Some(customer_doc)) => {
+            let Ok(oid) = ObjectId::parse_str(customer_doc.id) else {
+                return Err(MyError::build(
+                    400,
+                    Some("Invalid user id format.".to_string()),
+                ));
+            };
+
+            match certification_repo
+                .find(limit, page, oid, include_expired.unwrap_or(false))
+                .await
+            {
+                Ok(resp) => Ok(Cached(Json(resp))),
+                Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+            }
+        }
+
+        // Either not found or error
+        Ok(None) | Err(_) => Err(MyError::build(
+            400,
+            Some("Incorrect email or password".to_string()),
+        )),
+    }
+}
+
+#[openapi(tag = "Certification")]
+#[get("/certification/<id>")]
+pub async fn get_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+) -> Result<Json<Certification>, MyError> {
+    let (_, certification) = owned_certification(container, &key, id).await?;
+
+    Ok(Json(certification))
+}
+
+#[openapi(tag = "Certification")]
+#[post("/certification", data = "<input>")]
+pub async fn post(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    mut input: Json<CertificationInput>,
+) -> Result<Json<String>, BadRequest<Json<MessageResponse>>> {
+    let certification_repo = container
+        .get::<Arc<dyn CertificationRepository + Send + Sync>>()
+        .ok_or_else(|| {
+            BadRequest(Json(MessageResponse {
+                message: "Service not found".to_string(),
+            }))
+        })?;
+
+    let claims = &key.0; // Access the Claims struct
+    let id = &claims.sub;
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(BadRequest(Json(MessageResponse {
+            message: "Invalid user id format".to_string(),
+        })));
+    };
+
+    // can set with a single error like this.
+    match certification_repo.find(1000, 1, oid, true).await {
+        Ok(resp) => {
+            if let Some(last) = resp.first() {
+                input.order = last.order + 1;
+            }
+
+            match certification_repo.insert(input, oid).await {
+                Ok(resp) => Ok(Json(resp)),
+                Err(_error) => Err(BadRequest(Json(MessageResponse {
+                    message: "Invalid input".to_string(),
+                }))),
+            }
+        }
+        Err(error) => Err(BadRequest(Json(MessageResponse {
+            message: error.to_string(),
+        }))),
+    }
+}
+
+#[openapi(tag = "Certification")]
+#[patch("/certification/<id>", data = "<input>")]
+pub async fn patch_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+    input: Json<CertificationInput>,
+) -> Result<Json<Certification>, MyError> {
+    let (oid, _) = owned_certification(container, &key, id).await?;
+
+    let certification_repo = container
+        .get::<Arc<dyn CertificationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match certification_repo.update_by_id(oid, input).await {
+        Ok(resp) => match resp {
+            Some(resp) => Ok(Json(resp)),
+            None => Err(MyError::build(
+                400,
+                Some(format!("Certification not found with id {}", &id)),
+            )),
+        },
+        Err(_error) => Err(MyError::build(
+            400,
+            Some(format!("Certification not found with id {}", &id)),
+        )),
+    }
+}
+
+#[openapi(tag = "Certification")]
+#[patch("/certification", data = "<input>")]
+pub async fn patch_many(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    input: Json<Vec<CertificationsInput>>,
+) -> Result<Json<Vec<Certification>>, MyError> {
+    let Ok(customer_oid) = ObjectId::parse_str(&key.0.sub) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let certification_repo = container
+        .get::<Arc<dyn CertificationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    // Certifications of other customers are skipped.
+    match certification_repo.update_many(customer_oid, input).await {
+        Ok(resp) => match resp {
+            Some(resp) => Ok(Json(resp)),
+            None => Err(MyError::build(400, Some("Failed to update".to_string()))),
+        },
+        Err(_error) => Err(MyError::build(400, Some("Failed to update".to_string()))),
+    }
+}
+
+#[openapi(tag = "Certification")]
+#[delete("/certification/<id>")]
+pub async fn delete_by_id(
+    container: &State<crate::Container>,
+    id: &str,
+    key: ApiKey,
+) -> Result<Json<Certification>, MyError> {
+    let (oid, _) = owned_certification(container, &key, id).await?;
+
+    let certification_repo = container
+        .get::<Arc<dyn CertificationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match certification_repo.delete_by_id(oid).await {
+        Ok(resp) => match resp {
+            Some(resp) => Ok(Json(resp)),
+            None => Err(MyError::build(
+                400,
+                Some(format!("Certification not found with _id {}", &id)),
+            )),
+        },
+        Err(_error) => Err(MyError::build(
+            400,
+            Some(format!("Certification not found with _id {}", &id)),
+        )),
+    }
+}
+
+// Only the owner of the portfolio may see or change its entries by id.
+async fn owned_certification(
+    container: &State<crate::Container>,
+    key: &ApiKey,
+    id: &str,
+) -> Result<(ObjectId, Certification), MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid id format.".to_string())));
+    };
+
+    let certification_repo = container
+        .get::<Arc<dyn CertificationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match certification_repo.find_by_id(oid).await {
+        Ok(Some(certification)) if certification.customer_id == key.0.sub => {
+            Ok((oid, certification))
+        }
+        Ok(_) | Err(_) => Err(MyError::build(
+            404,
+            Some(format!("Certification not found with _id {}", &id)),
+        )),
+    }
+}
@@ -0,0 +1,455 @@
+use std::sync::Arc;
+
+use super::{
+    client_oid,
+    traits::{CustomCollectionRepository, CustomEntryRepository},
+};
+use mongodb::bson::oid::ObjectId;
+use rocket::{serde::json::Json, State};
+use rocket_okapi::openapi;
+use serde_json::Value;
+
+use crate::{
+    db::collection::collection_slug,
+    errors::response::MyError,
+    models::collection::{
+        CustomCollection, CustomCollectionInput, CustomEntriesInput, CustomEntry, CustomEntryInput,
+    },
+    request_guards::basic::{ApiKey, ClientApiKey},
+    responders::cache::Cached,
+    utils::json_schema::{validate_entry, validate_schema},
+};
+
+#[openapi(tag = "Collection")]
+#[get("/collection?<limit>&<page>")]
+pub async fn get_all(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    limit: Option<i64>,
+    page: Option<i64>,
+) -> Result<Json<Vec<CustomCollection>>, MyError> {
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let oid = key_oid(&key)?;
+
+    match collection_repo(container)?.find(limit, page, oid).await {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// get the custom collections of the portfolio owning the api key, with their schema.
+#[openapi(tag = "Collection")]
+#[get("/collections?<limit>&<page>")]
+pub async fn get(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    limit: Option<i64>,
+    page: Option<i64>,
+) -> Result<Cached<Vec<CustomCollection>>, MyError> {
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let oid = client_oid(container, &client_key).await?;
+
+    match collection_repo(container)?.find(limit, page, oid).await {
+        Ok(resp) => Ok(Cached(Json(resp))),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Collection")]
+#[get("/collection/<id>")]
+pub async fn get_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+) -> Result<Json<CustomCollection>, MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid _id format.".to_string())));
+    };
+    let customer_oid = key_oid(&key)?;
+
+    match collection_repo(container)?.find_by_id(oid).await {
+        Ok(Some(resp)) if resp.customer_id == customer_oid.to_string() => Ok(Json(resp)),
+        Ok(_) | Err(_) => Err(MyError::build(
+            404,
+            Some(format!("Collection not found with _id {}", &id)),
+        )),
+    }
+}
+
+/// create a collection, the slug is generated from the name when it is not given.
+/// <br />`schema` is the JSON Schema of an object every entry is validated against.
+#[openapi(tag = "Collection")]
+#[post("/collection", data = "<input>")]
+pub async fn post(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    input: Json<CustomCollectionInput>,
+) -> Result<Json<String>, MyError> {
+    let oid = key_oid(&key)?;
+    let collection_repo = collection_repo(container)?;
+
+    validate_schema(&input.schema).map_err(|message| MyError::build(400, Some(message)))?;
+
+    let slug = collection_slug(&input);
+    match collection_repo.find_by_slug(oid, slug.clone()).await {
+        Ok(None) => {}
+        Ok(Some(_)) => {
+            return Err(MyError::build(
+                400,
+                Some(format!("Collection with slug {slug} already exists")),
+            ))
+        }
+        Err(error) => return Err(MyError::build(400, Some(error.to_string()))),
+    }
+
+    match collection_repo.insert(input, oid).await {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// update a collection, a new schema has to accept the entries already saved.
+#[openapi(tag = "Collection")]
+#[patch("/collection/<id>", data = "<input>")]
+pub async fn patch_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+    input: Json<CustomCollectionInput>,
+) -> Result<Json<CustomCollection>, MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid id format.".to_string())));
+    };
+    let customer_oid = key_oid(&key)?;
+    let collection_repo = collection_repo(container)?;
+
+    match collection_repo.find_by_id(oid).await {
+        Ok(Some(collection)) if collection.customer_id == customer_oid.to_string() => {}
+        Ok(_) | Err(_) => {
+            return Err(MyError::build(
+                404,
+                Some(format!("Collection not found with id {}", &id)),
+            ))
+        }
+    }
+
+    validate_schema(&input.schema).map_err(|message| MyError::build(400, Some(message)))?;
+
+    let slug = collection_slug(&input);
+    match collection_repo
+        .find_by_slug(customer_oid, slug.clone())
+        .await
+    {
+        Ok(Some(existing)) if existing.id != id => {
+            return Err(MyError::build(
+                400,
+                Some(format!("Collection with slug {slug} already exists")),
+            ))
+        }
+        Ok(_) => {}
+        Err(error) => return Err(MyError::build(400, Some(error.to_string()))),
+    }
+
+    // a limit of 0 returns every entry
+    let entries = entry_repo(container)?
+        .find(0, 1, customer_oid, oid)
+        .await
+        .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+    for entry in entries {
+        validate_entry(&input.schema, &entry.data).map_err(|message| {
+            MyError::build(
+                400,
+                Some(format!("Entry {} no longer valid: {message}", entry.id)),
+            )
+        })?;
+    }
+
+    match collection_repo.update_by_id(oid, input).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) => Err(MyError::build(
+            400,
+            Some(format!("Collection not found with id {}", &id)),
+        )),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// delete a collection and all of its entries.
+#[openapi(tag = "Collection")]
+#[delete("/collection/<id>")]
+pub async fn delete_by_id(
+    container: &State<crate::Container>,
+    id: &str,
+    key: ApiKey,
+) -> Result<Json<CustomCollection>, MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid id format.".to_string())));
+    };
+    let customer_oid = key_oid(&key)?;
+    let collection_repo = collection_repo(container)?;
+
+    match collection_repo.find_by_id(oid).await {
+        Ok(Some(collection)) if collection.customer_id == customer_oid.to_string() => {}
+        Ok(_) | Err(_) => {
+            return Err(MyError::build(
+                404,
+                Some(format!("Collection not found with _id {}", &id)),
+            ))
+        }
+    }
+
+    match collection_repo.delete_by_id(oid).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Collection not found with _id {}", &id)),
+        )),
+    }
+}
+
+#[openapi(tag = "Collection")]
+#[get("/collection/<slug>/entry?<limit>&<page>")]
+pub async fn get_entries(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    slug: &str,
+    limit: Option<i64>,
+    page: Option<i64>,
+) -> Result<Json<Vec<CustomEntry>>, MyError> {
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let oid = key_oid(&key)?;
+    let collection = find_collection(container, oid, slug).await?;
+
+    match entry_repo(container)?
+        .find(limit, page, oid, parse_collection_oid(&collection)?)
+        .await
+    {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// get the entries of a custom collection of the portfolio owning the api key.
+#[openapi(tag = "Collection")]
+#[get("/collections/<slug>?<limit>&<page>")]
+pub async fn get_public_entries(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    slug: &str,
+    limit: Option<i64>,
+    page: Option<i64>,
+) -> Result<Cached<Vec<CustomEntry>>, MyError> {
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let oid = client_oid(container, &client_key).await?;
+    let collection = find_collection(container, oid, slug).await?;
+
+    match entry_repo(container)?
+        .find(limit, page, oid, parse_collection_oid(&collection)?)
+        .await
+    {
+        Ok(resp) => Ok(Cached(Json(resp))),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Collection")]
+#[get("/collection/<slug>/entry/<id>")]
+pub async fn get_entry(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    slug: &str,
+    id: &str,
+) -> Result<Json<CustomEntry>, MyError> {
+    let oid = key_oid(&key)?;
+    let collection = find_collection(container, oid, slug).await?;
+
+    find_entry(container, &collection, id).await.map(Json)
+}
+
+/// create an entry, it is put last in the order.
+/// <br />`data` has to be valid against the schema of the collection.
+#[openapi(tag = "Collection")]
+#[post("/collection/<slug>/entry", data = "<input>")]
+pub async fn post_entry(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    slug: &str,
+    mut input: Json<CustomEntryInput>,
+) -> Result<Json<String>, MyError> {
+    let oid = key_oid(&key)?;
+    let collection = find_collection(container, oid, slug).await?;
+    let collection_oid = parse_collection_oid(&collection)?;
+    let entry_repo = entry_repo(container)?;
+
+    validate_data(&collection, &input.data)?;
+
+    match entry_repo.find(1, 1, oid, collection_oid).await {
+        Ok(resp) => {
+            if let Some(last) = resp.first() {
+                input.order = last.order + 1;
+            }
+        }
+        Err(error) => return Err(MyError::build(400, Some(error.to_string()))),
+    }
+
+    match entry_repo.insert(input, oid, collection_oid).await {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Collection")]
+#[patch("/collection/<slug>/entry/<id>", data = "<input>")]
+pub async fn patch_entry(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    slug: &str,
+    id: &str,
+    input: Json<CustomEntryInput>,
+) -> Result<Json<CustomEntry>, MyError> {
+    let oid = key_oid(&key)?;
+    let collection = find_collection(container, oid, slug).await?;
+    let entry = find_entry(container, &collection, id).await?;
+
+    validate_data(&collection, &input.data)?;
+
+    match entry_repo(container)?
+        .update_by_id(ObjectId::parse_str(&entry.id).unwrap(), input)
+        .await
+    {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) => Err(MyError::build(
+            400,
+            Some(format!("Entry not found with id {}", &id)),
+        )),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// update several entries at once, e.g. to reorder them. `data` is optional.
+#[openapi(tag = "Collection")]
+#[patch("/collection/<slug>/entry", data = "<input>")]
+pub async fn patch_entries(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    slug: &str,
+    input: Json<Vec<CustomEntriesInput>>,
+) -> Result<Json<Vec<CustomEntry>>, MyError> {
+    let oid = key_oid(&key)?;
+    let collection = find_collection(container, oid, slug).await?;
+
+    for data in input.iter().filter_map(|item| item.data.as_ref()) {
+        validate_data(&collection, data)?;
+    }
+
+    match entry_repo(container)?
+        .update_many(parse_collection_oid(&collection)?, input)
+        .await
+    {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) | Err(_) => Err(MyError::build(400, Some("Failed to update".to_string()))),
+    }
+}
+
+#[openapi(tag = "Collection")]
+#[delete("/collection/<slug>/entry/<id>")]
+pub async fn delete_entry(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    slug: &str,
+    id: &str,
+) -> Result<Json<CustomEntry>, MyError> {
+    let oid = key_oid(&key)?;
+    let collection = find_collection(container, oid, slug).await?;
+    let entry = find_entry(container, &collection, id).await?;
+
+    match entry_repo(container)?
+        .delete_by_id(ObjectId::parse_str(&entry.id).unwrap())
+        .await
+    {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Entry not found with _id {}", &id)),
+        )),
+    }
+}
+
+fn collection_repo(
+    container: &State<crate::Container>,
+) -> Result<&Arc<dyn CustomCollectionRepository + Send + Sync>, MyError> {
+    container
+        .get::<Arc<dyn CustomCollectionRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))
+}
+
+fn entry_repo(
+    container: &State<crate::Container>,
+) -> Result<&Arc<dyn CustomEntryRepository + Send + Sync>, MyError> {
+    container
+        .get::<Arc<dyn CustomEntryRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))
+}
+
+fn key_oid(key: &ApiKey) -> Result<ObjectId, MyError> {
+    ObjectId::parse_str(&key.0.sub)
+        .map_err(|_| MyError::build(400, Some("Invalid user id format.".to_string())))
+}
+
+fn parse_collection_oid(collection: &CustomCollection) -> Result<ObjectId, MyError> {
+    ObjectId::parse_str(&collection.id)
+        .map_err(|_| MyError::build(400, Some("Invalid _id format.".to_string())))
+}
+
+// Collection of the customer with this slug, 404 otherwise.
+async fn find_collection(
+    container: &State<crate::Container>,
+    oid: ObjectId,
+    slug: &str,
+) -> Result<CustomCollection, MyError> {
+    match collection_repo(container)?
+        .find_by_slug(oid, slug.to_string())
+        .await
+    {
+        Ok(Some(collection)) => Ok(collection),
+        Ok(None) | Err(_) => Err(MyError::build(
+            404,
+            Some(format!("Collection not found with slug {slug}")),
+        )),
+    }
+}
+
+// Entry with this id in the collection, 404 otherwise.
+async fn find_entry(
+    container: &State<crate::Container>,
+    collection: &CustomCollection,
+    id: &str,
+) -> Result<CustomEntry, MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid _id format.".to_string())));
+    };
+
+    match entry_repo(container)?.find_by_id(oid).await {
+        Ok(Some(entry)) if entry.collection_id == collection.id => Ok(entry),
+        Ok(_) | Err(_) => Err(MyError::build(
+            404,
+            Some(format!("Entry not found with _id {id}")),
+        )),
+    }
+}
+
+fn validate_data(collection: &CustomCollection, data: &Value) -> Result<(), MyError> {
+    validate_entry(&collection.schema, data).map_err(|message| MyError::build(400, Some(message)))
+}
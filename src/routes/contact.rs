@@ -0,0 +1,289 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use super::{
+    client_oid,
+    traits::{ContactNotifier, ContactRepository},
+};
+use mongodb::bson::oid::ObjectId;
+use rocket::{serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use crate::{
+    errors::response::MyError,
+    models::{
+        contact::{
+            ContactFilter, ContactInput, ContactMessage, ContactMessageUpdate, ContactToken,
+        },
+        response::MessageResponse,
+    },
+    request_guards::basic::{ApiKey, ClientApiKey},
+    utils::spam::{ContactProtection, SpamCheck, SpamForm},
+};
+
+const MAX_NAME_LENGTH: usize = 100;
+const MAX_EMAIL_LENGTH: usize = 254;
+const MAX_SUBJECT_LENGTH: usize = 200;
+const MAX_MESSAGE_LENGTH: usize = 5000;
+
+/// get a token for the contact form of the portfolio owning the api key.
+/// <br />Fetch it when the form is shown and send it back with the message.
+#[openapi(tag = "Contact")]
+#[get("/contact/token")]
+pub async fn token(
+    protection: &State<ContactProtection>,
+    client_key: ClientApiKey,
+) -> Json<ContactToken> {
+    Json(ContactToken {
+        token: protection.issue_token(&client_key.0),
+        difficulty: protection.difficulty(),
+    })
+}
+
+/// send a message to the portfolio owning the api key.
+/// <br />Messages are rate limited by the client address, read from the `ip_header` configured
+/// in Rocket.toml when the server is behind a proxy.
+/// <br />When the token has a difficulty, `nonce` has to make `sha256("{token}:{nonce}")`
+/// start with that many zero bits.
+#[openapi(tag = "Contact")]
+#[post("/contact", data = "<input>")]
+pub async fn send(
+    container: &State<crate::Container>,
+    protection: &State<ContactProtection>,
+    client_key: ClientApiKey,
+    ip: Option<IpAddr>,
+    input: Json<ContactInput>,
+) -> Result<Json<MessageResponse>, MyError> {
+    let sent = Json(MessageResponse {
+        message: "Message sent.".to_string(),
+    });
+
+    let oid = client_oid(container, &client_key).await?;
+
+    validate(&input)?;
+
+    let ip = ip.map(|ip| ip.to_string());
+    match protection.check(
+        SpamForm::Contact,
+        &client_key.0,
+        ip.as_deref(),
+        &input.token,
+        input.nonce.as_deref(),
+        input.website.as_deref(),
+    ) {
+        Ok(()) => {}
+        // Do not tell bots they were caught.
+        Err(SpamCheck::Honeypot) => return Ok(sent),
+        Err(check) => return Err(spam_error(check)),
+    }
+
+    let contact_repo = container
+        .get::<Arc<dyn ContactRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let notifier = container
+        .get::<Arc<dyn ContactNotifier + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?
+        .clone();
+
+    match contact_repo.insert(input, oid, ip).await {
+        Ok(message) => {
+            // Do not make the visitor wait for the notification.
+            rocket::tokio::spawn(async move { notifier.notify(&message).await });
+            Ok(sent)
+        }
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// get the inbox of the signed in customer, newest first.
+/// <br />Archived messages are only listed with `archived=true`.
+#[openapi(tag = "Contact")]
+#[get("/contact?<limit>&<page>&<read>&<archived>")]
+pub async fn get_all(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    limit: Option<i64>,
+    page: Option<i64>,
+    read: Option<bool>,
+    archived: Option<bool>,
+) -> Result<Json<Vec<ContactMessage>>, MyError> {
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let claims = &key.0; // Access the Claims struct
+    let id = &claims.sub;
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let contact_repo = container
+        .get::<Arc<dyn ContactRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let filter = ContactFilter {
+        read,
+        archived: archived.unwrap_or(false),
+    };
+    match contact_repo.find(limit, page, oid, filter).await {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Contact")]
+#[get("/contact/<id>")]
+pub async fn get_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+) -> Result<Json<ContactMessage>, MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid _id format.".to_string())));
+    };
+
+    let contact_repo = container
+        .get::<Arc<dyn ContactRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match contact_repo.find_by_id(oid).await {
+        Ok(Some(resp)) if resp.customer_id == key.0.sub => Ok(Json(resp)),
+        Ok(_) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Message not found with _id {}", &id)),
+        )),
+    }
+}
+
+/// mark a message as read or unread, archive or restore it.
+#[openapi(tag = "Contact")]
+#[patch("/contact/<id>", data = "<input>")]
+pub async fn patch_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+    input: Json<ContactMessageUpdate>,
+) -> Result<Json<ContactMessage>, MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid id format.".to_string())));
+    };
+
+    let contact_repo = container
+        .get::<Arc<dyn ContactRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    // Messages are private, only their recipient may change them.
+    match contact_repo.find_by_id(oid).await {
+        Ok(Some(message)) if message.customer_id == key.0.sub => {}
+        Ok(_) | Err(_) => {
+            return Err(MyError::build(
+                400,
+                Some(format!("Message not found with _id {}", &id)),
+            ))
+        }
+    }
+
+    match contact_repo.update_by_id(oid, input).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) => Err(MyError::build(
+            400,
+            Some(format!("Message not found with id {}", &id)),
+        )),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Contact")]
+#[delete("/contact/<id>")]
+pub async fn delete_by_id(
+    container: &State<crate::Container>,
+    id: &str,
+    key: ApiKey,
+) -> Result<Json<ContactMessage>, MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid id format.".to_string())));
+    };
+
+    let contact_repo = container
+        .get::<Arc<dyn ContactRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match contact_repo.find_by_id(oid).await {
+        Ok(Some(message)) if message.customer_id == key.0.sub => {}
+        Ok(_) | Err(_) => {
+            return Err(MyError::build(
+                400,
+                Some(format!("Message not found with _id {}", &id)),
+            ))
+        }
+    }
+
+    match contact_repo.delete_by_id(oid).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Message not found with _id {}", &id)),
+        )),
+    }
+}
+
+fn validate(input: &ContactInput) -> Result<(), MyError> {
+    if input.name.trim().is_empty() || input.name.chars().count() > MAX_NAME_LENGTH {
+        return Err(MyError::build(
+            400,
+            Some(format!(
+                "name is required and at most {MAX_NAME_LENGTH} characters long"
+            )),
+        ));
+    }
+    let email = input.email.trim();
+    let valid_email = email.len() <= MAX_EMAIL_LENGTH
+        && email
+            .split_once('@')
+            .is_some_and(|(user, domain)| !user.is_empty() && domain.contains('.'));
+    if !valid_email {
+        return Err(MyError::build(
+            400,
+            Some("email is not a valid address".to_string()),
+        ));
+    }
+    if input
+        .subject
+        .as_ref()
+        .is_some_and(|subject| subject.chars().count() > MAX_SUBJECT_LENGTH)
+    {
+        return Err(MyError::build(
+            400,
+            Some(format!(
+                "subject is at most {MAX_SUBJECT_LENGTH} characters long"
+            )),
+        ));
+    }
+    if input.message.trim().is_empty() || input.message.chars().count() > MAX_MESSAGE_LENGTH {
+        return Err(MyError::build(
+            400,
+            Some(format!(
+                "message is required and at most {MAX_MESSAGE_LENGTH} characters long"
+            )),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Error answered for a submission turned down by the spam checks.
+pub(super) fn spam_error(check: SpamCheck) -> MyError {
+    let (status, message) = match check {
+        SpamCheck::Honeypot | SpamCheck::InvalidToken => (400, "Invalid token."),
+        SpamCheck::TooFast => (400, "The form was sent too fast, please try again."),
+        SpamCheck::Expired => (400, "The token expired, please reload the form."),
+        SpamCheck::InvalidProofOfWork => (400, "Invalid proof-of-work nonce."),
+        SpamCheck::RateLimited => (429, "Too many messages, please try again later."),
+    };
+
+    MyError::build(status, Some(message.to_string()))
+}
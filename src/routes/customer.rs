@@ -1,16 +1,47 @@
 use std::sync::Arc;
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use mongodb::bson::{doc, oid::ObjectId};
-use rocket::{serde::json::Json, State};
+use rocket::{http::ContentType, serde::json::Json, State};
 use rocket_okapi::openapi;
+use serde_json::Value;
 
 use crate::{
     errors::response::MyError,
-    models::customer::{Customer, CustomerUpdateInput},
-    request_guards::basic::{ApiKey, ClientApiKey},
+    models::{
+        customer::{Customer, CustomerUpdateInput},
+        experience::Experience,
+        skill::Skill,
+        social::Social,
+    },
+    request_guards::{
+        basic::{ApiKey, ClientApiKey},
+        locale::AcceptLanguage,
+    },
+    responders::{cache::Cached, download::Download},
+    utils::{
+        i18n::{
+            requested_locales, validate_locales, validate_translations, LocaleChain, Translatable,
+        },
+        image::contact_photo,
+        seo::{person_json_ld, validate_seo, validate_site_url},
+        vcard::profile_vcard,
+    },
+};
+
+use super::{
+    client_customer,
+    traits::{
+        CustomerRepository, ExperienceRepository, MediaRepository, MediaStore, SkillRepository,
+        SocialRepository,
+    },
 };
 
-use super::traits::CustomerRepository;
+// Upper bound for every section, same as the portfolio.
+const SECTION_LIMIT: i64 = 1000;
+
+// Pixels of the photo embedded into the vCard.
+const VCARD_PHOTO_SIZE: u32 = 256;
 
 // get customer documents
 // #[openapi(tag = "Customer")]
@@ -83,12 +114,16 @@ pub async fn get_customer_by_id(
     }
 }
 
+/// get the profile of the portfolio owning the api key.
+/// <br />`intro` and `about` are translated to `locale` or the `Accept-Language` header.
 #[openapi(tag = "Customer")]
-#[get("/customer/profile")]
+#[get("/customer/profile?<locale>")]
 pub async fn get_customer_profile(
     container: &State<crate::Container>,
     client_key: ClientApiKey,
-) -> Result<Json<Customer>, MyError> {
+    accept_language: AcceptLanguage,
+    locale: Option<String>,
+) -> Result<Cached<Customer>, MyError> {
     let customer_repo = container
         .get::<Arc<dyn CustomerRepository + Send + Sync>>()
         .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
@@ -99,7 +134,14 @@ pub async fn get_customer_profile(
                 400,
                 Some(format!("Customer not found with api key {}", client_key.0)),
             )),
-            Some(customer_doc) => Ok(Json(customer_doc)),
+            Some(mut customer_doc) => {
+                LocaleChain::new(
+                    &requested_locales(locale.as_deref(), &accept_language.0),
+                    &customer_doc,
+                )
+                .localize(&mut customer_doc);
+                Ok(Cached(Json(customer_doc)))
+            }
         },
         Err(_error) => Err(MyError::build(
             400,
@@ -108,6 +150,129 @@ pub async fn get_customer_profile(
     }
 }
 
+/// download the profile of the portfolio owning the api key as a vCard 4.0 contact.
+/// <br />It holds the current position, the socials and the profile picture, embedded when it is
+/// an uploaded media. Translated to `locale` or the `Accept-Language` header.
+#[openapi(tag = "Customer")]
+#[get("/customer/profile.vcf?<locale>")]
+pub async fn get_customer_vcard(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    accept_language: AcceptLanguage,
+    locale: Option<String>,
+) -> Result<Download, MyError> {
+    let locales = requested_locales(locale.as_deref(), &accept_language.0);
+    let (customer, experiences, socials, _) =
+        load_profile(container, &client_key, &locales).await?;
+    let photo = vcard_photo(container, &customer).await;
+
+    let name = Some(slug::slugify(&customer.name))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "contact".to_string());
+    Ok(Download {
+        file_name: format!("{name}.vcf"),
+        content_type: ContentType::new("text", "vcard"),
+        bytes: profile_vcard(&customer, &experiences, &socials, photo.as_deref()).into_bytes(),
+    })
+}
+
+/// get a schema.org `Person` of the portfolio owning the api key, as JSON-LD to embed into
+/// `<script type="application/ld+json">`.
+/// <br />Current positions are `worksFor`, every position is in `hasOccupation` with its skills,
+/// and the socials are `sameAs`. Translated to `locale` or the `Accept-Language` header.
+#[openapi(tag = "Customer")]
+#[get("/customer/profile.jsonld?<locale>")]
+pub async fn get_customer_json_ld(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    accept_language: AcceptLanguage,
+    locale: Option<String>,
+) -> Result<(ContentType, Json<Value>), MyError> {
+    let locales = requested_locales(locale.as_deref(), &accept_language.0);
+    let (customer, experiences, socials, skills) =
+        load_profile(container, &client_key, &locales).await?;
+
+    Ok((
+        ContentType::new("application", "ld+json"),
+        Json(person_json_ld(&customer, &experiences, &socials, &skills)),
+    ))
+}
+
+// The customer of the key with the sections of a contact card, translated to `locales`.
+async fn load_profile(
+    container: &State<crate::Container>,
+    client_key: &ClientApiKey,
+    locales: &[String],
+) -> Result<(Customer, Vec<Experience>, Vec<Social>, Vec<Skill>), MyError> {
+    let mut customer = client_customer(container, client_key).await?;
+    let Ok(oid) = ObjectId::parse_str(&customer.id) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let experience_repo = container
+        .get::<Arc<dyn ExperienceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+    let social_repo = container
+        .get::<Arc<dyn SocialRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+    let skill_repo = container
+        .get::<Arc<dyn SkillRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let (mut experiences, socials, skills) = futures::try_join!(
+        experience_repo.find(SECTION_LIMIT, 1, oid),
+        social_repo.find(SECTION_LIMIT, 1, oid),
+        skill_repo.find(SECTION_LIMIT, 1, oid),
+    )
+    .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+
+    let chain = LocaleChain::new(locales, &customer);
+    chain.localize(&mut customer);
+    experiences
+        .iter_mut()
+        .for_each(|experience| chain.localize(experience));
+
+    Ok((customer, experiences, socials, skills))
+}
+
+// An uploaded picture is embedded as a small JPEG, any other one is linked when it is absolute.
+async fn vcard_photo(container: &State<crate::Container>, customer: &Customer) -> Option<String> {
+    let media_id = customer
+        .profile_picture_id
+        .as_deref()
+        .and_then(|id| ObjectId::parse_str(id).ok());
+    if let Some(media_id) = media_id {
+        let media_repo = container.get::<Arc<dyn MediaRepository + Send + Sync>>()?;
+        let media_store = container.get::<Arc<dyn MediaStore + Send + Sync>>()?;
+        let media = media_repo.find_by_id(media_id).await.ok().flatten();
+        if let Some(media) = media.filter(|media| media.customer_id == customer.id) {
+            if let Ok(Some(bytes)) = media_store.get(&media.key).await {
+                let jpeg = rocket::tokio::task::spawn_blocking(move || {
+                    contact_photo(&bytes, VCARD_PHOTO_SIZE)
+                })
+                .await
+                .ok()
+                .flatten();
+                if let Some(jpeg) = jpeg {
+                    return Some(format!("data:image/jpeg;base64,{}", STANDARD.encode(jpeg)));
+                }
+            }
+        }
+    }
+
+    let picture = customer.profile_picture.as_deref()?;
+    if picture.starts_with("http://") || picture.starts_with("https://") {
+        return Some(picture.to_string());
+    }
+    let site_url = customer.site_url.as_deref()?;
+    picture
+        .starts_with('/')
+        .then(|| format!("{site_url}{picture}"))
+}
+
 // create a customer document
 // #[openapi(tag = "Customer")]
 // #[post("/customer", data = "<input>")]
@@ -149,6 +314,12 @@ pub async fn patch_customer_by_id(
         ));
     };
 
+    validate_locales(input.default_locale.as_deref(), input.locales.as_deref())
+        .and_then(|_| validate_translations(input.translations.as_ref(), Customer::FIELDS))
+        .and_then(|_| validate_site_url(input.site_url.as_deref()))
+        .and_then(|_| validate_seo(input.seo.as_ref()))
+        .map_err(|message| MyError::build(400, Some(message)))?;
+
     let customer_repo = container
         .get::<Arc<dyn CustomerRepository + Send + Sync>>()
         .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
@@ -175,10 +346,7 @@ pub async fn patch_customer_by_id(
                             )),
                         }
                     }
-                    _ => match customer_repo
-                        .find_customer_by_email(email)
-                        .await
-                    {
+                    _ => match customer_repo.find_customer_by_email(email).await {
                         Ok(email_customer) => match email_customer {
                             // Email already exists, return error
                             Some(_existing_customer) => Err(MyError::build(
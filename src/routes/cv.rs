@@ -0,0 +1,355 @@
+use std::sync::Arc;
+
+use super::{
+    client_customer,
+    traits::{
+        CustomerRepository, CvRepository, EducationRepository, ExperienceRepository,
+        ProjectRepository, SkillRepository, SocialRepository,
+    },
+};
+use mongodb::bson::oid::ObjectId;
+use rocket::{http::ContentType, response::content::RawHtml, serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use crate::{
+    errors::response::MyError,
+    models::{
+        customer::Customer,
+        cv::{Cv, CvSettings, CvSettingsDocument, CvSettingsInput, DEFAULT_CV_TEMPLATE},
+        project::ProjectFilter,
+    },
+    request_guards::{
+        basic::{ApiKey, ClientApiKey},
+        locale::AcceptLanguage,
+    },
+    responders::download::Download,
+    utils::{
+        cv::{to_cv, CvTemplates},
+        i18n::{requested_locales, LocaleChain},
+        pdf::cv_pdf,
+        util::generate_api_key,
+    },
+};
+
+// Upper bound for every section, same as the portfolio.
+const SECTION_LIMIT: i64 = 1000;
+
+/// get the CV as an HTML page, rendered with the template picked in `/cv/settings`.
+/// <br />Opened with an `X-API-KEY` header, or with the `token` of `POST /cv/share` so a link can
+/// be sent to a recruiter. `template` previews another template.
+/// <br />The profile, projects and experiences are translated to `locale` or the
+/// `Accept-Language` header.
+#[openapi(tag = "CV")]
+#[get("/cv.html?<token>&<template>&<locale>")]
+pub async fn get_html(
+    container: &State<crate::Container>,
+    templates: &State<CvTemplates>,
+    client_key: Option<ClientApiKey>,
+    accept_language: AcceptLanguage,
+    token: Option<&str>,
+    template: Option<&str>,
+    locale: Option<String>,
+) -> Result<RawHtml<String>, MyError> {
+    let (cv, template) = load_cv(
+        container,
+        templates,
+        client_key,
+        accept_language,
+        token,
+        template,
+        locale,
+    )
+    .await?;
+
+    templates
+        .render(&template, &cv)
+        .map(RawHtml)
+        .map_err(|error| MyError::build(500, Some(error)))
+}
+
+/// download the CV as an A4 PDF, generated by the server.
+/// <br />Same keys and parameters as `GET /cv.html`, the template picks the style of the PDF.
+#[openapi(tag = "CV")]
+#[get("/cv.pdf?<token>&<template>&<locale>")]
+pub async fn get_pdf(
+    container: &State<crate::Container>,
+    templates: &State<CvTemplates>,
+    client_key: Option<ClientApiKey>,
+    accept_language: AcceptLanguage,
+    token: Option<&str>,
+    template: Option<&str>,
+    locale: Option<String>,
+) -> Result<Download, MyError> {
+    let (cv, template) = load_cv(
+        container,
+        templates,
+        client_key,
+        accept_language,
+        token,
+        template,
+        locale,
+    )
+    .await?;
+
+    let file_name = format!("CV {}.pdf", cv.name);
+    let bytes = rocket::tokio::task::spawn_blocking(move || cv_pdf(&cv, &template))
+        .await
+        .map_err(|error| MyError::build(500, Some(error.to_string())))?;
+
+    Ok(Download {
+        file_name,
+        content_type: ContentType::PDF,
+        bytes,
+    })
+}
+
+/// get the CV template and share token of the signed in customer.
+#[openapi(tag = "CV")]
+#[get("/cv/settings")]
+pub async fn get_settings(
+    container: &State<crate::Container>,
+    templates: &State<CvTemplates>,
+    key: ApiKey,
+) -> Result<Json<CvSettings>, MyError> {
+    let oid = key_oid(&key)?;
+
+    let settings = cv_repo(container)?
+        .find_settings(oid)
+        .await
+        .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+
+    to_settings(templates, settings, oid)
+}
+
+/// pick the template of `GET /cv.html` and `GET /cv.pdf`.
+#[openapi(tag = "CV")]
+#[patch("/cv/settings", data = "<input>")]
+pub async fn patch_settings(
+    container: &State<crate::Container>,
+    templates: &State<CvTemplates>,
+    key: ApiKey,
+    input: Json<CvSettingsInput>,
+) -> Result<Json<CvSettings>, MyError> {
+    let oid = key_oid(&key)?;
+
+    if !templates.contains(&input.template) {
+        return Err(MyError::build(
+            400,
+            Some(format!(
+                "Unknown template {}, expected one of {}",
+                input.template,
+                templates.names().join(", ")
+            )),
+        ));
+    }
+
+    let settings = cv_repo(container)?
+        .update_template(oid, &input.template)
+        .await
+        .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+
+    to_settings(templates, settings, oid)
+}
+
+/// create a new share token, the previous one stops working.
+#[openapi(tag = "CV")]
+#[post("/cv/share")]
+pub async fn post_share(
+    container: &State<crate::Container>,
+    templates: &State<CvTemplates>,
+    key: ApiKey,
+) -> Result<Json<CvSettings>, MyError> {
+    let oid = key_oid(&key)?;
+
+    let settings = cv_repo(container)?
+        .update_share_token(oid, Some(&generate_api_key()))
+        .await
+        .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+
+    to_settings(templates, settings, oid)
+}
+
+/// stop sharing the CV, only an `X-API-KEY` opens it afterwards.
+#[openapi(tag = "CV")]
+#[delete("/cv/share")]
+pub async fn delete_share(
+    container: &State<crate::Container>,
+    templates: &State<CvTemplates>,
+    key: ApiKey,
+) -> Result<Json<CvSettings>, MyError> {
+    let oid = key_oid(&key)?;
+
+    let settings = cv_repo(container)?
+        .update_share_token(oid, None)
+        .await
+        .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+
+    to_settings(templates, settings, oid)
+}
+
+// CV of the customer owning the share token or the api key, with the template to render.
+async fn load_cv(
+    container: &State<crate::Container>,
+    templates: &CvTemplates,
+    client_key: Option<ClientApiKey>,
+    accept_language: AcceptLanguage,
+    token: Option<&str>,
+    template: Option<&str>,
+    locale: Option<String>,
+) -> Result<(Cv, String), MyError> {
+    let customer_repo = container
+        .get::<Arc<dyn CustomerRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let cv_repo = cv_repo(container)?;
+
+    let mut customer = match (token, client_key) {
+        (Some(token), _) => {
+            let oid = match cv_repo.find_customer_id_by_share_token(token).await {
+                Ok(Some(oid)) => oid,
+                Ok(None) | Err(_) => {
+                    return Err(MyError::build(401, Some("Invalid share token".to_string())))
+                }
+            };
+            match customer_repo.find_customer_by_id(oid).await {
+                Ok(Some(customer)) => customer,
+                Ok(None) | Err(_) => {
+                    return Err(MyError::build(
+                        400,
+                        Some(format!("Customer not found with _id {oid}")),
+                    ))
+                }
+            }
+        }
+        (None, Some(client_key)) => client_customer(container, &client_key).await?,
+        (None, None) => {
+            return Err(MyError::build(
+                401,
+                Some("A share token or an X-API-KEY header is required".to_string()),
+            ))
+        }
+    };
+    let oid = customer_oid(&customer)?;
+
+    let template = match template {
+        Some(template) if templates.contains(template) => template.to_string(),
+        Some(template) => {
+            return Err(MyError::build(
+                400,
+                Some(format!(
+                    "Unknown template {template}, expected one of {}",
+                    templates.names().join(", ")
+                )),
+            ))
+        }
+        None => cv_repo
+            .find_settings(oid)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|settings| settings.cv_template)
+            // a template removed from `CV_TEMPLATE_DIR` falls back to the default
+            .filter(|template| templates.contains(template))
+            .unwrap_or_else(|| DEFAULT_CV_TEMPLATE.to_string()),
+    };
+
+    let social_repo = container
+        .get::<Arc<dyn SocialRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let experience_repo = container
+        .get::<Arc<dyn ExperienceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let education_repo = container
+        .get::<Arc<dyn EducationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let skill_repo = container
+        .get::<Arc<dyn SkillRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let project_repo = container
+        .get::<Arc<dyn ProjectRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let (socials, mut experiences, educations, skills, mut projects) = futures::try_join!(
+        social_repo.find(SECTION_LIMIT, 1, oid),
+        experience_repo.find(SECTION_LIMIT, 1, oid),
+        education_repo.find(SECTION_LIMIT, 1, oid),
+        skill_repo.find(SECTION_LIMIT, 1, oid),
+        project_repo.find(SECTION_LIMIT, 1, oid, ProjectFilter::default()),
+    )
+    .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+
+    let requested = requested_locales(locale.as_deref(), &accept_language.0);
+    let chain = LocaleChain::new(&requested, &customer);
+    chain.localize(&mut customer);
+    experiences
+        .iter_mut()
+        .for_each(|experience| chain.localize(experience));
+    projects
+        .iter_mut()
+        .for_each(|project| chain.localize(project));
+
+    let locale = requested
+        .into_iter()
+        .find(|locale| {
+            customer
+                .locales
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(locale))
+        })
+        .or_else(|| customer.default_locale.clone());
+
+    let cv = to_cv(
+        &customer,
+        &socials,
+        &experiences,
+        &educations,
+        &skills,
+        &projects,
+        locale,
+    );
+
+    Ok((cv, template))
+}
+
+fn to_settings(
+    templates: &CvTemplates,
+    settings: Option<CvSettingsDocument>,
+    oid: ObjectId,
+) -> Result<Json<CvSettings>, MyError> {
+    let Some(settings) = settings else {
+        return Err(MyError::build(
+            400,
+            Some(format!("Customer not found with _id {oid}")),
+        ));
+    };
+
+    Ok(Json(CvSettings {
+        template: settings
+            .cv_template
+            .unwrap_or_else(|| DEFAULT_CV_TEMPLATE.to_string()),
+        templates: templates.names().to_vec(),
+        share_token: settings.cv_share_token,
+    }))
+}
+
+fn cv_repo(
+    container: &State<crate::Container>,
+) -> Result<&Arc<dyn CvRepository + Send + Sync>, MyError> {
+    container
+        .get::<Arc<dyn CvRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))
+}
+
+fn key_oid(key: &ApiKey) -> Result<ObjectId, MyError> {
+    ObjectId::parse_str(&key.0.sub)
+        .map_err(|_| MyError::build(400, Some("Invalid user id format.".to_string())))
+}
+
+fn customer_oid(customer: &Customer) -> Result<ObjectId, MyError> {
+    ObjectId::parse_str(&customer.id)
+        .map_err(|_| MyError::build(400, Some("Invalid user id format.".to_string())))
+}
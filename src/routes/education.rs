@@ -0,0 +1,279 @@
+use std::sync::Arc;
+
+use super::traits::{CustomerRepository, EducationRepository};
+use mongodb::bson::{doc, oid::ObjectId};
+use rocket::{response::status::BadRequest, serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use crate::{
+    errors::response::MyError,
+    models::{
+        education::{Education, EducationInput, EducationsInput},
+        response::MessageResponse,
+    },
+    request_guards::basic::{ApiKey, ClientApiKey},
+    responders::cache::Cached,
+};
+
+#[openapi(tag = "Education")]
+#[get("/education?<limit>&<page>")]
+pub async fn get_all(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    limit: Option<i64>,
+    page: Option<i64>,
+) -> Result<Json<Vec<Education>>, MyError> {
+    // Error handling
+    // This is also valid when strict checking is necessary.
+    // if limit < 0 {
+    //     return Err(BadRequest(Some(Json(MessageResponse {
+    //         message: "limit cannot be less than 0".to_string(),
+    //     }))));
+    // }
+    // if !page.is_none() && page.unwrap() < 1 {
+    //     return Err(BadRequest(Some(Json(MessageResponse {
+    //         message: "page cannot be less than 1".to_string(),
+    //     }))));
+    // }
+
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let claims = &key.0; // Access the Claims struct
+    let id = &claims.sub;
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let education_repo = container
+        .get::<Arc<dyn EducationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match education_repo.find(limit, page, oid).await {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Education")]
+#[get("/educations?<limit>&<page>")]
+pub async fn get(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    limit: Option<i64>,
+    page: Option<i64>,
+) -> Result<Cached<Vec<Education>>, MyError> {
+    // Error handling
+    // This is also valid when strict checking is necessary.
+    // if limit < 0 {
+    //     return Err(BadRequest(Some(Json(MessageResponse {
+    //         message: "limit cannot be less than 0".to_string(),
+    //     }))));
+    // }
+    // if !page.is_none() && page.unwrap() < 1 {
+    //     return Err(BadRequest(Some(Json(MessageResponse {
+    //         message: "page cannot be less than 1".to_string(),
+    //     }))));
+    // }
+
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let education_repo = container
+        .get::<Arc<dyn EducationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let customer_repo = container
+        .get::<Arc<dyn CustomerRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match customer_repo.find_customer_by_api_key(&client_key.0).await {
+        Ok(Some(customer_doc)) => {
+            let Ok(oid) = ObjectId::parse_str(customer_doc.id) else {
+                return Err(MyError::build(
+                    400,
+                    Some("Invalid user id format.".to_string()),
+                ));
+            };
+
+            match education_repo.find(limit, page, oid).await {
+                Ok(resp) => Ok(Cached(Json(resp))),
+                Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+            }
+        }
+
+        // Either not found or error
+        Ok(None) | Err(_) => Err(MyError::build(
+            400,
+            Some("Incorrect email or password".to_string()),
+        )),
+    }
+}
+
+#[openapi(tag = "Education")]
+#[get("/education/<id>")]
+pub async fn get_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+) -> Result<Json<Education>, MyError> {
+    let (_, education) = owned_education(container, &key, id).await?;
+
+    Ok(Json(education))
+}
+
+#[openapi(tag = "Education")]
+#[post("/education", data = "<input>")]
+pub async fn post(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    mut input: Json<EducationInput>,
+) -> Result<Json<String>, BadRequest<Json<MessageResponse>>> {
+    let education_repo = container
+        .get::<Arc<dyn EducationRepository + Send + Sync>>()
+        .ok_or_else(|| {
+            BadRequest(Json(MessageResponse {
+                message: "Service not found".to_string(),
+            }))
+        })?;
+
+    let claims = &key.0; // Access the Claims struct
+    let id = &claims.sub;
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(BadRequest(Json(MessageResponse {
+            message: "Invalid user id format".to_string(),
+        })));
+    };
+
+    // can set with a single error like this.
+    match education_repo.find(1000, 1, oid).await {
+        Ok(resp) => {
+            if let Some(last) = resp.first() {
+                input.order = last.order + 1;
+            }
+
+            match education_repo.insert(input, oid).await {
+                Ok(resp) => Ok(Json(resp)),
+                Err(_error) => Err(BadRequest(Json(MessageResponse {
+                    message: "Invalid input".to_string(),
+                }))),
+            }
+        }
+        Err(error) => Err(BadRequest(Json(MessageResponse {
+            message: error.to_string(),
+        }))),
+    }
+}
+
+#[openapi(tag = "Education")]
+#[patch("/education/<id>", data = "<input>")]
+pub async fn patch_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+    input: Json<EducationInput>,
+) -> Result<Json<Education>, MyError> {
+    let (oid, _) = owned_education(container, &key, id).await?;
+
+    let education_repo = container
+        .get::<Arc<dyn EducationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match education_repo.update_by_id(oid, input).await {
+        Ok(resp) => match resp {
+            Some(resp) => Ok(Json(resp)),
+            None => Err(MyError::build(
+                400,
+                Some(format!("Education not found with id {}", &id)),
+            )),
+        },
+        Err(_error) => Err(MyError::build(
+            400,
+            Some(format!("Education not found with id {}", &id)),
+        )),
+    }
+}
+
+#[openapi(tag = "Education")]
+#[patch("/education", data = "<input>")]
+pub async fn patch_many(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    input: Json<Vec<EducationsInput>>,
+) -> Result<Json<Vec<Education>>, MyError> {
+    let Ok(customer_oid) = ObjectId::parse_str(&key.0.sub) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let education_repo = container
+        .get::<Arc<dyn EducationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    // Educations of other customers are skipped.
+    match education_repo.update_many(customer_oid, input).await {
+        Ok(resp) => match resp {
+            Some(resp) => Ok(Json(resp)),
+            None => Err(MyError::build(400, Some("Failed to update".to_string()))),
+        },
+        Err(_error) => Err(MyError::build(400, Some("Failed to update".to_string()))),
+    }
+}
+
+#[openapi(tag = "Education")]
+#[delete("/education/<id>")]
+pub async fn delete_by_id(
+    container: &State<crate::Container>,
+    id: &str,
+    key: ApiKey,
+) -> Result<Json<Education>, MyError> {
+    let (oid, _) = owned_education(container, &key, id).await?;
+
+    let education_repo = container
+        .get::<Arc<dyn EducationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match education_repo.delete_by_id(oid).await {
+        Ok(resp) => match resp {
+            Some(resp) => Ok(Json(resp)),
+            None => Err(MyError::build(
+                400,
+                Some(format!("Education not found with _id {}", &id)),
+            )),
+        },
+        Err(_error) => Err(MyError::build(
+            400,
+            Some(format!("Education not found with _id {}", &id)),
+        )),
+    }
+}
+
+// Only the owner of the portfolio may see or change its entries by id.
+async fn owned_education(
+    container: &State<crate::Container>,
+    key: &ApiKey,
+    id: &str,
+) -> Result<(ObjectId, Education), MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid id format.".to_string())));
+    };
+
+    let education_repo = container
+        .get::<Arc<dyn EducationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match education_repo.find_by_id(oid).await {
+        Ok(Some(education)) if education.customer_id == key.0.sub => Ok((oid, education)),
+        Ok(_) | Err(_) => Err(MyError::build(
+            404,
+            Some(format!("Education not found with _id {}", &id)),
+        )),
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use super::client_customer;
+use futures::stream::{self, BoxStream, StreamExt};
+use rocket::{
+    response::stream::{Event, EventStream},
+    tokio::sync::broadcast::error::RecvError,
+    Shutdown, State,
+};
+use rocket_okapi::openapi;
+
+use crate::{
+    db::events::ContentEvents,
+    errors::response::MyError,
+    models::event::{event_matches, validate_event_patterns, ContentEvent},
+    request_guards::basic::{ApiKey, ClientApiKey},
+    utils::util::parse_list,
+};
+
+type ContentEventStream = EventStream<BoxStream<'static, Event>>;
+
+/// stream the changes of the content as Server-Sent Events, one JSON `ContentEvent` per message.
+/// <br />Signed in with a JWT, the changes of the customer come with the changed document in
+/// `data`. With an `X-API-KEY` header, or `api_key` for `EventSource` which cannot send
+/// headers, `data` is left out and the content is fetched again through the public endpoints;
+/// only the changes of published content are streamed then, not drafts or pending testimonials.
+/// `events` is a comma separated list of patterns like `project.*` or `*.deleted`, every event
+/// by default. A `lagged` event tells that changes were missed and everything should be fetched
+/// again.
+#[openapi(tag = "Events")]
+#[get("/events?<events>&<api_key>")]
+pub async fn get(
+    container: &State<crate::Container>,
+    key: Option<ApiKey>,
+    client_key: Option<ClientApiKey>,
+    events: Option<&str>,
+    api_key: Option<String>,
+    shutdown: Shutdown,
+) -> Result<ContentEventStream, MyError> {
+    let patterns = events.map(parse_list).unwrap_or_default();
+    validate_event_patterns(&patterns).map_err(|error| MyError::build(400, Some(error)))?;
+
+    let (customer_id, with_data) = match (key, client_key.or(api_key.map(ClientApiKey))) {
+        (Some(key), _) => (key.0.sub, true),
+        (None, Some(client_key)) => (client_customer(container, &client_key).await?.id, false),
+        (None, None) => {
+            return Err(MyError::build(
+                401,
+                Some("A Bearer JWT or an api key is required".to_string()),
+            ))
+        }
+    };
+
+    let receiver = container
+        .get::<Arc<ContentEvents>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?
+        .subscribe();
+
+    let events = stream::unfold((receiver, shutdown), move |(mut receiver, mut shutdown)| {
+        let customer_id = customer_id.clone();
+        let patterns = patterns.clone();
+        async move {
+            loop {
+                let received = rocket::tokio::select! {
+                    received = receiver.recv() => received,
+                    _ = &mut shutdown => return None,
+                };
+                match received {
+                    Ok(event)
+                        if event.customer_id == customer_id && (with_data || event.public) =>
+                    {
+                        if !patterns.is_empty()
+                            && !patterns
+                                .iter()
+                                .any(|pattern| event_matches(pattern, &event.event))
+                        {
+                            continue;
+                        }
+                        return Some((message(event, with_data), (receiver, shutdown)));
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(missed)) => {
+                        let lagged = Event::data(missed.to_string()).event("lagged");
+                        return Some((lagged, (receiver, shutdown)));
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }
+    });
+
+    Ok(EventStream::from(events.boxed()))
+}
+
+fn message(mut event: ContentEvent, with_data: bool) -> Event {
+    if !with_data {
+        event.data = None;
+    }
+    Event::json(&event)
+}
@@ -4,24 +4,42 @@ use mongodb::bson::{doc, oid::ObjectId};
 use rocket::{response::status::BadRequest, serde::json::Json, State};
 use rocket_okapi::openapi;
 
-use super::traits::{CustomerRepository, ExperienceRepository};
+use super::{
+    check_project_ids, check_skill_ids,
+    traits::{CustomerRepository, ExperienceRepository},
+};
 use crate::{
     errors::response::MyError,
     models::{
-        experience::{Experience, ExperienceInput, ExperiencesInput},
+        experience::{Experience, ExperienceInput, ExperiencesInput, EXPERIENCE_FIELDS},
         response::MessageResponse,
+        sparse::Sparse,
+    },
+    request_guards::{
+        basic::{ApiKey, ClientApiKey},
+        locale::AcceptLanguage,
+    },
+    responders::cache::Cached,
+    utils::{
+        i18n::{
+            requested_locales, select_translations, validate_translations, LocaleChain,
+            Translatable,
+        },
+        util::parse_fields,
     },
-    request_guards::basic::{ApiKey, ClientApiKey},
 };
 
+/// get the experiences of the signed in customer.
+/// <br />Use `fields` with a comma separated list, e.g. `name,link,order`, to only return those fields.
 #[openapi(tag = "Experience")]
-#[get("/experience?<limit>&<page>")]
+#[get("/experience?<limit>&<page>&<fields>")]
 pub async fn get_all(
     container: &State<crate::Container>,
     key: ApiKey,
     limit: Option<i64>,
     page: Option<i64>,
-) -> Result<Json<Vec<Experience>>, MyError> {
+    fields: Option<String>,
+) -> Result<Json<Vec<Sparse<Experience>>>, MyError> {
     // Error handling
     // This is also valid when strict checking is necessary.
     // if limit < 0 {
@@ -46,25 +64,46 @@ pub async fn get_all(
     // Setting default values
     let limit: i64 = limit.unwrap_or(100);
     let page: i64 = page.unwrap_or(1);
+    let fields = fields
+        .map(|fields| parse_fields(&fields, &EXPERIENCE_FIELDS))
+        .transpose()
+        .map_err(|error| MyError::build(400, Some(error)))?;
 
     let experience_repo = container
         .get::<Arc<dyn ExperienceRepository + Send + Sync>>()
         .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
 
-    match experience_repo.find(limit, page, oid).await {
+    let resp: mongodb::error::Result<Vec<Sparse<Experience>>> = match fields {
+        Some(fields) => experience_repo
+            .find_fields(limit, page, oid, fields)
+            .await
+            .map(|resp| resp.into_iter().map(Sparse::Partial).collect()),
+        None => experience_repo
+            .find(limit, page, oid)
+            .await
+            .map(|resp| resp.into_iter().map(Sparse::Full).collect()),
+    };
+
+    match resp {
         Ok(resp) => Ok(Json(resp)),
         Err(error) => Err(MyError::build(400, Some(error.to_string()))),
     }
 }
 
+/// get the experiences of the portfolio owning the api key.
+/// <br />Use `fields` with a comma separated list, e.g. `name,link,order`, to only return those fields.
+/// <br />`position` and `description` are translated to `locale` or the `Accept-Language` header.
 #[openapi(tag = "Experience")]
-#[get("/experiences?<limit>&<page>")]
+#[get("/experiences?<limit>&<page>&<fields>&<locale>")]
 pub async fn get(
     container: &State<crate::Container>,
     client_key: ClientApiKey,
+    accept_language: AcceptLanguage,
     limit: Option<i64>,
     page: Option<i64>,
-) -> Result<Json<Vec<Experience>>, MyError> {
+    fields: Option<String>,
+    locale: Option<String>,
+) -> Result<Cached<Vec<Sparse<Experience>>>, MyError> {
     // Error handling
     // This is also valid when strict checking is necessary.
     // if limit < 0 {
@@ -81,6 +120,11 @@ pub async fn get(
     // Setting default values
     let limit: i64 = limit.unwrap_or(100);
     let page: i64 = page.unwrap_or(1);
+    let mut fields = fields
+        .map(|fields| parse_fields(&fields, &EXPERIENCE_FIELDS))
+        .transpose()
+        .map_err(|error| MyError::build(400, Some(error)))?;
+    let drop_translations = select_translations(&mut fields);
 
     let experience_repo = container
         .get::<Arc<dyn ExperienceRepository + Send + Sync>>()
@@ -92,6 +136,10 @@ pub async fn get(
 
     match customer_repo.find_customer_by_api_key(&client_key.0).await {
         Ok(Some(customer_doc)) => {
+            let chain = LocaleChain::new(
+                &requested_locales(locale.as_deref(), &accept_language.0),
+                &customer_doc,
+            );
             let Ok(oid) = ObjectId::parse_str(customer_doc.id) else {
                 return Err(MyError::build(
                     400,
@@ -99,8 +147,22 @@ pub async fn get(
                 ));
             };
 
-            match experience_repo.find(limit, page, oid).await {
-                Ok(resp) => Ok(Json(resp)),
+            let resp: mongodb::error::Result<Vec<Sparse<Experience>>> = match fields {
+                Some(fields) => experience_repo
+                    .find_fields(limit, page, oid, fields)
+                    .await
+                    .map(|resp| resp.into_iter().map(Sparse::Partial).collect()),
+                None => experience_repo
+                    .find(limit, page, oid)
+                    .await
+                    .map(|resp| resp.into_iter().map(Sparse::Full).collect()),
+            };
+
+            match resp {
+                Ok(mut resp) => {
+                    chain.localize_sparse(&mut resp, drop_translations);
+                    Ok(Cached(Json(resp)))
+                }
                 Err(error) => Err(MyError::build(400, Some(error.to_string()))),
             }
         }
@@ -166,9 +228,18 @@ pub async fn post(
         })));
     };
 
+    if let Err(message) =
+        check_references(container, oid, &input.skill_ids, &input.project_ids).await
+    {
+        return Err(BadRequest(Json(MessageResponse { message })));
+    }
+    if let Err(message) = validate_translations(input.translations.as_ref(), Experience::FIELDS) {
+        return Err(BadRequest(Json(MessageResponse { message })));
+    }
+
     match experience_repo.find(1000, 1, oid).await {
         Ok(resp) => {
-            if resp.len() > 0 {
+            if !resp.is_empty() {
                 input.order = resp[0].order + 1;
             }
 
@@ -189,13 +260,30 @@ pub async fn post(
 #[patch("/experience/<id>", data = "<input>")]
 pub async fn patch_by_id(
     container: &State<crate::Container>,
-    _key: ApiKey,
+    key: ApiKey,
     id: &str,
     input: Json<ExperienceInput>,
 ) -> Result<Json<Experience>, MyError> {
     let Ok(oid) = ObjectId::parse_str(id) else {
         return Err(MyError::build(400, Some("Invalid id format.".to_string())));
     };
+    let Ok(customer_oid) = ObjectId::parse_str(&key.0.sub) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    check_references(
+        container,
+        customer_oid,
+        &input.skill_ids,
+        &input.project_ids,
+    )
+    .await
+    .map_err(|message| MyError::build(400, Some(message)))?;
+    validate_translations(input.translations.as_ref(), Experience::FIELDS)
+        .map_err(|message| MyError::build(400, Some(message)))?;
 
     let experience_repo = container
         .get::<Arc<dyn ExperienceRepository + Send + Sync>>()
@@ -220,9 +308,24 @@ pub async fn patch_by_id(
 #[patch("/experience", data = "<input>")]
 pub async fn patch_many(
     container: &State<crate::Container>,
-    _key: ApiKey,
+    key: ApiKey,
     input: Json<Vec<ExperiencesInput>>,
 ) -> Result<Json<Vec<Experience>>, MyError> {
+    let Ok(customer_oid) = ObjectId::parse_str(&key.0.sub) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    for item in input.iter() {
+        check_references(container, customer_oid, &item.skill_ids, &item.project_ids)
+            .await
+            .map_err(|message| MyError::build(400, Some(message)))?;
+        validate_translations(item.translations.as_ref(), Experience::FIELDS)
+            .map_err(|message| MyError::build(400, Some(message)))?;
+    }
+
     let experience_repo = container
         .get::<Arc<dyn ExperienceRepository + Send + Sync>>()
         .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
@@ -265,3 +368,14 @@ pub async fn delete_by_id(
         )),
     }
 }
+
+// The skills and projects of an experience have to be the customer's.
+async fn check_references(
+    container: &State<crate::Container>,
+    oid: ObjectId,
+    skill_ids: &Option<Vec<String>>,
+    project_ids: &Option<Vec<String>>,
+) -> Result<(), String> {
+    check_skill_ids(container, oid, skill_ids.as_deref()).await?;
+    check_project_ids(container, oid, project_ids.as_deref()).await
+}
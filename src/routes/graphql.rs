@@ -0,0 +1,45 @@
+use std::env;
+
+use async_graphql::http::GraphiQLSource;
+use rocket::{response::content::RawHtml, serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use crate::{
+    graphql::PortfolioSchema,
+    models::graphql::{GraphQLRequest, GraphQLResponse},
+    request_guards::basic::{ApiKey, ClientApiKey},
+};
+
+/// Execute a GraphQL query or mutation.
+/// <br />Queries accept a Bearer JWT or an `X-API-KEY` header, mutations require the JWT.
+#[openapi(tag = "GraphQL")]
+#[post("/graphql", data = "<request>")]
+pub async fn post(
+    schema: &State<PortfolioSchema>,
+    key: Option<ApiKey>,
+    client_key: Option<ClientApiKey>,
+    request: Json<GraphQLRequest>,
+) -> Json<GraphQLResponse> {
+    let mut request = async_graphql::Request::from(request.into_inner());
+    if let Some(key) = key {
+        request = request.data(key);
+    }
+    if let Some(client_key) = client_key {
+        request = request.data(client_key);
+    }
+
+    Json(GraphQLResponse(schema.execute(request).await))
+}
+
+/// GraphiQL playground, only served with `GRAPHIQL_ENABLED=true`.
+#[openapi(tag = "GraphQL")]
+#[get("/graphql")]
+pub fn graphiql() -> Option<RawHtml<String>> {
+    if !env::var("GRAPHIQL_ENABLED").is_ok_and(|value| value == "true") {
+        return None;
+    }
+
+    Some(RawHtml(
+        GraphiQLSource::build().endpoint("/api/graphql").finish(),
+    ))
+}
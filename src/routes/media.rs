@@ -0,0 +1,276 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use mongodb::bson::oid::ObjectId;
+use rocket::{form::Form, http::ContentType, serde::json::Json, tokio::io::AsyncReadExt, State};
+use rocket_okapi::openapi;
+
+use super::traits::{MediaRepository, MediaStore};
+use crate::{
+    errors::response::MyError,
+    models::media::{Media, MediaInput, MediaUpload, MediaVariant},
+    request_guards::basic::ApiKey,
+    utils::image::{process_image, variant_widths},
+};
+
+// Documents accepted next to any `image/*` type.
+const DOCUMENT_TYPES: [&str; 2] = [
+    "application/pdf",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+];
+
+#[openapi(tag = "Media")]
+#[get("/media?<limit>&<page>")]
+pub async fn get_all(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    limit: Option<i64>,
+    page: Option<i64>,
+) -> Result<Json<Vec<Media>>, MyError> {
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let claims = &key.0; // Access the Claims struct
+    let id = &claims.sub;
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let media_repo = container
+        .get::<Arc<dyn MediaRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match media_repo.find(limit, page, oid).await {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Media")]
+#[get("/media/<id>")]
+pub async fn get_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+) -> Result<Json<Media>, MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid _id format.".to_string())));
+    };
+
+    let media_repo = container
+        .get::<Arc<dyn MediaRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match media_repo.find_by_id(oid).await {
+        Ok(Some(resp)) if resp.customer_id == key.0.sub => Ok(Json(resp)),
+        Ok(_) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Media not found with _id {}", &id)),
+        )),
+    }
+}
+
+/// upload an image or a document as `multipart/form-data` with a `file` field
+#[openapi(tag = "Media")]
+#[post("/media", data = "<upload>")]
+pub async fn post(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    upload: Form<MediaUpload<'_>>,
+) -> Result<Json<Media>, MyError> {
+    let claims = &key.0; // Access the Claims struct
+    let id = &claims.sub;
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let media_repo = container
+        .get::<Arc<dyn MediaRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let media_store = container
+        .get::<Arc<dyn MediaStore + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let mut bytes: Vec<u8> = vec![];
+    let mut file = upload
+        .file
+        .open()
+        .await
+        .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+    file.read_to_end(&mut bytes)
+        .await
+        .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+
+    // Trust the content, not the name or the Content-Type sent by the client.
+    let Some(kind) = infer::get(&bytes).filter(|kind| {
+        kind.mime_type().starts_with("image/") || DOCUMENT_TYPES.contains(&kind.mime_type())
+    }) else {
+        return Err(MyError::build(
+            400,
+            Some("Unsupported media type.".to_string()),
+        ));
+    };
+
+    let (mut width, mut height) = match imagesize::blob_size(&bytes) {
+        Ok(size) => (Some(size.width as i32), Some(size.height as i32)),
+        Err(_) => (None, None),
+    };
+
+    let media_oid = ObjectId::new();
+    let mut variants = vec![];
+    let mut variant_files = vec![];
+    let mut blurhash = None;
+
+    if kind.mime_type().starts_with("image/") {
+        let widths = variant_widths();
+        let processed = rocket::tokio::task::spawn_blocking(move || process_image(&bytes, &widths))
+            .await
+            .map_err(|error| MyError::build(500, Some(error.to_string())))?;
+
+        // The metadata of an image we cannot decode could not be stripped.
+        let Some(processed) = processed else {
+            return Err(MyError::build(
+                400,
+                Some("Unsupported or corrupt image.".to_string()),
+            ));
+        };
+
+        bytes = processed.original;
+        width = Some(processed.width as i32);
+        height = Some(processed.height as i32);
+        blurhash = processed.blurhash;
+
+        for variant in processed.variants {
+            let key = format!("{oid}/{media_oid}-{}w.{}", variant.width, variant.extension);
+            variants.push(MediaVariant {
+                width: variant.width as i32,
+                height: variant.height as i32,
+                key: key.clone(),
+            });
+            variant_files.push((key, variant.bytes));
+        }
+    }
+
+    let input = MediaInput {
+        id: media_oid,
+        file_name: upload
+            .file
+            .name()
+            .map(|name| format!("{name}.{}", kind.extension()))
+            .unwrap_or_else(|| format!("{media_oid}.{}", kind.extension())),
+        key: format!("{oid}/{media_oid}.{}", kind.extension()),
+        mime_type: kind.mime_type().to_string(),
+        size: bytes.len() as i64,
+        width,
+        height,
+        variants,
+        blurhash,
+    };
+
+    let mut keys = vec![input.key.clone()];
+    let mut stored = media_store.put(&input.key, bytes).await;
+    for (key, bytes) in variant_files {
+        if stored.is_err() {
+            break;
+        }
+        keys.push(key.clone());
+        stored = media_store.put(&key, bytes).await;
+    }
+
+    let inserted = match stored {
+        Ok(()) => media_repo
+            .insert(input, oid)
+            .await
+            .map_err(|error| MyError::build(400, Some(error.to_string()))),
+        Err(error) => Err(MyError::build(500, Some(error.to_string()))),
+    };
+
+    if let Err(error) = inserted {
+        // Do not leave orphan files behind.
+        for key in keys {
+            let _ = media_store.delete(&key).await;
+        }
+        return Err(error);
+    }
+
+    match media_repo.find_by_id(media_oid).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Media not found with _id {media_oid}")),
+        )),
+    }
+}
+
+#[openapi(tag = "Media")]
+#[delete("/media/<id>")]
+pub async fn delete_by_id(
+    container: &State<crate::Container>,
+    id: &str,
+    key: ApiKey,
+) -> Result<Json<Media>, MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid id format.".to_string())));
+    };
+
+    let media_repo = container
+        .get::<Arc<dyn MediaRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let media_store = container
+        .get::<Arc<dyn MediaStore + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    // Files are shared publicly, only their owner may remove them.
+    match media_repo.find_by_id(oid).await {
+        Ok(Some(media)) if media.customer_id == key.0.sub => {}
+        Ok(_) | Err(_) => {
+            return Err(MyError::build(
+                400,
+                Some(format!("Media not found with _id {}", &id)),
+            ))
+        }
+    }
+
+    match media_repo.delete_by_id(oid).await {
+        Ok(Some(resp)) => {
+            let keys = std::iter::once(&resp.key).chain(resp.variants.iter().map(|v| &v.key));
+            for key in keys {
+                if let Err(error) = media_store.delete(key).await {
+                    return Err(MyError::build(500, Some(error.to_string())));
+                }
+            }
+            Ok(Json(resp))
+        }
+        Ok(None) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Media not found with _id {}", &id)),
+        )),
+    }
+}
+
+/// serve an uploaded file, mounted under `/media`
+#[get("/<key..>")]
+pub async fn serve(
+    container: &State<crate::Container>,
+    key: PathBuf,
+) -> Option<(ContentType, Vec<u8>)> {
+    let media_store = container.get::<Arc<dyn MediaStore + Send + Sync>>()?;
+
+    let content_type = key
+        .extension()
+        .and_then(|extension| ContentType::from_extension(&extension.to_string_lossy()))
+        .unwrap_or(ContentType::Binary);
+
+    let bytes = media_store.get(key.to_str()?).await.ok()??;
+
+    Some((content_type, bytes))
+}
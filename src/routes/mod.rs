@@ -1,15 +1,43 @@
-use rocket::serde::json::Json;
+use std::sync::Arc;
+
+use mongodb::bson::oid::ObjectId;
+use rocket::{serde::json::Json, State};
 use rocket_okapi::openapi;
 
-use crate::models::response::MessageResponse;
+use crate::{
+    errors::response::MyError,
+    models::{customer::Customer, response::MessageResponse},
+    request_guards::basic::ClientApiKey,
+    routes::traits::{CustomerRepository, ProjectRepository, SkillRepository},
+};
 
-pub mod customer;
+pub mod archive;
+pub mod article;
 pub mod auth;
+pub mod cache;
+pub mod certification;
+pub mod collection;
+pub mod contact;
+pub mod customer;
+pub mod cv;
+pub mod education;
+pub mod events;
 pub mod experience;
+pub mod graphql;
+pub mod media;
+pub mod portfolio;
 pub mod project;
+pub mod resume;
+pub mod search;
+pub mod seo;
+pub mod service;
+pub mod site;
 pub mod skill;
 pub mod social;
+pub mod testimonial;
 pub mod traits;
+pub mod translation;
+pub mod webhook;
 
 /// This is a description. <br />You can do simple html <br /> like <b>this<b/>
 #[openapi(tag = "Hello World")]
@@ -24,3 +52,79 @@ pub fn index() -> Json<MessageResponse> {
 pub fn all_options_handler() -> &'static str {
     ""
 }
+
+// Customer id of the portfolio owning the api key.
+pub async fn client_oid(
+    container: &State<crate::Container>,
+    client_key: &ClientApiKey,
+) -> Result<ObjectId, MyError> {
+    let customer_doc = client_customer(container, client_key).await?;
+
+    ObjectId::parse_str(customer_doc.id)
+        .map_err(|_| MyError::build(400, Some("Invalid user id format.".to_string())))
+}
+
+// Customer owning the api key.
+pub async fn client_customer(
+    container: &State<crate::Container>,
+    client_key: &ClientApiKey,
+) -> Result<Customer, MyError> {
+    let customer_repo = container
+        .get::<Arc<dyn CustomerRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match customer_repo.find_customer_by_api_key(&client_key.0).await {
+        Ok(Some(customer_doc)) => Ok(customer_doc),
+        // Either not found or error
+        Ok(None) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Customer not found with api key {}", client_key.0)),
+        )),
+    }
+}
+
+// Every id has to be a skill of the customer, the error is the message to send back.
+pub async fn check_skill_ids(
+    container: &State<crate::Container>,
+    oid: ObjectId,
+    ids: Option<&[String]>,
+) -> Result<(), String> {
+    let skill_repo = container
+        .get::<Arc<dyn SkillRepository + Send + Sync>>()
+        .ok_or_else(|| "Service not found".to_string())?;
+
+    for id in ids.unwrap_or_default() {
+        let skill = match ObjectId::parse_str(id) {
+            Ok(skill_oid) => skill_repo.find_by_id(skill_oid).await.ok().flatten(),
+            Err(_) => None,
+        };
+        if skill.is_none_or(|skill| skill.customer_id != oid.to_string()) {
+            return Err(format!("Skill not found with _id {id}"));
+        }
+    }
+
+    Ok(())
+}
+
+// Every id has to be a project of the customer, the error is the message to send back.
+pub async fn check_project_ids(
+    container: &State<crate::Container>,
+    oid: ObjectId,
+    ids: Option<&[String]>,
+) -> Result<(), String> {
+    let project_repo = container
+        .get::<Arc<dyn ProjectRepository + Send + Sync>>()
+        .ok_or_else(|| "Service not found".to_string())?;
+
+    for id in ids.unwrap_or_default() {
+        let project = match ObjectId::parse_str(id) {
+            Ok(project_oid) => project_repo.find_by_id(project_oid).await.ok().flatten(),
+            Err(_) => None,
+        };
+        if project.is_none_or(|project| project.customer_id != oid.to_string()) {
+            return Err(format!("Project not found with _id {id}"));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,260 @@
+use std::sync::Arc;
+
+use mongodb::bson::oid::ObjectId;
+use rocket::{serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use super::traits::{
+    ArticleRepository, CertificationRepository, CustomerRepository, EducationRepository,
+    ExperienceRepository, ProjectRepository, ServiceRepository, SkillRepository, SocialRepository,
+    TestimonialRepository,
+};
+use crate::{
+    errors::response::MyError,
+    models::{portfolio::Portfolio, project::ProjectFilter, testimonial::TestimonialStatus},
+    request_guards::{basic::ClientApiKey, locale::AcceptLanguage},
+    responders::cache::Cached,
+    utils::i18n::{requested_locales, LocaleChain},
+};
+
+// Upper bound for every section, same as the one used when computing the next `order`.
+const SECTION_LIMIT: i64 = 1000;
+
+const SECTIONS: [&str; 10] = [
+    "profile",
+    "projects",
+    "skills",
+    "socials",
+    "experiences",
+    "articles",
+    "educations",
+    "certifications",
+    "testimonials",
+    "services",
+];
+
+/// get the profile and every section of a portfolio in a single request.
+/// <br />Use `include` with a comma separated list of `profile`, `projects`, `skills`,
+/// `socials`, `experiences`, `articles`, `educations`, `certifications`, `testimonials` and
+/// `services` to only return some of them.
+/// <br />The profile, projects and experiences are translated to `locale` or the
+/// `Accept-Language` header.
+#[openapi(tag = "Portfolio")]
+#[get("/portfolio?<include>&<locale>")]
+pub async fn get(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    accept_language: AcceptLanguage,
+    include: Option<String>,
+    locale: Option<String>,
+) -> Result<Cached<Portfolio>, MyError> {
+    let include: Vec<&str> = match &include {
+        Some(include) => include
+            .split(',')
+            .map(|section| section.trim())
+            .filter(|section| !section.is_empty())
+            .collect(),
+        None => SECTIONS.to_vec(),
+    };
+
+    if let Some(section) = include.iter().find(|section| !SECTIONS.contains(section)) {
+        return Err(MyError::build(
+            400,
+            Some(format!("Unknown portfolio section {section}")),
+        ));
+    }
+
+    let customer_repo = container
+        .get::<Arc<dyn CustomerRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let project_repo = container
+        .get::<Arc<dyn ProjectRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let skill_repo = container
+        .get::<Arc<dyn SkillRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let social_repo = container
+        .get::<Arc<dyn SocialRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let experience_repo = container
+        .get::<Arc<dyn ExperienceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let article_repo = container
+        .get::<Arc<dyn ArticleRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let education_repo = container
+        .get::<Arc<dyn EducationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let certification_repo = container
+        .get::<Arc<dyn CertificationRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let testimonial_repo = container
+        .get::<Arc<dyn TestimonialRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let service_repo = container
+        .get::<Arc<dyn ServiceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    // The customer is resolved once and shared by every section.
+    let customer_doc = match customer_repo.find_customer_by_api_key(&client_key.0).await {
+        Ok(Some(customer_doc)) => customer_doc,
+        Ok(None) | Err(_) => {
+            return Err(MyError::build(
+                400,
+                Some(format!("Customer not found with api key {}", client_key.0)),
+            ))
+        }
+    };
+
+    let chain = LocaleChain::new(
+        &requested_locales(locale.as_deref(), &accept_language.0),
+        &customer_doc,
+    );
+
+    let Ok(oid) = ObjectId::parse_str(&customer_doc.id) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let projects = async {
+        if include.contains(&"projects") {
+            project_repo
+                .find(SECTION_LIMIT, 1, oid, ProjectFilter::default())
+                .await
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    };
+    let skills = async {
+        if include.contains(&"skills") {
+            skill_repo.find(SECTION_LIMIT, 1, oid).await.map(Some)
+        } else {
+            Ok(None)
+        }
+    };
+    let socials = async {
+        if include.contains(&"socials") {
+            social_repo.find(SECTION_LIMIT, 1, oid).await.map(Some)
+        } else {
+            Ok(None)
+        }
+    };
+    let experiences = async {
+        if include.contains(&"experiences") {
+            experience_repo.find(SECTION_LIMIT, 1, oid).await.map(Some)
+        } else {
+            Ok(None)
+        }
+    };
+
+    let articles = async {
+        if include.contains(&"articles") {
+            article_repo
+                .find(SECTION_LIMIT, 1, oid, true)
+                .await
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    };
+
+    let educations = async {
+        if include.contains(&"educations") {
+            education_repo.find(SECTION_LIMIT, 1, oid).await.map(Some)
+        } else {
+            Ok(None)
+        }
+    };
+    let certifications = async {
+        if include.contains(&"certifications") {
+            certification_repo
+                .find(SECTION_LIMIT, 1, oid, false)
+                .await
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    };
+
+    let testimonials = async {
+        if include.contains(&"testimonials") {
+            testimonial_repo
+                .find(SECTION_LIMIT, 1, oid, Some(TestimonialStatus::Approved))
+                .await
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    };
+
+    let services = async {
+        if include.contains(&"services") {
+            service_repo.find(SECTION_LIMIT, 1, oid).await.map(Some)
+        } else {
+            Ok(None)
+        }
+    };
+
+    match futures::try_join!(
+        projects,
+        skills,
+        socials,
+        experiences,
+        articles,
+        educations,
+        certifications,
+        testimonials,
+        services
+    ) {
+        Ok((
+            mut projects,
+            skills,
+            socials,
+            mut experiences,
+            articles,
+            educations,
+            certifications,
+            testimonials,
+            services,
+        )) => {
+            let mut profile = include.contains(&"profile").then_some(customer_doc);
+            profile
+                .iter_mut()
+                .for_each(|profile| chain.localize(profile));
+            projects
+                .iter_mut()
+                .flatten()
+                .for_each(|project| chain.localize(project));
+            experiences
+                .iter_mut()
+                .flatten()
+                .for_each(|experience| chain.localize(experience));
+
+            Ok(Cached(Json(Portfolio {
+                profile,
+                projects,
+                skills,
+                socials,
+                experiences,
+                articles,
+                educations,
+                certifications,
+                testimonials,
+                services,
+            })))
+        }
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
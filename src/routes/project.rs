@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use super::traits::{CustomerRepository, ProjectRepository};
+use super::{
+    check_skill_ids,
+    traits::{CustomerRepository, ProjectRepository},
+};
 use mongodb::bson::{doc, oid::ObjectId};
 use rocket::{response::status::BadRequest, serde::json::Json, State};
 use rocket_okapi::openapi;
@@ -8,20 +11,40 @@ use rocket_okapi::openapi;
 use crate::{
     errors::response::MyError,
     models::{
-        project::{Project, ProjectInput, ProjectsInput},
+        project::{Project, ProjectFilter, ProjectInput, ProjectsInput, PROJECT_FIELDS},
         response::MessageResponse,
+        sparse::Sparse,
+    },
+    request_guards::{
+        basic::{ApiKey, ClientApiKey},
+        locale::AcceptLanguage,
+    },
+    responders::cache::Cached,
+    utils::{
+        i18n::{
+            requested_locales, select_translations, validate_translations, LocaleChain,
+            Translatable,
+        },
+        seo::validate_seo,
+        util::{parse_fields, parse_list},
     },
-    request_guards::basic::{ApiKey, ClientApiKey},
 };
 
+/// get the projects of the signed in customer.
+/// <br />Use `fields` with a comma separated list, e.g. `name,link,order`, to only return those fields.
+/// <br />Use `featured=true` to only return featured projects and `stack` with a comma separated list,
+/// e.g. `rust,react`, to only return the projects using all of them.
 #[openapi(tag = "Project")]
-#[get("/project?<limit>&<page>")]
+#[get("/project?<limit>&<page>&<fields>&<featured>&<stack>")]
 pub async fn get_all(
     container: &State<crate::Container>,
     key: ApiKey,
     limit: Option<i64>,
     page: Option<i64>,
-) -> Result<Json<Vec<Project>>, MyError> {
+    fields: Option<String>,
+    featured: Option<bool>,
+    stack: Option<String>,
+) -> Result<Json<Vec<Sparse<Project>>>, MyError> {
     // Error handling
     // This is also valid when strict checking is necessary.
     // if limit < 0 {
@@ -38,6 +61,15 @@ pub async fn get_all(
     // Setting default values
     let limit: i64 = limit.unwrap_or(100);
     let page: i64 = page.unwrap_or(1);
+    let fields = fields
+        .map(|fields| parse_fields(&fields, &PROJECT_FIELDS))
+        .transpose()
+        .map_err(|error| MyError::build(400, Some(error)))?;
+    let filter = ProjectFilter {
+        featured,
+        stack: stack.as_deref().map(parse_list).unwrap_or_default(),
+        ..Default::default()
+    };
 
     let claims = &key.0; // Access the Claims struct
     let id = &claims.sub;
@@ -52,20 +84,42 @@ pub async fn get_all(
         .get::<Arc<dyn ProjectRepository + Send + Sync>>()
         .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
 
-    match project_repo.find(limit, page, oid).await {
+    let resp: mongodb::error::Result<Vec<Sparse<Project>>> = match fields {
+        Some(fields) => project_repo
+            .find_fields(limit, page, oid, fields, filter)
+            .await
+            .map(|resp| resp.into_iter().map(Sparse::Partial).collect()),
+        None => project_repo
+            .find(limit, page, oid, filter)
+            .await
+            .map(|resp| resp.into_iter().map(Sparse::Full).collect()),
+    };
+
+    match resp {
         Ok(resp) => Ok(Json(resp)),
         Err(error) => Err(MyError::build(400, Some(error.to_string()))),
     }
 }
 
+/// get the projects of the portfolio owning the api key.
+/// <br />Use `fields` with a comma separated list, e.g. `name,link,order`, to only return those fields.
+/// <br />Use `featured=true` to only return featured projects and `stack` with a comma separated list,
+/// e.g. `rust,react`, to only return the projects using all of them.
+/// <br />`name` and `description` are translated to `locale` or the `Accept-Language` header.
 #[openapi(tag = "Project")]
-#[get("/projects?<limit>&<page>")]
+#[get("/projects?<limit>&<page>&<fields>&<featured>&<stack>&<locale>")]
+#[allow(clippy::too_many_arguments)]
 pub async fn get(
     container: &State<crate::Container>,
     client_key: ClientApiKey,
+    accept_language: AcceptLanguage,
     limit: Option<i64>,
     page: Option<i64>,
-) -> Result<Json<Vec<Project>>, MyError> {
+    fields: Option<String>,
+    featured: Option<bool>,
+    stack: Option<String>,
+    locale: Option<String>,
+) -> Result<Cached<Vec<Sparse<Project>>>, MyError> {
     // Error handling
     // This is also valid when strict checking is necessary.
     // if limit < 0 {
@@ -82,6 +136,16 @@ pub async fn get(
     // Setting default values
     let limit: i64 = limit.unwrap_or(100);
     let page: i64 = page.unwrap_or(1);
+    let mut fields = fields
+        .map(|fields| parse_fields(&fields, &PROJECT_FIELDS))
+        .transpose()
+        .map_err(|error| MyError::build(400, Some(error)))?;
+    let drop_translations = select_translations(&mut fields);
+    let filter = ProjectFilter {
+        featured,
+        stack: stack.as_deref().map(parse_list).unwrap_or_default(),
+        ..Default::default()
+    };
 
     let project_repo = container
         .get::<Arc<dyn ProjectRepository + Send + Sync>>()
@@ -93,6 +157,10 @@ pub async fn get(
 
     match customer_repo.find_customer_by_api_key(&client_key.0).await {
         Ok(Some(customer_doc)) => {
+            let chain = LocaleChain::new(
+                &requested_locales(locale.as_deref(), &accept_language.0),
+                &customer_doc,
+            );
             let Ok(oid) = ObjectId::parse_str(customer_doc.id) else {
                 return Err(MyError::build(
                     400,
@@ -100,8 +168,22 @@ pub async fn get(
                 ));
             };
 
-            match project_repo.find(limit, page, oid).await {
-                Ok(resp) => Ok(Json(resp)),
+            let resp: mongodb::error::Result<Vec<Sparse<Project>>> = match fields {
+                Some(fields) => project_repo
+                    .find_fields(limit, page, oid, fields, filter)
+                    .await
+                    .map(|resp| resp.into_iter().map(Sparse::Partial).collect()),
+                None => project_repo
+                    .find(limit, page, oid, filter)
+                    .await
+                    .map(|resp| resp.into_iter().map(Sparse::Full).collect()),
+            };
+
+            match resp {
+                Ok(mut resp) => {
+                    chain.localize_sparse(&mut resp, drop_translations);
+                    Ok(Cached(Json(resp)))
+                }
                 Err(error) => Err(MyError::build(400, Some(error.to_string()))),
             }
         }
@@ -167,10 +249,23 @@ pub async fn post(
         })));
     };
 
+    if let Err(message) = check_skill_ids(container, oid, input.skill_ids.as_deref()).await {
+        return Err(BadRequest(Json(MessageResponse { message })));
+    }
+    if let Err(message) = validate_translations(input.translations.as_ref(), Project::FIELDS) {
+        return Err(BadRequest(Json(MessageResponse { message })));
+    }
+    if let Err(message) = validate_seo(input.seo.as_ref()) {
+        return Err(BadRequest(Json(MessageResponse { message })));
+    }
+
     // can set with a single error like this.
-    match project_repo.find(1000, 1, oid).await {
+    match project_repo
+        .find(1000, 1, oid, ProjectFilter::default())
+        .await
+    {
         Ok(resp) => {
-            if resp.len() > 0 {
+            if !resp.is_empty() {
                 input.order = resp[0].order + 1;
             }
 
@@ -191,13 +286,26 @@ pub async fn post(
 #[patch("/project/<id>", data = "<input>")]
 pub async fn patch_by_id(
     container: &State<crate::Container>,
-    _key: ApiKey,
+    key: ApiKey,
     id: &str,
     input: Json<ProjectInput>,
 ) -> Result<Json<Project>, MyError> {
     let Ok(oid) = ObjectId::parse_str(id) else {
         return Err(MyError::build(400, Some("Invalid id format.".to_string())));
     };
+    let Ok(customer_oid) = ObjectId::parse_str(&key.0.sub) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    check_skill_ids(container, customer_oid, input.skill_ids.as_deref())
+        .await
+        .map_err(|message| MyError::build(400, Some(message)))?;
+    validate_translations(input.translations.as_ref(), Project::FIELDS)
+        .and_then(|_| validate_seo(input.seo.as_ref()))
+        .map_err(|message| MyError::build(400, Some(message)))?;
 
     let project_repo = container
         .get::<Arc<dyn ProjectRepository + Send + Sync>>()
@@ -222,9 +330,25 @@ pub async fn patch_by_id(
 #[patch("/project", data = "<input>")]
 pub async fn patch_many(
     container: &State<crate::Container>,
-    _key: ApiKey,
+    key: ApiKey,
     input: Json<Vec<ProjectsInput>>,
 ) -> Result<Json<Vec<Project>>, MyError> {
+    let Ok(customer_oid) = ObjectId::parse_str(&key.0.sub) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    for item in input.iter() {
+        check_skill_ids(container, customer_oid, item.skill_ids.as_deref())
+            .await
+            .map_err(|message| MyError::build(400, Some(message)))?;
+        validate_translations(item.translations.as_ref(), Project::FIELDS)
+            .and_then(|_| validate_seo(item.seo.as_ref()))
+            .map_err(|message| MyError::build(400, Some(message)))?;
+    }
+
     let project_repo = container
         .get::<Arc<dyn ProjectRepository + Send + Sync>>()
         .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
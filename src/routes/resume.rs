@@ -0,0 +1,429 @@
+use std::sync::Arc;
+
+use super::{
+    client_customer,
+    traits::{
+        CustomerRepository, ExperienceRepository, ProjectRepository, SkillRepository,
+        SocialRepository,
+    },
+};
+use mongodb::bson::oid::ObjectId;
+use rocket::{serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use crate::{
+    errors::response::MyError,
+    models::{
+        customer::Customer,
+        project::ProjectFilter,
+        resume::{Resume, ResumeAction, ResumeChange, ResumeImportReport},
+    },
+    request_guards::{
+        basic::{ApiKey, ClientApiKey},
+        locale::AcceptLanguage,
+    },
+    utils::{
+        i18n::{requested_locales, LocaleChain},
+        resume::{
+            merge_basics, merge_profile, merge_project, merge_skill, merge_work, resume_skills,
+            to_resume,
+        },
+    },
+};
+
+// Upper bound for every section, same as the portfolio.
+const SECTION_LIMIT: i64 = 1000;
+
+/// get the portfolio as a JSON Resume, see jsonresume.org.
+/// <br />With a Bearer JWT the resume of the signed in customer, with an `X-API-KEY` header the
+/// resume of the portfolio owning it.
+/// <br />The profile, projects and experiences are translated to `locale` or the
+/// `Accept-Language` header.
+#[openapi(tag = "Resume")]
+#[get("/resume.json?<locale>")]
+pub async fn get(
+    container: &State<crate::Container>,
+    key: Option<ApiKey>,
+    client_key: Option<ClientApiKey>,
+    accept_language: AcceptLanguage,
+    locale: Option<String>,
+) -> Result<Json<Resume>, MyError> {
+    let customer_repo = customer_repo(container)?;
+
+    let mut customer = match (key, client_key) {
+        (Some(key), _) => {
+            let oid = key_oid(&key)?;
+            match customer_repo.find_customer_by_id(oid).await {
+                Ok(Some(customer)) => customer,
+                Ok(None) | Err(_) => {
+                    return Err(MyError::build(
+                        400,
+                        Some(format!("Customer not found with _id {oid}")),
+                    ))
+                }
+            }
+        }
+        (None, Some(client_key)) => client_customer(container, &client_key).await?,
+        (None, None) => {
+            return Err(MyError::build(
+                401,
+                Some("A Bearer token or an X-API-KEY header is required".to_string()),
+            ))
+        }
+    };
+    let oid = customer_oid(&customer)?;
+
+    let experience_repo = container
+        .get::<Arc<dyn ExperienceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let skill_repo = container
+        .get::<Arc<dyn SkillRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let social_repo = container
+        .get::<Arc<dyn SocialRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let project_repo = container
+        .get::<Arc<dyn ProjectRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let (mut experiences, skills, socials, mut projects) = futures::try_join!(
+        experience_repo.find(SECTION_LIMIT, 1, oid),
+        skill_repo.find(SECTION_LIMIT, 1, oid),
+        social_repo.find(SECTION_LIMIT, 1, oid),
+        project_repo.find(SECTION_LIMIT, 1, oid, ProjectFilter::default()),
+    )
+    .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+
+    let chain = LocaleChain::new(
+        &requested_locales(locale.as_deref(), &accept_language.0),
+        &customer,
+    );
+    chain.localize(&mut customer);
+    experiences
+        .iter_mut()
+        .for_each(|experience| chain.localize(experience));
+    projects
+        .iter_mut()
+        .for_each(|project| chain.localize(project));
+
+    Ok(Json(to_resume(
+        &customer,
+        &experiences,
+        &skills,
+        &socials,
+        &projects,
+    )))
+}
+
+/// create or update the profile, skills, socials, experiences and projects from a JSON Resume.
+/// <br />Entries are matched by name: skills and socials by name, experiences by company and
+/// position and projects by name. Nothing is deleted.
+/// <br />With `dry_run=true` only the changes are reported.
+#[openapi(tag = "Resume")]
+#[post("/resume/import?<dry_run>", data = "<input>")]
+pub async fn import(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    dry_run: Option<bool>,
+    input: Json<Resume>,
+) -> Result<Json<ResumeImportReport>, MyError> {
+    let dry_run = dry_run.unwrap_or(false);
+    let oid = key_oid(&key)?;
+    let resume = input.into_inner();
+
+    let customer_repo = customer_repo(container)?;
+
+    let experience_repo = container
+        .get::<Arc<dyn ExperienceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let skill_repo = container
+        .get::<Arc<dyn SkillRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let social_repo = container
+        .get::<Arc<dyn SocialRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let project_repo = container
+        .get::<Arc<dyn ProjectRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let customer = match customer_repo.find_customer_by_id(oid).await {
+        Ok(Some(customer)) => customer,
+        Ok(None) | Err(_) => {
+            return Err(MyError::build(
+                400,
+                Some(format!("Customer not found with _id {oid}")),
+            ))
+        }
+    };
+
+    let (experiences, skills, socials, projects) = futures::try_join!(
+        experience_repo.find(SECTION_LIMIT, 1, oid),
+        skill_repo.find(SECTION_LIMIT, 1, oid),
+        social_repo.find(SECTION_LIMIT, 1, oid),
+        project_repo.find(SECTION_LIMIT, 1, oid, ProjectFilter::default()),
+    )
+    .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+
+    let to_error = |error: mongodb::error::Error| MyError::build(400, Some(error.to_string()));
+    let mut changes = vec![];
+    let mut skipped = vec![];
+
+    let (input, fields) = merge_basics(&customer, &resume.basics);
+    let action = if fields.is_empty() {
+        ResumeAction::Unchanged
+    } else {
+        ResumeAction::Update
+    };
+    if action == ResumeAction::Update && !dry_run {
+        customer_repo
+            .update_customer_by_id(oid, Json(input))
+            .await
+            .map_err(to_error)?;
+    }
+    changes.push(change(
+        "profile",
+        action,
+        Some(customer.id.clone()),
+        &customer.name,
+        fields,
+    ));
+
+    // skills
+    let mut claimed = vec![];
+    let mut order = next_order(skills.iter().map(|skill| skill.order));
+    for entry in resume_skills(&resume.skills) {
+        let existing = skills.iter().find(|skill| {
+            !claimed.contains(&skill.id) && skill.name.trim().eq_ignore_ascii_case(&entry.0)
+        });
+        let (input, fields) = merge_skill(existing, &entry, order);
+        let (action, id) = apply(existing.map(|skill| skill.id.clone()), &fields, &mut order);
+        claimed.extend(id.clone());
+        if !dry_run {
+            match (action, &id) {
+                (ResumeAction::Create, _) => {
+                    skill_repo
+                        .insert(Json(input), oid)
+                        .await
+                        .map_err(to_error)?;
+                }
+                (ResumeAction::Update, Some(id)) => {
+                    skill_repo
+                        .update_by_id(parse_oid(id)?, Json(input))
+                        .await
+                        .map_err(to_error)?;
+                }
+                _ => {}
+            }
+        }
+        changes.push(change("skill", action, id, &entry.0, fields));
+    }
+
+    // socials
+    let mut claimed = vec![];
+    let mut order = next_order(socials.iter().map(|social| social.order));
+    for profile in &resume.basics.profiles {
+        let existing =
+            socials.iter().find(|social| {
+                !claimed.contains(&social.id)
+                    && (profile.network.as_deref().is_some_and(|network| {
+                        social.name.trim().eq_ignore_ascii_case(network.trim())
+                    }) || profile.url.as_deref().map(str::trim) == Some(social.link.trim()))
+            });
+        let (input, fields) = match merge_profile(existing, profile, order) {
+            Ok(merged) => merged,
+            Err(reason) => {
+                skipped.push(reason);
+                continue;
+            }
+        };
+        let (action, id) = apply(
+            existing.map(|social| social.id.clone()),
+            &fields,
+            &mut order,
+        );
+        claimed.extend(id.clone());
+        let title = input.name.clone();
+        if !dry_run {
+            match (action, &id) {
+                (ResumeAction::Create, _) => {
+                    social_repo
+                        .insert(Json(input), oid)
+                        .await
+                        .map_err(to_error)?;
+                }
+                (ResumeAction::Update, Some(id)) => {
+                    social_repo
+                        .update_by_id(parse_oid(id)?, Json(input))
+                        .await
+                        .map_err(to_error)?;
+                }
+                _ => {}
+            }
+        }
+        changes.push(change("social", action, id, &title, fields));
+    }
+
+    // experiences
+    let mut claimed = vec![];
+    let mut order = next_order(experiences.iter().map(|experience| experience.order));
+    for work in &resume.work {
+        let same = |known: &str, value: &Option<String>| {
+            value
+                .as_deref()
+                .is_some_and(|value| known.trim().eq_ignore_ascii_case(value.trim()))
+        };
+        let existing = experiences.iter().find(|experience| {
+            !claimed.contains(&experience.id)
+                && same(&experience.company, &work.name)
+                && same(&experience.position, &work.position)
+        });
+        let (input, fields) = match merge_work(existing, work, order) {
+            Ok(merged) => merged,
+            Err(reason) => {
+                skipped.push(reason);
+                continue;
+            }
+        };
+        let (action, id) = apply(
+            existing.map(|experience| experience.id.clone()),
+            &fields,
+            &mut order,
+        );
+        claimed.extend(id.clone());
+        let title = format!("{} at {}", input.position, input.company);
+        if !dry_run {
+            match (action, &id) {
+                (ResumeAction::Create, _) => {
+                    experience_repo
+                        .insert(Json(input), oid)
+                        .await
+                        .map_err(to_error)?;
+                }
+                (ResumeAction::Update, Some(id)) => {
+                    experience_repo
+                        .update_by_id(parse_oid(id)?, Json(input))
+                        .await
+                        .map_err(to_error)?;
+                }
+                _ => {}
+            }
+        }
+        changes.push(change("experience", action, id, &title, fields));
+    }
+
+    // projects
+    let mut claimed = vec![];
+    let mut order = next_order(projects.iter().map(|project| project.order));
+    for resume_project in &resume.projects {
+        let existing = projects.iter().find(|project| {
+            !claimed.contains(&project.id)
+                && resume_project
+                    .name
+                    .as_deref()
+                    .is_some_and(|name| project.name.trim().eq_ignore_ascii_case(name.trim()))
+        });
+        let (input, fields) = match merge_project(existing, resume_project, order) {
+            Ok(merged) => merged,
+            Err(reason) => {
+                skipped.push(reason);
+                continue;
+            }
+        };
+        let (action, id) = apply(
+            existing.map(|project| project.id.clone()),
+            &fields,
+            &mut order,
+        );
+        claimed.extend(id.clone());
+        let title = input.name.clone();
+        if !dry_run {
+            match (action, &id) {
+                (ResumeAction::Create, _) => {
+                    project_repo
+                        .insert(Json(input), oid)
+                        .await
+                        .map_err(to_error)?;
+                }
+                (ResumeAction::Update, Some(id)) => {
+                    project_repo
+                        .update_by_id(parse_oid(id)?, Json(input))
+                        .await
+                        .map_err(to_error)?;
+                }
+                _ => {}
+            }
+        }
+        changes.push(change("project", action, id, &title, fields));
+    }
+
+    Ok(Json(ResumeImportReport {
+        dry_run,
+        changes,
+        skipped,
+    }))
+}
+
+// Action for a merged entry, a created one takes the next `order`.
+fn apply(
+    existing: Option<String>,
+    fields: &[String],
+    order: &mut i32,
+) -> (ResumeAction, Option<String>) {
+    match existing {
+        Some(id) if fields.is_empty() => (ResumeAction::Unchanged, Some(id)),
+        Some(id) => (ResumeAction::Update, Some(id)),
+        None => {
+            *order += 1;
+            (ResumeAction::Create, None)
+        }
+    }
+}
+
+fn change(
+    section: &str,
+    action: ResumeAction,
+    id: Option<String>,
+    title: &str,
+    fields: Vec<String>,
+) -> ResumeChange {
+    ResumeChange {
+        section: section.to_string(),
+        action,
+        id,
+        title: title.to_string(),
+        fields,
+    }
+}
+
+fn next_order(orders: impl Iterator<Item = i32>) -> i32 {
+    orders.max().map_or(1, |order| order + 1)
+}
+
+fn customer_repo(
+    container: &State<crate::Container>,
+) -> Result<&Arc<dyn CustomerRepository + Send + Sync>, MyError> {
+    container
+        .get::<Arc<dyn CustomerRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))
+}
+
+fn key_oid(key: &ApiKey) -> Result<ObjectId, MyError> {
+    ObjectId::parse_str(&key.0.sub)
+        .map_err(|_| MyError::build(400, Some("Invalid user id format.".to_string())))
+}
+
+fn customer_oid(customer: &Customer) -> Result<ObjectId, MyError> {
+    ObjectId::parse_str(&customer.id)
+        .map_err(|_| MyError::build(400, Some("Invalid user id format.".to_string())))
+}
+
+fn parse_oid(id: &str) -> Result<ObjectId, MyError> {
+    ObjectId::parse_str(id)
+        .map_err(|_| MyError::build(400, Some("Invalid _id format.".to_string())))
+}
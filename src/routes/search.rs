@@ -0,0 +1,118 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use super::{client_oid, traits::SearchRepository};
+use mongodb::bson::oid::ObjectId;
+use rocket::{serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use crate::{
+    errors::response::MyError,
+    models::search::{SearchFacet, SearchKind, SearchResult},
+    request_guards::basic::{ApiKey, ClientApiKey},
+    utils::util::parse_list,
+};
+
+const MAX_QUERY_LENGTH: usize = 200;
+
+/// search the content of a portfolio, most relevant first.
+/// <br />With a Bearer JWT every content of the signed in customer is searched, drafts included,
+/// with an `X-API-KEY` header only the published content of the portfolio.
+/// <br />`types` is a comma separated list of profile, project, experience, skill, social and article.
+#[openapi(tag = "Search")]
+#[get("/search?<q>&<types>&<limit>&<page>")]
+pub async fn get(
+    container: &State<crate::Container>,
+    key: Option<ApiKey>,
+    client_key: Option<ClientApiKey>,
+    q: &str,
+    types: Option<&str>,
+    limit: Option<i64>,
+    page: Option<i64>,
+) -> Result<Json<SearchResult>, MyError> {
+    // Setting default values
+    let limit = limit.unwrap_or(20).max(1) as usize;
+    let page = page.unwrap_or(1).max(1) as usize;
+
+    let query = q.trim();
+    if query.is_empty() {
+        return Err(MyError::build(
+            400,
+            Some("Search query cannot be empty".to_string()),
+        ));
+    }
+    if query.chars().count() > MAX_QUERY_LENGTH {
+        return Err(MyError::build(
+            400,
+            Some(format!(
+                "Search query cannot be longer than {MAX_QUERY_LENGTH} characters"
+            )),
+        ));
+    }
+
+    let types = match types {
+        Some(types) => parse_list(types)
+            .iter()
+            .map(|kind| {
+                SearchKind::parse(kind).ok_or_else(|| {
+                    MyError::build(400, Some(format!("{kind} is not a searchable type")))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => vec![],
+    };
+
+    let (oid, published_only) = match (key, client_key) {
+        (Some(key), _) => {
+            let Ok(oid) = ObjectId::parse_str(&key.0.sub) else {
+                return Err(MyError::build(
+                    400,
+                    Some("Invalid user id format.".to_string()),
+                ));
+            };
+            (oid, false)
+        }
+        (None, Some(client_key)) => (client_oid(container, &client_key).await?, true),
+        (None, None) => {
+            return Err(MyError::build(
+                401,
+                Some("A Bearer token or an X-API-KEY header is required".to_string()),
+            ))
+        }
+    };
+
+    let search_repo = container
+        .get::<Arc<dyn SearchRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let hits = search_repo
+        .search(oid, query, published_only)
+        .await
+        .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+
+    let mut counts: BTreeMap<SearchKind, usize> = BTreeMap::new();
+    for hit in &hits {
+        *counts.entry(hit.kind).or_default() += 1;
+    }
+    let facets = counts
+        .into_iter()
+        .map(|(kind, count)| SearchFacet { kind, count })
+        .collect();
+
+    let hits: Vec<_> = hits
+        .into_iter()
+        .filter(|hit| types.is_empty() || types.contains(&hit.kind))
+        .collect();
+    let total = hits.len();
+    let hits = hits
+        .into_iter()
+        .skip((page - 1) * limit)
+        .take(limit)
+        .collect();
+
+    Ok(Json(SearchResult {
+        query: query.to_string(),
+        total,
+        facets,
+        hits,
+    }))
+}
@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use super::{
+    client_customer,
+    traits::{ArticleRepository, ProjectRepository},
+};
+use mongodb::bson::oid::ObjectId;
+use rocket::{http::ContentType, serde::json::Json, State};
+use rocket_okapi::openapi;
+use serde_json::Value;
+
+use crate::{
+    errors::response::MyError,
+    models::{customer::Customer, project::ProjectFilter, seo::PageMeta},
+    request_guards::{basic::ClientApiKey, locale::AcceptLanguage},
+    utils::{
+        feed::{atom_xml, json_feed, rss_xml, sitemap_xml},
+        i18n::{requested_locales, LocaleChain},
+        seo::{
+            normalize_path, page_meta, robots_txt, site_feed, site_pages, sitemap_urls, SitePage,
+        },
+    },
+};
+
+// Upper bound for every section, same as the portfolio.
+const SECTION_LIMIT: i64 = 1000;
+
+/// get the `sitemap.xml` of the portfolio site: the home page, the projects and the published
+/// articles, at `/projects/<slug>/` and `/articles/<slug>/` below the `site_url` of the customer.
+/// <br />Pages with a `seo.canonical_url` are left out, they are indexed where they point to.
+/// Slugs come from the project names translated to `locale` or the `Accept-Language` header,
+/// the same as `GET /site/build`.
+#[openapi(tag = "SEO")]
+#[get("/seo/sitemap.xml?<locale>")]
+pub async fn sitemap(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    accept_language: AcceptLanguage,
+    locale: Option<String>,
+) -> Result<(ContentType, String), MyError> {
+    let locales = requested_locales(locale.as_deref(), &accept_language.0);
+    let (_, site_url, pages) = load_pages(container, &client_key, &locales).await?;
+
+    Ok((
+        ContentType::XML,
+        sitemap_xml(&sitemap_urls(&site_url, &pages)),
+    ))
+}
+
+/// get the `robots.txt` of the portfolio site, it allows everything and points to the sitemap.
+#[openapi(tag = "SEO")]
+#[get("/seo/robots.txt")]
+pub async fn robots(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+) -> Result<(ContentType, String), MyError> {
+    let customer = client_customer(container, &client_key).await?;
+    let site_url = site_url(&customer)?;
+
+    Ok((ContentType::Plain, robots_txt(&site_url)))
+}
+
+/// get an Atom feed of the projects and published articles, newest first.
+/// <br />Its own url is `<site_url>/feed.xml`. Projects are dated by their `start_date`, and are
+/// translated to `locale` or the `Accept-Language` header.
+#[openapi(tag = "SEO")]
+#[get("/seo/feed.xml?<locale>")]
+pub async fn atom(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    accept_language: AcceptLanguage,
+    locale: Option<String>,
+) -> Result<(ContentType, String), MyError> {
+    let locales = requested_locales(locale.as_deref(), &accept_language.0);
+    let (customer, site_url, pages) = load_pages(container, &client_key, &locales).await?;
+    let feed = site_feed(&customer, &site_url, "/feed.xml", &pages);
+
+    Ok((ContentType::new("application", "atom+xml"), atom_xml(&feed)))
+}
+
+/// get the feed of `GET /seo/feed.xml` as RSS 2.0, its own url is `<site_url>/rss.xml`.
+#[openapi(tag = "SEO")]
+#[get("/seo/rss.xml?<locale>")]
+pub async fn rss(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    accept_language: AcceptLanguage,
+    locale: Option<String>,
+) -> Result<(ContentType, String), MyError> {
+    let locales = requested_locales(locale.as_deref(), &accept_language.0);
+    let (customer, site_url, pages) = load_pages(container, &client_key, &locales).await?;
+    let feed = site_feed(&customer, &site_url, "/rss.xml", &pages);
+
+    Ok((ContentType::new("application", "rss+xml"), rss_xml(&feed)))
+}
+
+/// get the feed of `GET /seo/feed.xml` as a JSON Feed 1.1, its own url is `<site_url>/feed.json`.
+#[openapi(tag = "SEO")]
+#[get("/seo/feed.json?<locale>")]
+pub async fn json(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    accept_language: AcceptLanguage,
+    locale: Option<String>,
+) -> Result<(ContentType, Json<Value>), MyError> {
+    let locales = requested_locales(locale.as_deref(), &accept_language.0);
+    let (customer, site_url, pages) = load_pages(container, &client_key, &locales).await?;
+    let feed = site_feed(&customer, &site_url, "/feed.json", &pages);
+
+    Ok((
+        ContentType::new("application", "feed+json"),
+        Json(json_feed(&feed)),
+    ))
+}
+
+/// get the meta tags of a page of the portfolio site, `path` is `/`, `/projects/<slug>/` or
+/// `/articles/<slug>/`.
+/// <br />`html` holds the `<title>`, description, Open Graph, Twitter card and canonical tags,
+/// ready to be pasted into `<head>`. The `seo` fields of the page take precedence over its
+/// content. Projects are translated to `locale` or the `Accept-Language` header.
+#[openapi(tag = "SEO")]
+#[get("/seo/meta?<path>&<locale>")]
+pub async fn meta(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    accept_language: AcceptLanguage,
+    path: &str,
+    locale: Option<String>,
+) -> Result<Json<PageMeta>, MyError> {
+    let locales = requested_locales(locale.as_deref(), &accept_language.0);
+    let (customer, site_url, pages) = load_pages(container, &client_key, &locales).await?;
+
+    let path = normalize_path(path);
+    let Some(page) = pages.iter().find(|page| page.path == path) else {
+        return Err(MyError::build(404, Some(format!("No page at {path}"))));
+    };
+
+    Ok(Json(page_meta(&customer, &site_url, page)))
+}
+
+fn site_url(customer: &Customer) -> Result<String, MyError> {
+    customer
+        .site_url
+        .clone()
+        .filter(|url| !url.is_empty())
+        .ok_or_else(|| {
+            MyError::build(
+                400,
+                Some("The customer has no site_url, set it with PATCH /customer".to_string()),
+            )
+        })
+}
+
+// The customer of the key with its `site_url` and the pages of its site, translated to
+// `locales`.
+async fn load_pages(
+    container: &State<crate::Container>,
+    client_key: &ClientApiKey,
+    locales: &[String],
+) -> Result<(Customer, String, Vec<SitePage>), MyError> {
+    let mut customer = client_customer(container, client_key).await?;
+    let site_url = site_url(&customer)?;
+    let Ok(oid) = ObjectId::parse_str(&customer.id) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let project_repo = container
+        .get::<Arc<dyn ProjectRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+    let article_repo = container
+        .get::<Arc<dyn ArticleRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let (mut projects, articles) = futures::try_join!(
+        project_repo.find(SECTION_LIMIT, 1, oid, ProjectFilter::default()),
+        article_repo.find(SECTION_LIMIT, 1, oid, true),
+    )
+    .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+
+    let chain = LocaleChain::new(locales, &customer);
+    chain.localize(&mut customer);
+    projects
+        .iter_mut()
+        .for_each(|project| chain.localize(project));
+
+    let pages = site_pages(&customer, &projects, &articles);
+    Ok((customer, site_url, pages))
+}
@@ -0,0 +1,290 @@
+use std::sync::Arc;
+
+use super::{check_project_ids, client_oid, traits::ServiceRepository};
+use mongodb::bson::oid::ObjectId;
+use rocket::{serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use crate::{
+    errors::response::MyError,
+    models::service::{Service, ServiceInput, ServicesInput},
+    request_guards::basic::{ApiKey, ClientApiKey},
+    responders::cache::Cached,
+    utils::money::is_currency_code,
+};
+
+#[openapi(tag = "Service")]
+#[get("/service?<limit>&<page>")]
+pub async fn get_all(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    limit: Option<i64>,
+    page: Option<i64>,
+) -> Result<Json<Vec<Service>>, MyError> {
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let claims = &key.0; // Access the Claims struct
+    let id = &claims.sub;
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let service_repo = container
+        .get::<Arc<dyn ServiceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match service_repo.find(limit, page, oid).await {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// get the services offered by the portfolio owning the api key, with their formatted price.
+#[openapi(tag = "Service")]
+#[get("/services?<limit>&<page>")]
+pub async fn get(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    limit: Option<i64>,
+    page: Option<i64>,
+) -> Result<Cached<Vec<Service>>, MyError> {
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let oid = client_oid(container, &client_key).await?;
+
+    let service_repo = container
+        .get::<Arc<dyn ServiceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match service_repo.find(limit, page, oid).await {
+        Ok(resp) => Ok(Cached(Json(resp))),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Service")]
+#[get("/service/<id>")]
+pub async fn get_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+) -> Result<Json<Service>, MyError> {
+    let (_, service) = owned_service(container, &key, id).await?;
+
+    Ok(Json(service))
+}
+
+/// create a service, it is put last in the order.
+/// <br />`project_ids` have to be projects of the signed in customer.
+#[openapi(tag = "Service")]
+#[post("/service", data = "<input>")]
+pub async fn post(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    mut input: Json<ServiceInput>,
+) -> Result<Json<String>, MyError> {
+    let claims = &key.0; // Access the Claims struct
+    let id = &claims.sub;
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let service_repo = container
+        .get::<Arc<dyn ServiceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    validate(
+        container,
+        oid,
+        (input.price_min, input.price_max),
+        input.currency.as_deref(),
+        input.project_ids.as_deref(),
+    )
+    .await?;
+
+    match service_repo.find(1, 1, oid).await {
+        Ok(resp) => {
+            if let Some(last) = resp.first() {
+                input.order = last.order + 1;
+            }
+        }
+        Err(error) => return Err(MyError::build(400, Some(error.to_string()))),
+    }
+
+    match service_repo.insert(input, oid).await {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Service")]
+#[patch("/service/<id>", data = "<input>")]
+pub async fn patch_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+    input: Json<ServiceInput>,
+) -> Result<Json<Service>, MyError> {
+    let (oid, _) = owned_service(container, &key, id).await?;
+    let Ok(customer_oid) = ObjectId::parse_str(&key.0.sub) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let service_repo = container
+        .get::<Arc<dyn ServiceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    validate(
+        container,
+        customer_oid,
+        (input.price_min, input.price_max),
+        input.currency.as_deref(),
+        input.project_ids.as_deref(),
+    )
+    .await?;
+
+    match service_repo.update_by_id(oid, input).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) => Err(MyError::build(
+            400,
+            Some(format!("Service not found with id {}", &id)),
+        )),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Service")]
+#[patch("/service", data = "<input>")]
+pub async fn patch_many(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    input: Json<Vec<ServicesInput>>,
+) -> Result<Json<Vec<Service>>, MyError> {
+    let Ok(customer_oid) = ObjectId::parse_str(&key.0.sub) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let service_repo = container
+        .get::<Arc<dyn ServiceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    for item in input.iter() {
+        validate(
+            container,
+            customer_oid,
+            (item.price_min, item.price_max),
+            item.currency.as_deref(),
+            item.project_ids.as_deref(),
+        )
+        .await?;
+    }
+
+    // Services of other customers are skipped.
+    match service_repo.update_many(customer_oid, input).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) | Err(_) => Err(MyError::build(400, Some("Failed to update".to_string()))),
+    }
+}
+
+#[openapi(tag = "Service")]
+#[delete("/service/<id>")]
+pub async fn delete_by_id(
+    container: &State<crate::Container>,
+    id: &str,
+    key: ApiKey,
+) -> Result<Json<Service>, MyError> {
+    let (oid, _) = owned_service(container, &key, id).await?;
+
+    let service_repo = container
+        .get::<Arc<dyn ServiceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match service_repo.delete_by_id(oid).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Service not found with _id {}", &id)),
+        )),
+    }
+}
+
+// Check the price range, the currency and that the related projects belong to the customer.
+async fn validate(
+    container: &State<crate::Container>,
+    oid: ObjectId,
+    (price_min, price_max): (Option<f64>, Option<f64>),
+    currency: Option<&str>,
+    project_ids: Option<&[String]>,
+) -> Result<(), MyError> {
+    if [price_min, price_max]
+        .into_iter()
+        .flatten()
+        .any(|price| !price.is_finite() || price < 0.0)
+    {
+        return Err(MyError::build(
+            400,
+            Some("prices must be positive numbers".to_string()),
+        ));
+    }
+    if let (Some(min), Some(max)) = (price_min, price_max) {
+        if min > max {
+            return Err(MyError::build(
+                400,
+                Some("price_min must not be greater than price_max".to_string()),
+            ));
+        }
+    }
+    if let Some(currency) = currency {
+        if !is_currency_code(&currency.trim().to_uppercase()) {
+            return Err(MyError::build(
+                400,
+                Some(format!("{currency} is not an ISO 4217 currency code")),
+            ));
+        }
+    }
+
+    check_project_ids(container, oid, project_ids)
+        .await
+        .map_err(|message| MyError::build(400, Some(message)))?;
+
+    Ok(())
+}
+
+// Only the owner of the portfolio may see or change its services by id.
+async fn owned_service(
+    container: &State<crate::Container>,
+    key: &ApiKey,
+    id: &str,
+) -> Result<(ObjectId, Service), MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid id format.".to_string())));
+    };
+
+    let service_repo = container
+        .get::<Arc<dyn ServiceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match service_repo.find_by_id(oid).await {
+        Ok(Some(service)) if service.customer_id == key.0.sub => Ok((oid, service)),
+        Ok(_) | Err(_) => Err(MyError::build(
+            404,
+            Some(format!("Service not found with _id {}", &id)),
+        )),
+    }
+}
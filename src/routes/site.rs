@@ -0,0 +1,222 @@
+use std::sync::Arc;
+
+use super::traits::{
+    CustomerRepository, ExperienceRepository, MediaRepository, MediaStore, ProjectRepository,
+    SkillRepository, SocialRepository,
+};
+use mongodb::bson::oid::ObjectId;
+use rocket::{http::ContentType, State};
+use rocket_okapi::openapi;
+
+use crate::{
+    errors::response::MyError,
+    models::{
+        customer::Customer,
+        project::ProjectFilter,
+        site::{SiteContent, SiteExperience, SiteProfile, SiteProject},
+    },
+    request_guards::basic::ApiKey,
+    responders::download::Download,
+    utils::{
+        cv::period,
+        i18n::{requested_locales, LocaleChain, DEFAULT_LOCALE},
+        site::{build_site, project_slugs, write_zip, SiteFiles, Theme},
+    },
+};
+
+// Upper bound for every section, same as the portfolio.
+const SECTION_LIMIT: i64 = 1000;
+
+/// build the portfolio of the signed in customer into a static site, downloaded as a ZIP file.
+/// <br />`base_url` is where the site will be hosted, e.g. `https://jane.dev`, links of the
+/// sitemap and the feed start with it. `locale` picks the translation of the content.
+/// <br />The theme is `SITE_THEME_DIR`, or the built-in one. Uploaded media are included when
+/// `MEDIA_BASE_URL` is a path, under that same path.
+#[openapi(tag = "Site")]
+#[get("/site/build?<base_url>&<locale>")]
+pub async fn build(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    base_url: &str,
+    locale: Option<&str>,
+) -> Result<Download, MyError> {
+    let oid = ObjectId::parse_str(&key.0.sub)
+        .map_err(|_| MyError::build(400, Some("Invalid user id format.".to_string())))?;
+
+    let customer_repo = container
+        .get::<Arc<dyn CustomerRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let customer = match customer_repo.find_customer_by_id(oid).await {
+        Ok(Some(customer)) => customer,
+        Ok(None) | Err(_) => {
+            return Err(MyError::build(
+                400,
+                Some(format!("Customer not found with _id {oid}")),
+            ))
+        }
+    };
+
+    let (content, media) = site_content(container, customer, base_url, locale)
+        .await
+        .map_err(|error| MyError::build(400, Some(error)))?;
+
+    let file_name = format!(
+        "site-{}.zip",
+        content.generated_at.get(..10).unwrap_or_default()
+    );
+    let bytes = rocket::tokio::task::spawn_blocking(move || {
+        let theme = Theme::from_env()?;
+        write_zip(&build_site(&theme, &content, media)?)
+    })
+    .await
+    .map_err(|error| MyError::build(500, Some(error.to_string())))?
+    .map_err(|error| MyError::build(500, Some(error)))?;
+
+    Ok(Download {
+        file_name,
+        content_type: ContentType::ZIP,
+        bytes,
+    })
+}
+
+/// Content of the site of the customer, and the media files to copy into it by path.
+pub async fn site_content(
+    container: &crate::Container,
+    mut customer: Customer,
+    base_url: &str,
+    locale: Option<&str>,
+) -> Result<(SiteContent, SiteFiles), String> {
+    let base_url = base_url.trim().trim_end_matches('/');
+    let Some(host_and_path) = base_url
+        .strip_prefix("https://")
+        .or_else(|| base_url.strip_prefix("http://"))
+        .filter(|rest| !rest.is_empty())
+    else {
+        return Err(format!(
+            "Invalid base_url {base_url}, expected an http(s) url"
+        ));
+    };
+    let base_path = host_and_path
+        .find('/')
+        .map(|index| host_and_path[index..].to_string())
+        .unwrap_or_default();
+
+    let oid =
+        ObjectId::parse_str(&customer.id).map_err(|_| "Invalid user id format.".to_string())?;
+
+    let not_found = || "Service not found".to_string();
+    let project_repo = container
+        .get::<Arc<dyn ProjectRepository + Send + Sync>>()
+        .ok_or_else(not_found)?;
+    let experience_repo = container
+        .get::<Arc<dyn ExperienceRepository + Send + Sync>>()
+        .ok_or_else(not_found)?;
+    let skill_repo = container
+        .get::<Arc<dyn SkillRepository + Send + Sync>>()
+        .ok_or_else(not_found)?;
+    let social_repo = container
+        .get::<Arc<dyn SocialRepository + Send + Sync>>()
+        .ok_or_else(not_found)?;
+    let media_repo = container
+        .get::<Arc<dyn MediaRepository + Send + Sync>>()
+        .ok_or_else(not_found)?;
+    let media_store = container
+        .get::<Arc<dyn MediaStore + Send + Sync>>()
+        .ok_or_else(not_found)?;
+
+    let (mut projects, mut experiences, skills, socials, media) = futures::try_join!(
+        project_repo.find(SECTION_LIMIT, 1, oid, ProjectFilter::default()),
+        experience_repo.find(SECTION_LIMIT, 1, oid),
+        skill_repo.find(SECTION_LIMIT, 1, oid),
+        social_repo.find(SECTION_LIMIT, 1, oid),
+        media_repo.find(SECTION_LIMIT, 1, oid),
+    )
+    .map_err(|error| error.to_string())?;
+
+    let requested = requested_locales(locale, &[]);
+    let chain = LocaleChain::new(&requested, &customer);
+    chain.localize(&mut customer);
+    experiences
+        .iter_mut()
+        .for_each(|experience| chain.localize(experience));
+    projects
+        .iter_mut()
+        .for_each(|project| chain.localize(project));
+    let locale = requested
+        .into_iter()
+        .find(|locale| {
+            customer
+                .locales
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(locale))
+        })
+        .or_else(|| customer.default_locale.clone())
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+
+    // files served by this server have to move along with the site
+    let mut files = SiteFiles::new();
+    for media in &media {
+        let urls = std::iter::once((&media.key, &media.url)).chain(
+            media
+                .variants
+                .iter()
+                .map(|variant| (&variant.key, &variant.url)),
+        );
+        for (key, url) in urls {
+            let Some(path) = url.strip_prefix('/') else {
+                continue;
+            };
+            if let Ok(Some(bytes)) = media_store.get(key).await {
+                files.insert(path.to_string(), bytes);
+            }
+        }
+    }
+
+    let slugs = project_slugs(&projects);
+    let projects = projects
+        .into_iter()
+        .zip(slugs)
+        .map(|(project, slug)| SiteProject {
+            url: format!("{base_path}/projects/{slug}/"),
+            slug,
+            period: project
+                .start_date
+                .as_deref()
+                .and_then(|start| period(Some(start), project.end_date.as_deref())),
+            project,
+        })
+        .collect();
+
+    let experiences = experiences
+        .into_iter()
+        .map(|experience| SiteExperience {
+            period: period(Some(&experience.start_date), experience.end_date.as_deref()),
+            experience,
+        })
+        .collect();
+
+    let content = SiteContent {
+        profile: SiteProfile {
+            name: customer.name,
+            email: customer.email,
+            phone: customer.phone,
+            wa_link: customer.wa_link,
+            intro: customer.intro,
+            about: customer.about,
+            profile_picture: customer.profile_picture,
+            profile_picture_srcset: customer.profile_picture_srcset,
+            profile_picture_blurhash: customer.profile_picture_blurhash,
+        },
+        projects,
+        experiences,
+        skills,
+        socials,
+        base_url: base_url.to_string(),
+        base_path,
+        locale,
+        generated_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    Ok((content, files))
+}
@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use super::traits::{CustomerRepository, SkillRepository};
+use super::{
+    client_customer,
+    traits::{CustomerRepository, ExperienceRepository, ProjectRepository, SkillRepository},
+};
 use mongodb::bson::{doc, oid::ObjectId};
 use rocket::{response::status::BadRequest, serde::json::Json, State};
 use rocket_okapi::openapi;
@@ -8,10 +11,18 @@ use rocket_okapi::openapi;
 use crate::{
     errors::response::MyError,
     models::{
+        customer::Customer,
+        experience::Experience,
+        project::{Project, ProjectFilter},
         response::MessageResponse,
         skill::{Skill, SkillInput, SkillsInput},
     },
-    request_guards::basic::{ApiKey, ClientApiKey},
+    request_guards::{
+        basic::{ApiKey, ClientApiKey},
+        locale::AcceptLanguage,
+    },
+    responders::cache::Cached,
+    utils::i18n::{requested_locales, LocaleChain},
 };
 
 #[openapi(tag = "Skill")]
@@ -65,7 +76,7 @@ pub async fn get(
     client_key: ClientApiKey,
     limit: Option<i64>,
     page: Option<i64>,
-) -> Result<Json<Vec<Skill>>, MyError> {
+) -> Result<Cached<Vec<Skill>>, MyError> {
     // Error handling
     // This is also valid when strict checking is necessary.
     // if limit < 0 {
@@ -101,7 +112,7 @@ pub async fn get(
             };
 
             match skill_repo.find(limit, page, oid).await {
-                Ok(resp) => Ok(Json(resp)),
+                Ok(resp) => Ok(Cached(Json(resp))),
                 Err(error) => Err(MyError::build(400, Some(error.to_string()))),
             }
         }
@@ -170,7 +181,7 @@ pub async fn post(
     // can set with a single error like this.
     match skill_repo.find(1000, 1, oid).await {
         Ok(resp) => {
-            if resp.len() > 0 {
+            if !resp.is_empty() {
                 input.order = resp[0].order + 1;
             }
 
@@ -253,6 +264,7 @@ pub async fn delete_by_id(
         .get::<Arc<dyn SkillRepository + Send + Sync>>()
         .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
 
+    // With `SKILL_DELETE_POLICY=restrict` a skill still in use is not deleted.
     match skill_repo.delete_by_id(oid).await {
         Ok(resp) => match resp {
             Some(resp) => Ok(Json(resp)),
@@ -261,9 +273,95 @@ pub async fn delete_by_id(
                 Some(format!("Skill not found with _id {}", &id)),
             )),
         },
-        Err(_error) => Err(MyError::build(
-            400,
-            Some(format!("Skill not found with _id {}", &id)),
-        )),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// get the projects of the portfolio owning the api key that use a skill.
+/// <br />They are translated like `/projects`.
+#[openapi(tag = "Skill")]
+#[get("/skills/<id>/projects?<locale>")]
+pub async fn get_projects(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    accept_language: AcceptLanguage,
+    id: &str,
+    locale: Option<String>,
+) -> Result<Cached<Vec<Project>>, MyError> {
+    let (customer_doc, skill_oid) = portfolio_skill(container, &client_key, id).await?;
+    let chain = LocaleChain::new(
+        &requested_locales(locale.as_deref(), &accept_language.0),
+        &customer_doc,
+    );
+    let oid = ObjectId::parse_str(&customer_doc.id)
+        .map_err(|_| MyError::build(400, Some("Invalid user id format.".to_string())))?;
+
+    let project_repo = container
+        .get::<Arc<dyn ProjectRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let filter = ProjectFilter {
+        skill_id: Some(skill_oid),
+        ..Default::default()
+    };
+    match project_repo.find(1000, 1, oid, filter).await {
+        Ok(mut resp) => {
+            resp.iter_mut().for_each(|project| chain.localize(project));
+            Ok(Cached(Json(resp)))
+        }
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// get the experiences of the portfolio owning the api key that use a skill.
+/// <br />They are translated like `/experiences`.
+#[openapi(tag = "Skill")]
+#[get("/skills/<id>/experiences?<locale>")]
+pub async fn get_experiences(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    accept_language: AcceptLanguage,
+    id: &str,
+    locale: Option<String>,
+) -> Result<Cached<Vec<Experience>>, MyError> {
+    let (customer_doc, skill_oid) = portfolio_skill(container, &client_key, id).await?;
+    let chain = LocaleChain::new(
+        &requested_locales(locale.as_deref(), &accept_language.0),
+        &customer_doc,
+    );
+    let oid = ObjectId::parse_str(&customer_doc.id)
+        .map_err(|_| MyError::build(400, Some("Invalid user id format.".to_string())))?;
+
+    let experience_repo = container
+        .get::<Arc<dyn ExperienceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match experience_repo.find_by_skill(oid, skill_oid).await {
+        Ok(mut resp) => {
+            resp.iter_mut()
+                .for_each(|experience| chain.localize(experience));
+            Ok(Cached(Json(resp)))
+        }
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+// Customer of the api key and id of one of its skills.
+async fn portfolio_skill(
+    container: &State<crate::Container>,
+    client_key: &ClientApiKey,
+    id: &str,
+) -> Result<(Customer, ObjectId), MyError> {
+    let customer_doc = client_customer(container, client_key).await?;
+
+    let skill_repo = container
+        .get::<Arc<dyn SkillRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let not_found = || MyError::build(404, Some(format!("Skill not found with _id {id}")));
+    let skill_oid = ObjectId::parse_str(id).map_err(|_| not_found())?;
+    match skill_repo.find_by_id(skill_oid).await {
+        Ok(Some(skill)) if skill.customer_id == customer_doc.id => Ok((customer_doc, skill_oid)),
+        Ok(_) | Err(_) => Err(not_found()),
     }
 }
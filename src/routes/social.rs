@@ -12,6 +12,7 @@ use crate::{
         social::{Social, SocialInput, SocialsInput},
     },
     request_guards::basic::{ApiKey, ClientApiKey},
+    responders::cache::Cached,
 };
 
 #[openapi(tag = "Social")]
@@ -65,7 +66,7 @@ pub async fn get(
     client_key: ClientApiKey,
     limit: Option<i64>,
     page: Option<i64>,
-) -> Result<Json<Vec<Social>>, MyError> {
+) -> Result<Cached<Vec<Social>>, MyError> {
     // Error handling
     // This is also valid when strict checking is necessary.
     // if limit < 0 {
@@ -101,7 +102,7 @@ pub async fn get(
             };
 
             match social_repo.find(limit, page, oid).await {
-                Ok(resp) => Ok(Json(resp)),
+                Ok(resp) => Ok(Cached(Json(resp))),
                 Err(error) => Err(MyError::build(400, Some(error.to_string()))),
             }
         }
@@ -170,7 +171,7 @@ pub async fn post(
     // can set with a single error like this.
     match social_repo.find(1000, 1, oid).await {
         Ok(resp) => {
-            if resp.len() > 0 {
+            if !resp.is_empty() {
                 input.order = resp[0].order + 1;
             }
 
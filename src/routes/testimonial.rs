@@ -0,0 +1,321 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use super::{
+    client_oid,
+    contact::spam_error,
+    traits::{ProjectRepository, TestimonialRepository},
+};
+use mongodb::bson::oid::ObjectId;
+use rocket::{serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use crate::{
+    errors::response::MyError,
+    models::testimonial::{
+        Testimonial, TestimonialInput, TestimonialStatus, TestimonialSubmission, TestimonialsInput,
+    },
+    request_guards::basic::{ApiKey, ClientApiKey},
+    responders::cache::Cached,
+    utils::spam::{ContactProtection, SpamCheck, SpamForm},
+};
+
+const MAX_AUTHOR_LENGTH: usize = 100;
+const MAX_QUOTE_LENGTH: usize = 2000;
+
+/// get the testimonials of the signed in customer.
+/// <br />Use `status` with `pending`, `approved` or `rejected` for the moderation queue.
+#[openapi(tag = "Testimonial")]
+#[get("/testimonial?<limit>&<page>&<status>")]
+pub async fn get_all(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    limit: Option<i64>,
+    page: Option<i64>,
+    status: Option<TestimonialStatus>,
+) -> Result<Json<Vec<Testimonial>>, MyError> {
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let claims = &key.0; // Access the Claims struct
+    let id = &claims.sub;
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let testimonial_repo = container
+        .get::<Arc<dyn TestimonialRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match testimonial_repo.find(limit, page, oid, status).await {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// get the approved testimonials of the portfolio owning the api key.
+#[openapi(tag = "Testimonial")]
+#[get("/testimonials?<limit>&<page>")]
+pub async fn get(
+    container: &State<crate::Container>,
+    client_key: ClientApiKey,
+    limit: Option<i64>,
+    page: Option<i64>,
+) -> Result<Cached<Vec<Testimonial>>, MyError> {
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(100);
+    let page: i64 = page.unwrap_or(1);
+
+    let oid = client_oid(container, &client_key).await?;
+
+    let testimonial_repo = container
+        .get::<Arc<dyn TestimonialRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match testimonial_repo
+        .find(limit, page, oid, Some(TestimonialStatus::Approved))
+        .await
+    {
+        Ok(resp) => Ok(Cached(Json(resp))),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// submit a testimonial to the portfolio owning the api key.
+/// <br />It is pending until the owner approves it. The form is protected like the contact form,
+/// get a token from `GET /contact/token` when it is shown.
+#[openapi(tag = "Testimonial")]
+#[post("/testimonials", data = "<input>")]
+pub async fn submit(
+    container: &State<crate::Container>,
+    protection: &State<ContactProtection>,
+    client_key: ClientApiKey,
+    ip: Option<IpAddr>,
+    input: Json<TestimonialSubmission>,
+) -> Result<Json<String>, MyError> {
+    let oid = client_oid(container, &client_key).await?;
+
+    if input.author.trim().is_empty() || input.author.chars().count() > MAX_AUTHOR_LENGTH {
+        return Err(MyError::build(
+            400,
+            Some(format!(
+                "author is required and at most {MAX_AUTHOR_LENGTH} characters long"
+            )),
+        ));
+    }
+    if input.quote.trim().is_empty() || input.quote.chars().count() > MAX_QUOTE_LENGTH {
+        return Err(MyError::build(
+            400,
+            Some(format!(
+                "quote is required and at most {MAX_QUOTE_LENGTH} characters long"
+            )),
+        ));
+    }
+    if input
+        .rating
+        .is_some_and(|rating| !(1..=5).contains(&rating))
+    {
+        return Err(MyError::build(
+            400,
+            Some("rating must be between 1 and 5".to_string()),
+        ));
+    }
+
+    match protection.check(
+        SpamForm::Testimonial,
+        &client_key.0,
+        ip.map(|ip| ip.to_string()).as_deref(),
+        &input.token,
+        input.nonce.as_deref(),
+        input.website.as_deref(),
+    ) {
+        Ok(()) => {}
+        // Do not tell bots they were caught.
+        Err(SpamCheck::Honeypot) => return Ok(Json(ObjectId::new().to_string())),
+        Err(check) => return Err(spam_error(check)),
+    }
+
+    let testimonial_repo = container
+        .get::<Arc<dyn TestimonialRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let project_repo = container
+        .get::<Arc<dyn ProjectRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    // The linked project has to be one of the portfolio.
+    if let Some(project_id) = &input.project_id {
+        let project = match ObjectId::parse_str(project_id) {
+            Ok(project_oid) => project_repo.find_by_id(project_oid).await.ok().flatten(),
+            Err(_) => None,
+        };
+        if project.is_none_or(|project| project.customer_id != oid.to_string()) {
+            return Err(MyError::build(
+                400,
+                Some(format!("Project not found with _id {project_id}")),
+            ));
+        }
+    }
+
+    let order = match testimonial_repo.find(1, 1, oid, None).await {
+        Ok(resp) => resp.first().map_or(0, |last| last.order + 1),
+        Err(error) => return Err(MyError::build(400, Some(error.to_string()))),
+    };
+
+    match testimonial_repo.insert(input, oid, order).await {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Testimonial")]
+#[get("/testimonial/<id>")]
+pub async fn get_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+) -> Result<Json<Testimonial>, MyError> {
+    let (_, testimonial) = owned_testimonial(container, &key, id).await?;
+
+    Ok(Json(testimonial))
+}
+
+#[openapi(tag = "Testimonial")]
+#[patch("/testimonial/<id>", data = "<input>")]
+pub async fn patch_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+    input: Json<TestimonialInput>,
+) -> Result<Json<Testimonial>, MyError> {
+    let (oid, _) = owned_testimonial(container, &key, id).await?;
+
+    let testimonial_repo = container
+        .get::<Arc<dyn TestimonialRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match testimonial_repo.update_by_id(oid, input).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Testimonial not found with id {}", &id)),
+        )),
+    }
+}
+
+/// approve a testimonial, it then appears in the public listing.
+#[openapi(tag = "Testimonial")]
+#[patch("/testimonial/<id>/approve")]
+pub async fn approve(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+) -> Result<Json<Testimonial>, MyError> {
+    update_status(container, &key, id, TestimonialStatus::Approved).await
+}
+
+/// reject a testimonial, it is kept but never shown publicly.
+#[openapi(tag = "Testimonial")]
+#[patch("/testimonial/<id>/reject")]
+pub async fn reject(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+) -> Result<Json<Testimonial>, MyError> {
+    update_status(container, &key, id, TestimonialStatus::Rejected).await
+}
+
+#[openapi(tag = "Testimonial")]
+#[patch("/testimonial", data = "<input>")]
+pub async fn patch_many(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    input: Json<Vec<TestimonialsInput>>,
+) -> Result<Json<Vec<Testimonial>>, MyError> {
+    let Ok(customer_oid) = ObjectId::parse_str(&key.0.sub) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let testimonial_repo = container
+        .get::<Arc<dyn TestimonialRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    // Testimonials of other customers are skipped.
+    match testimonial_repo.update_many(customer_oid, input).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) | Err(_) => Err(MyError::build(400, Some("Failed to update".to_string()))),
+    }
+}
+
+#[openapi(tag = "Testimonial")]
+#[delete("/testimonial/<id>")]
+pub async fn delete_by_id(
+    container: &State<crate::Container>,
+    id: &str,
+    key: ApiKey,
+) -> Result<Json<Testimonial>, MyError> {
+    let (oid, _) = owned_testimonial(container, &key, id).await?;
+
+    let testimonial_repo = container
+        .get::<Arc<dyn TestimonialRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match testimonial_repo.delete_by_id(oid).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Testimonial not found with _id {}", &id)),
+        )),
+    }
+}
+
+async fn update_status(
+    container: &State<crate::Container>,
+    key: &ApiKey,
+    id: &str,
+    status: TestimonialStatus,
+) -> Result<Json<Testimonial>, MyError> {
+    let (oid, _) = owned_testimonial(container, key, id).await?;
+
+    let testimonial_repo = container
+        .get::<Arc<dyn TestimonialRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match testimonial_repo.update_status(oid, status).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Testimonial not found with id {}", &id)),
+        )),
+    }
+}
+
+// Only the owner of the portfolio moderates its testimonials.
+async fn owned_testimonial(
+    container: &State<crate::Container>,
+    key: &ApiKey,
+    id: &str,
+) -> Result<(ObjectId, Testimonial), MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid id format.".to_string())));
+    };
+
+    let testimonial_repo = container
+        .get::<Arc<dyn TestimonialRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    match testimonial_repo.find_by_id(oid).await {
+        Ok(Some(testimonial)) if testimonial.customer_id == key.0.sub => Ok((oid, testimonial)),
+        Ok(_) | Err(_) => Err(MyError::build(
+            404,
+            Some(format!("Testimonial not found with _id {}", &id)),
+        )),
+    }
+}
@@ -1,12 +1,138 @@
 use crate::models::{
+    archive::{ImportMode, ImportPlan, PortfolioArchive},
+    article::{Article, ArticleInput},
+    certification::{Certification, CertificationInput, CertificationsInput},
+    collection::{
+        CustomCollection, CustomCollectionInput, CustomEntriesInput, CustomEntry, CustomEntryInput,
+    },
+    contact::{ContactFilter, ContactInput, ContactMessage, ContactMessageUpdate},
     customer::{Customer, CustomerInput, CustomerUpdateInput},
+    cv::CvSettingsDocument,
+    education::{Education, EducationInput, EducationsInput},
+    event::ContentEvent,
     experience::{Experience, ExperienceInput, ExperiencesInput},
-    project::{Project, ProjectInput, ProjectsInput},
+    media::{Media, MediaInput},
+    project::{Project, ProjectFilter, ProjectInput, ProjectsInput},
+    search::SearchHit,
+    service::{Service, ServiceInput, ServicesInput},
     skill::{Skill, SkillInput, SkillsInput},
     social::{Social, SocialInput, SocialsInput},
+    testimonial::{
+        Testimonial, TestimonialInput, TestimonialStatus, TestimonialSubmission, TestimonialsInput,
+    },
+    webhook::{DeliveryAttempt, DeliveryStatus, Webhook, WebhookDelivery, WebhookInput},
 };
+use chrono::{DateTime, Utc};
 use mongodb::bson::oid::ObjectId;
 use rocket::serde::json::Json;
+use serde_json::{Map, Value};
+
+#[async_trait]
+pub trait ArchiveRepository {
+    async fn export(&self, oid: ObjectId) -> mongodb::error::Result<Option<PortfolioArchive>>;
+    /// Compare the archive with the portfolio, nothing is written.
+    async fn plan_import(
+        &self,
+        oid: ObjectId,
+        archive: PortfolioArchive,
+        version: u32,
+        mode: ImportMode,
+    ) -> mongodb::error::Result<ImportPlan>;
+    async fn apply_import(&self, oid: ObjectId, plan: &ImportPlan) -> mongodb::error::Result<()>;
+}
+
+#[async_trait]
+pub trait ArticleRepository {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        published_only: bool,
+    ) -> mongodb::error::Result<Vec<Article>>;
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Article>>;
+    async fn find_by_slug(
+        &self,
+        oid: ObjectId,
+        slug: String,
+        published_only: bool,
+    ) -> mongodb::error::Result<Option<Article>>;
+    async fn insert(
+        &self,
+        input: Json<ArticleInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String>;
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<ArticleInput>,
+    ) -> mongodb::error::Result<Option<Article>>;
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Article>>;
+}
+
+#[async_trait]
+pub trait CertificationRepository {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        include_expired: bool,
+    ) -> mongodb::error::Result<Vec<Certification>>;
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Certification>>;
+    async fn insert(
+        &self,
+        input: Json<CertificationInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String>;
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<CertificationInput>,
+    ) -> mongodb::error::Result<Option<Certification>>;
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<CertificationsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Certification>>>;
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Certification>>;
+}
+
+#[async_trait]
+pub trait ContactRepository {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        filter: ContactFilter,
+    ) -> mongodb::error::Result<Vec<ContactMessage>>;
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<ContactMessage>>;
+    async fn insert(
+        &self,
+        input: Json<ContactInput>,
+        oid: ObjectId,
+        ip: Option<String>,
+    ) -> mongodb::error::Result<ContactMessage>;
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<ContactMessageUpdate>,
+    ) -> mongodb::error::Result<Option<ContactMessage>>;
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<ContactMessage>>;
+}
+
+/// Told about every new contact message, e.g. to forward it to the portfolio owner.
+#[async_trait]
+pub trait ContactNotifier {
+    async fn notify(&self, message: &ContactMessage);
+}
+
+/// Told about every change of the content, see [`crate::db::events::ContentEvents`].
+#[async_trait]
+pub trait ContentEventListener {
+    async fn on_event(&self, event: &ContentEvent);
+}
 
 #[async_trait]
 pub trait CustomerRepository {
@@ -30,6 +156,115 @@ pub trait CustomerRepository {
     // ) -> mongodb::error::Result<Option<Customer>>;
 }
 
+#[async_trait]
+pub trait CustomCollectionRepository {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<CustomCollection>>;
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<CustomCollection>>;
+    async fn find_by_slug(
+        &self,
+        oid: ObjectId,
+        slug: String,
+    ) -> mongodb::error::Result<Option<CustomCollection>>;
+    async fn insert(
+        &self,
+        input: Json<CustomCollectionInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String>;
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<CustomCollectionInput>,
+    ) -> mongodb::error::Result<Option<CustomCollection>>;
+    /// Deletes the entries of the collection too.
+    async fn delete_by_id(&self, oid: ObjectId)
+        -> mongodb::error::Result<Option<CustomCollection>>;
+}
+
+#[async_trait]
+pub trait CustomEntryRepository {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        collection_oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<CustomEntry>>;
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<CustomEntry>>;
+    async fn insert(
+        &self,
+        input: Json<CustomEntryInput>,
+        oid: ObjectId,
+        collection_oid: ObjectId,
+    ) -> mongodb::error::Result<String>;
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<CustomEntryInput>,
+    ) -> mongodb::error::Result<Option<CustomEntry>>;
+    /// Only entries of the collection are updated.
+    async fn update_many(
+        &self,
+        collection_oid: ObjectId,
+        input: Json<Vec<CustomEntriesInput>>,
+    ) -> mongodb::error::Result<Option<Vec<CustomEntry>>>;
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<CustomEntry>>;
+}
+
+#[async_trait]
+pub trait CvRepository {
+    async fn find_settings(
+        &self,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Option<CvSettingsDocument>>;
+    async fn update_template(
+        &self,
+        oid: ObjectId,
+        template: &str,
+    ) -> mongodb::error::Result<Option<CvSettingsDocument>>;
+    /// `None` turns sharing off.
+    async fn update_share_token(
+        &self,
+        oid: ObjectId,
+        token: Option<&str>,
+    ) -> mongodb::error::Result<Option<CvSettingsDocument>>;
+    async fn find_customer_id_by_share_token(
+        &self,
+        token: &str,
+    ) -> mongodb::error::Result<Option<ObjectId>>;
+}
+
+#[async_trait]
+pub trait EducationRepository {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Education>>;
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Education>>;
+    async fn insert(
+        &self,
+        input: Json<EducationInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String>;
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<EducationInput>,
+    ) -> mongodb::error::Result<Option<Education>>;
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<EducationsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Education>>>;
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Education>>;
+}
+
 #[async_trait]
 pub trait ExperienceRepository {
     async fn find(
@@ -38,7 +273,20 @@ pub trait ExperienceRepository {
         page: i64,
         oid: ObjectId,
     ) -> mongodb::error::Result<Vec<Experience>>;
+    async fn find_fields(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        fields: Vec<String>,
+    ) -> mongodb::error::Result<Vec<Map<String, Value>>>;
     async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Experience>>;
+    /// Experiences of a customer using a skill.
+    async fn find_by_skill(
+        &self,
+        oid: ObjectId,
+        skill_oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Experience>>;
     async fn insert(
         &self,
         input: Json<ExperienceInput>,
@@ -63,7 +311,16 @@ pub trait ProjectRepository {
         limit: i64,
         page: i64,
         oid: ObjectId,
+        filter: ProjectFilter,
     ) -> mongodb::error::Result<Vec<Project>>;
+    async fn find_fields(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        fields: Vec<String>,
+        filter: ProjectFilter,
+    ) -> mongodb::error::Result<Vec<Map<String, Value>>>;
     async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Project>>;
     async fn insert(
         &self,
@@ -82,6 +339,33 @@ pub trait ProjectRepository {
     async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Project>>;
 }
 
+#[async_trait]
+pub trait ServiceRepository {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Service>>;
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Service>>;
+    async fn insert(
+        &self,
+        input: Json<ServiceInput>,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<String>;
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<ServiceInput>,
+    ) -> mongodb::error::Result<Option<Service>>;
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<ServicesInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Service>>>;
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Service>>;
+}
+
 #[async_trait]
 pub trait SkillRepository {
     async fn find(
@@ -133,3 +417,131 @@ pub trait SocialRepository {
     ) -> mongodb::error::Result<Option<Vec<Social>>>;
     async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Social>>;
 }
+
+#[async_trait]
+pub trait TestimonialRepository {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+        status: Option<TestimonialStatus>,
+    ) -> mongodb::error::Result<Vec<Testimonial>>;
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Testimonial>>;
+    async fn insert(
+        &self,
+        input: Json<TestimonialSubmission>,
+        oid: ObjectId,
+        order: i32,
+    ) -> mongodb::error::Result<String>;
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<TestimonialInput>,
+    ) -> mongodb::error::Result<Option<Testimonial>>;
+    async fn update_status(
+        &self,
+        oid: ObjectId,
+        status: TestimonialStatus,
+    ) -> mongodb::error::Result<Option<Testimonial>>;
+    async fn update_many(
+        &self,
+        customer_oid: ObjectId,
+        input: Json<Vec<TestimonialsInput>>,
+    ) -> mongodb::error::Result<Option<Vec<Testimonial>>>;
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Testimonial>>;
+}
+
+#[async_trait]
+pub trait MediaRepository {
+    async fn find(
+        &self,
+        limit: i64,
+        page: i64,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Vec<Media>>;
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Media>>;
+    async fn insert(&self, input: MediaInput, oid: ObjectId) -> mongodb::error::Result<String>;
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Media>>;
+}
+
+/// Where the bytes of uploaded media live, keyed by `Media.key`.
+#[async_trait]
+pub trait MediaStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> std::io::Result<()>;
+    async fn get(&self, key: &str) -> std::io::Result<Option<Vec<u8>>>;
+    async fn delete(&self, key: &str) -> std::io::Result<()>;
+}
+
+#[async_trait]
+pub trait SearchRepository {
+    /// Hits of every content type of the customer, most relevant first.
+    async fn search(
+        &self,
+        oid: ObjectId,
+        query: &str,
+        published_only: bool,
+    ) -> mongodb::error::Result<Vec<SearchHit>>;
+}
+
+/// Subscriptions of the customers and their deliveries, which are also the queue of the sender.
+#[async_trait]
+pub trait WebhookRepository {
+    async fn find(&self, oid: ObjectId) -> mongodb::error::Result<Vec<Webhook>>;
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Webhook>>;
+    /// Active webhooks of the customer subscribed to `event`.
+    async fn find_subscribed(
+        &self,
+        oid: ObjectId,
+        event: &str,
+    ) -> mongodb::error::Result<Vec<Webhook>>;
+    async fn insert(
+        &self,
+        input: Json<WebhookInput>,
+        oid: ObjectId,
+        secret: &str,
+    ) -> mongodb::error::Result<Webhook>;
+    async fn update_by_id(
+        &self,
+        oid: ObjectId,
+        input: Json<WebhookInput>,
+    ) -> mongodb::error::Result<Option<Webhook>>;
+    async fn update_secret(
+        &self,
+        oid: ObjectId,
+        secret: &str,
+    ) -> mongodb::error::Result<Option<Webhook>>;
+    /// Remove the webhook along with its deliveries.
+    async fn delete_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Webhook>>;
+    async fn enqueue(
+        &self,
+        webhook: &Webhook,
+        event: &str,
+        payload: String,
+        redelivery_of: Option<ObjectId>,
+        next_attempt_at: DateTime<Utc>,
+    ) -> mongodb::error::Result<WebhookDelivery>;
+    async fn find_deliveries(
+        &self,
+        limit: i64,
+        page: i64,
+        webhook_oid: ObjectId,
+        status: Option<DeliveryStatus>,
+    ) -> mongodb::error::Result<Vec<WebhookDelivery>>;
+    async fn find_delivery_by_id(
+        &self,
+        oid: ObjectId,
+    ) -> mongodb::error::Result<Option<WebhookDelivery>>;
+    /// Take the pending delivery due the longest, it is not handed out again before `lease_until`.
+    async fn claim_due_delivery(
+        &self,
+        lease_until: DateTime<Utc>,
+    ) -> mongodb::error::Result<Option<WebhookDelivery>>;
+    async fn record_attempt(
+        &self,
+        oid: ObjectId,
+        attempt: &DeliveryAttempt,
+        status: DeliveryStatus,
+        next_attempt_at: DateTime<Utc>,
+    ) -> mongodb::error::Result<Option<WebhookDelivery>>;
+}
@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use super::traits::{CustomerRepository, ExperienceRepository, ProjectRepository};
+use mongodb::bson::oid::ObjectId;
+use rocket::{serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use crate::{
+    errors::response::MyError,
+    models::{project::ProjectFilter, translation::MissingTranslation},
+    request_guards::basic::ApiKey,
+    utils::i18n::{is_locale, missing_fields, Translatable, DEFAULT_LOCALE},
+};
+
+// Upper bound of the documents checked per section.
+const SECTION_LIMIT: i64 = 1000;
+
+/// list the profile, projects and experiences fields that are not translated yet,
+/// for every locale of the signed in customer or only `locale`.
+#[openapi(tag = "Translation")]
+#[get("/translation/missing?<locale>")]
+pub async fn get_missing(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    locale: Option<String>,
+) -> Result<Json<Vec<MissingTranslation>>, MyError> {
+    let Ok(oid) = ObjectId::parse_str(&key.0.sub) else {
+        return Err(MyError::build(
+            400,
+            Some("Invalid user id format.".to_string()),
+        ));
+    };
+
+    let customer_repo = container
+        .get::<Arc<dyn CustomerRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let project_repo = container
+        .get::<Arc<dyn ProjectRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let experience_repo = container
+        .get::<Arc<dyn ExperienceRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))?;
+
+    let customer_doc = match customer_repo.find_customer_by_id(oid).await {
+        Ok(Some(customer_doc)) => customer_doc,
+        Ok(None) | Err(_) => {
+            return Err(MyError::build(
+                400,
+                Some(format!("Customer not found with _id {oid}")),
+            ))
+        }
+    };
+
+    let default_locale = customer_doc
+        .default_locale
+        .as_deref()
+        .unwrap_or(DEFAULT_LOCALE);
+    let locales: Vec<String> = match locale {
+        Some(locale) if !is_locale(&locale) => {
+            return Err(MyError::build(
+                400,
+                Some(format!("{locale} is not a valid locale")),
+            ))
+        }
+        Some(locale) => vec![locale],
+        None => customer_doc
+            .locales
+            .iter()
+            .filter(|locale| !locale.eq_ignore_ascii_case(default_locale))
+            .cloned()
+            .collect(),
+    };
+
+    let (projects, experiences) = match futures::try_join!(
+        project_repo.find(SECTION_LIMIT, 1, oid, ProjectFilter::default()),
+        experience_repo.find(SECTION_LIMIT, 1, oid)
+    ) {
+        Ok(resp) => resp,
+        Err(error) => return Err(MyError::build(400, Some(error.to_string()))),
+    };
+
+    let mut resp = missing("profile", [(&customer_doc.id, &customer_doc)], &locales);
+    resp.extend(missing(
+        "projects",
+        projects.iter().map(|project| (&project.id, project)),
+        &locales,
+    ));
+    resp.extend(missing(
+        "experiences",
+        experiences
+            .iter()
+            .map(|experience| (&experience.id, experience)),
+        &locales,
+    ));
+
+    Ok(Json(resp))
+}
+
+// One entry per document and locale with at least one untranslated field.
+fn missing<'a, T: Translatable + 'a>(
+    section: &str,
+    items: impl IntoIterator<Item = (&'a String, &'a T)>,
+    locales: &[String],
+) -> Vec<MissingTranslation> {
+    let mut resp = vec![];
+    for (id, item) in items {
+        for locale in locales {
+            let fields = missing_fields(item, locale);
+            if !fields.is_empty() {
+                resp.push(MissingTranslation {
+                    section: section.to_string(),
+                    id: id.clone(),
+                    locale: locale.clone(),
+                    fields,
+                });
+            }
+        }
+    }
+
+    resp
+}
@@ -0,0 +1,306 @@
+use std::sync::Arc;
+
+use super::traits::WebhookRepository;
+use chrono::Utc;
+use mongodb::bson::oid::ObjectId;
+use rocket::{serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use crate::{
+    errors::response::MyError,
+    models::{
+        event::validate_event_patterns,
+        webhook::{DeliveryStatus, Webhook, WebhookDelivery, WebhookInput},
+    },
+    notifications::delivery::{is_private_url, Deliveries},
+    request_guards::basic::ApiKey,
+    utils::{seo::is_http_url, util::generate_api_key},
+};
+
+/// get the webhooks of the signed in customer.
+#[openapi(tag = "Webhook")]
+#[get("/webhook")]
+pub async fn get_all(
+    container: &State<crate::Container>,
+    key: ApiKey,
+) -> Result<Json<Vec<Webhook>>, MyError> {
+    let oid = key_oid(&key)?;
+
+    match webhook_repo(container)?.find(oid).await {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Webhook")]
+#[get("/webhook/<id>")]
+pub async fn get_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+) -> Result<Json<Webhook>, MyError> {
+    let (_, webhook) = owned_webhook(container, &key, id).await?;
+
+    Ok(Json(webhook))
+}
+
+/// subscribe an url to content changes.
+/// <br />Each change is posted as JSON with `X-Webhook-Id`, `X-Webhook-Event`,
+/// `X-Webhook-Timestamp` and `X-Webhook-Signature: sha256=<hex>` headers, the signature being
+/// the HMAC-SHA256 of `<timestamp>.<body>` keyed with the returned `secret`. Events are
+/// `<section>.<action>`: sections are `profile`, `project`, `skill`, `social`, `experience`,
+/// `article`, `education`, `certification`, `testimonial`, `service`, `collection` and `entry`,
+/// actions are `created`, `updated`, `deleted` and `published`. Failed deliveries are retried
+/// with an exponential backoff.
+#[openapi(tag = "Webhook")]
+#[post("/webhook", data = "<input>")]
+pub async fn post(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    input: Json<WebhookInput>,
+) -> Result<Json<Webhook>, MyError> {
+    let oid = key_oid(&key)?;
+    validate(&input)?;
+
+    match webhook_repo(container)?
+        .insert(input, oid, &generate_api_key())
+        .await
+    {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Webhook")]
+#[patch("/webhook/<id>", data = "<input>")]
+pub async fn patch_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+    input: Json<WebhookInput>,
+) -> Result<Json<Webhook>, MyError> {
+    let (oid, _) = owned_webhook(container, &key, id).await?;
+    validate(&input)?;
+
+    match webhook_repo(container)?.update_by_id(oid, input).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) => Err(MyError::build(
+            400,
+            Some(format!("Webhook not found with id {}", &id)),
+        )),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// remove a webhook along with its delivery log.
+#[openapi(tag = "Webhook")]
+#[delete("/webhook/<id>")]
+pub async fn delete_by_id(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+) -> Result<Json<Webhook>, MyError> {
+    let (oid, _) = owned_webhook(container, &key, id).await?;
+
+    match webhook_repo(container)?.delete_by_id(oid).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Webhook not found with _id {}", &id)),
+        )),
+    }
+}
+
+/// replace the signing secret of a webhook, the previous one stops being used at once.
+#[openapi(tag = "Webhook")]
+#[post("/webhook/<id>/secret")]
+pub async fn rotate_secret(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+) -> Result<Json<Webhook>, MyError> {
+    let (oid, _) = owned_webhook(container, &key, id).await?;
+
+    match webhook_repo(container)?
+        .update_secret(oid, &generate_api_key())
+        .await
+    {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) => Err(MyError::build(
+            400,
+            Some(format!("Webhook not found with id {}", &id)),
+        )),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// send a `ping` event to a webhook and wait for its response, also when it is inactive.
+/// <br />The ping is logged but never retried.
+#[openapi(tag = "Webhook")]
+#[post("/webhook/<id>/ping")]
+pub async fn ping(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+) -> Result<Json<WebhookDelivery>, MyError> {
+    let (_, webhook) = owned_webhook(container, &key, id).await?;
+
+    match deliveries(container)?.ping(&webhook).await {
+        Ok(Some(resp)) => Ok(Json(resp)),
+        Ok(None) => Err(MyError::build(
+            500,
+            Some("The ping could not be logged".to_string()),
+        )),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+/// get the delivery log of a webhook, newest first.
+#[openapi(tag = "Webhook")]
+#[get("/webhook/<id>/deliveries?<limit>&<page>&<status>")]
+pub async fn get_deliveries(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+    limit: Option<i64>,
+    page: Option<i64>,
+    status: Option<DeliveryStatus>,
+) -> Result<Json<Vec<WebhookDelivery>>, MyError> {
+    // Setting default values
+    let limit: i64 = limit.unwrap_or(50);
+    let page: i64 = page.unwrap_or(1);
+
+    let (oid, _) = owned_webhook(container, &key, id).await?;
+
+    match webhook_repo(container)?
+        .find_deliveries(limit, page, oid, status)
+        .await
+    {
+        Ok(resp) => Ok(Json(resp)),
+        Err(error) => Err(MyError::build(400, Some(error.to_string()))),
+    }
+}
+
+#[openapi(tag = "Webhook")]
+#[get("/webhook/<id>/deliveries/<delivery_id>")]
+pub async fn get_delivery(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+    delivery_id: &str,
+) -> Result<Json<WebhookDelivery>, MyError> {
+    let (oid, _) = owned_webhook(container, &key, id).await?;
+    let delivery = webhook_delivery(container, oid, delivery_id).await?;
+
+    Ok(Json(delivery))
+}
+
+/// queue a delivery again with the same payload, e.g. after fixing the receiving end.
+/// <br />The new delivery points to the original one with `redelivery_of`.
+#[openapi(tag = "Webhook")]
+#[post("/webhook/<id>/deliveries/<delivery_id>/redeliver")]
+pub async fn redeliver(
+    container: &State<crate::Container>,
+    key: ApiKey,
+    id: &str,
+    delivery_id: &str,
+) -> Result<Json<WebhookDelivery>, MyError> {
+    let (oid, webhook) = owned_webhook(container, &key, id).await?;
+    let delivery = webhook_delivery(container, oid, delivery_id).await?;
+    let Ok(delivery_oid) = ObjectId::parse_str(&delivery.id) else {
+        return Err(MyError::build(400, Some("Invalid id format.".to_string())));
+    };
+
+    let resp = webhook_repo(container)?
+        .enqueue(
+            &webhook,
+            &delivery.event,
+            delivery.payload,
+            Some(delivery_oid),
+            Utc::now(),
+        )
+        .await
+        .map_err(|error| MyError::build(400, Some(error.to_string())))?;
+    deliveries(container)?.wake();
+
+    Ok(Json(resp))
+}
+
+fn validate(input: &WebhookInput) -> Result<(), MyError> {
+    if !is_http_url(input.url.trim()) {
+        return Err(MyError::build(
+            400,
+            Some(format!(
+                "Invalid url {}, expected an http(s) url",
+                input.url
+            )),
+        ));
+    }
+    if is_private_url(input.url.trim()) {
+        return Err(MyError::build(
+            400,
+            Some(format!(
+                "Invalid url {}, expected a public address",
+                input.url
+            )),
+        ));
+    }
+    validate_event_patterns(input.events.as_deref().unwrap_or_default())
+        .map_err(|error| MyError::build(400, Some(error)))
+}
+
+fn key_oid(key: &ApiKey) -> Result<ObjectId, MyError> {
+    ObjectId::parse_str(&key.0.sub)
+        .map_err(|_| MyError::build(400, Some("Invalid user id format.".to_string())))
+}
+
+fn webhook_repo(
+    container: &State<crate::Container>,
+) -> Result<&Arc<dyn WebhookRepository + Send + Sync>, MyError> {
+    container
+        .get::<Arc<dyn WebhookRepository + Send + Sync>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))
+}
+
+fn deliveries(container: &State<crate::Container>) -> Result<&Arc<Deliveries>, MyError> {
+    container
+        .get::<Arc<Deliveries>>()
+        .ok_or_else(|| MyError::build(500, Some("Service not found".to_string())))
+}
+
+// Webhooks hold secrets, only their owner may see them.
+async fn owned_webhook(
+    container: &State<crate::Container>,
+    key: &ApiKey,
+    id: &str,
+) -> Result<(ObjectId, Webhook), MyError> {
+    let Ok(oid) = ObjectId::parse_str(id) else {
+        return Err(MyError::build(400, Some("Invalid id format.".to_string())));
+    };
+
+    match webhook_repo(container)?.find_by_id(oid).await {
+        Ok(Some(webhook)) if webhook.customer_id == key.0.sub => Ok((oid, webhook)),
+        Ok(_) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Webhook not found with _id {}", &id)),
+        )),
+    }
+}
+
+async fn webhook_delivery(
+    container: &State<crate::Container>,
+    webhook_oid: ObjectId,
+    delivery_id: &str,
+) -> Result<WebhookDelivery, MyError> {
+    let Ok(oid) = ObjectId::parse_str(delivery_id) else {
+        return Err(MyError::build(400, Some("Invalid id format.".to_string())));
+    };
+
+    match webhook_repo(container)?.find_delivery_by_id(oid).await {
+        Ok(Some(delivery)) if delivery.webhook_id == webhook_oid.to_string() => Ok(delivery),
+        Ok(_) | Err(_) => Err(MyError::build(
+            400,
+            Some(format!("Delivery not found with _id {}", &delivery_id)),
+        )),
+    }
+}
@@ -0,0 +1,59 @@
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path::{Component, Path, PathBuf};
+
+use rocket::tokio::fs;
+
+use crate::routes::traits::MediaStore;
+
+/// Media store writing files below `MEDIA_DIR` on the local disk.
+pub struct LocalMediaStore {
+    root: PathBuf,
+}
+
+impl LocalMediaStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(PathBuf::from(
+            env::var("MEDIA_DIR").unwrap_or_else(|_| "media".to_string()),
+        ))
+    }
+
+    // Keys are generated by us, but never let one escape the media directory.
+    fn path(&self, key: &str) -> std::io::Result<PathBuf> {
+        let key = Path::new(key);
+        if !key.components().all(|c| matches!(c, Component::Normal(_))) {
+            return Err(Error::new(ErrorKind::InvalidInput, "Invalid media key"));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl MediaStore for LocalMediaStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> std::io::Result<()> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(path, bytes).await
+    }
+
+    async fn get(&self, key: &str) -> std::io::Result<Option<Vec<u8>>> {
+        match fs::read(self.path(key)?).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> std::io::Result<()> {
+        match fs::remove_file(self.path(key)?).await {
+            Err(error) if error.kind() != ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use std::env;
+use std::sync::Arc;
+
+use crate::routes::traits::MediaStore;
+
+pub mod local;
+#[cfg(feature = "s3")]
+pub mod s3;
+
+/// Pick the media store from `MEDIA_STORE` (`local` or, with the `s3` feature, `s3`).
+pub fn from_env() -> Arc<dyn MediaStore + Send + Sync> {
+    let media_store = env::var("MEDIA_STORE").unwrap_or_else(|_| "local".to_string());
+
+    match media_store.as_str() {
+        #[cfg(feature = "s3")]
+        "s3" => Arc::new(s3::S3MediaStore::from_env()),
+        "local" => Arc::new(local::LocalMediaStore::from_env()),
+        other => panic!("MEDIA_STORE {other} is not supported."),
+    }
+}
@@ -0,0 +1,51 @@
+use std::io::Error;
+
+use object_store::{
+    aws::{AmazonS3, AmazonS3Builder},
+    path::Path,
+    ObjectStore, PutPayload,
+};
+
+use crate::routes::traits::MediaStore;
+
+/// Media store backed by an S3-compatible bucket.
+/// <br />Configured with the usual `AWS_*` variables (`AWS_BUCKET_NAME`, `AWS_ENDPOINT`,
+/// `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`, `AWS_REGION`, ...).
+pub struct S3MediaStore {
+    store: AmazonS3,
+}
+
+impl S3MediaStore {
+    pub fn from_env() -> Self {
+        let store = AmazonS3Builder::from_env()
+            .build()
+            .expect("S3 media store is not configured.");
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl MediaStore for S3MediaStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> std::io::Result<()> {
+        self.store
+            .put(&Path::from(key), PutPayload::from(bytes))
+            .await
+            .map_err(Error::other)?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> std::io::Result<Option<Vec<u8>>> {
+        match self.store.get(&Path::from(key)).await {
+            Ok(result) => Ok(Some(result.bytes().await.map_err(Error::other)?.to_vec())),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(error) => Err(Error::other(error)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> std::io::Result<()> {
+        match self.store.delete(&Path::from(key)).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(error) => Err(Error::other(error)),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::io::{Cursor, Write};
+
+use mongodb::bson::{doc, oid::ObjectId, Bson};
+use serde_json::json;
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+use crate::db::archive::{remap_ids, strip_private_fields};
+use crate::models::archive::{ARCHIVE_FORMAT, ARCHIVE_VERSION};
+use crate::utils::archive::read_archive;
+
+fn archive_json(version: serde_json::Value) -> Vec<u8> {
+    serde_json::to_vec(&json!({
+        "format": ARCHIVE_FORMAT,
+        "version": version,
+        "exported_at": "2024-01-01T00:00:00+00:00",
+        "customer_id": "6500000000000000000000aa",
+        "profile": { "name": "Budi" },
+        "sections": { "skill": [{ "_id": { "$oid": "6500000000000000000000b1" }, "name": "Rust" }] },
+    }))
+    .unwrap()
+}
+
+#[test]
+fn current_version_is_read() {
+    let (archive, version, media_files) = read_archive(&archive_json(json!(1))).unwrap();
+
+    assert_eq!(version, 1);
+    assert_eq!(archive.version, ARCHIVE_VERSION);
+    assert_eq!(archive.sections["skill"].len(), 1);
+    assert!(media_files.is_empty());
+}
+
+#[test]
+fn unknown_versions_are_rejected() {
+    let newer = ARCHIVE_VERSION + 1;
+    assert_eq!(
+        read_archive(&archive_json(json!(newer))).unwrap_err(),
+        format!("Archive version {newer} is newer than the supported version {ARCHIVE_VERSION}")
+    );
+    for version in [json!(0), json!(-1), json!("1"), json!(null)] {
+        assert_eq!(
+            read_archive(&archive_json(version)).unwrap_err(),
+            "Missing archive version"
+        );
+    }
+}
+
+#[test]
+fn other_formats_are_rejected() {
+    let other = serde_json::to_vec(&json!({ "format": "wordpress", "version": 1 })).unwrap();
+    assert_eq!(
+        read_archive(&other).unwrap_err(),
+        format!("Not a {ARCHIVE_FORMAT} archive")
+    );
+    assert!(read_archive(b"[1, 2]").is_err());
+}
+
+#[test]
+fn zip_entries_leaving_the_archive_are_rejected() {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    writer
+        .start_file("portfolio.json", SimpleFileOptions::default())
+        .unwrap();
+    writer.write_all(&archive_json(json!(1))).unwrap();
+    writer
+        .start_file("media/../../escaped.png", SimpleFileOptions::default())
+        .unwrap();
+    writer.write_all(b"image").unwrap();
+    let bytes = writer.finish().unwrap().into_inner();
+
+    assert_eq!(
+        read_archive(&bytes).unwrap_err(),
+        "Invalid file name media/../../escaped.png in the archive"
+    );
+}
+
+#[test]
+fn private_profile_fields_are_stripped() {
+    let mut profile = doc! {
+        "_id": ObjectId::new(),
+        "name": "Budi",
+        "api_key": "key",
+        "cv_share_token": "token",
+        "password": "hash",
+        "email": "budi@example.com",
+        "intro": "Backend developer",
+    };
+
+    strip_private_fields(&mut profile);
+
+    assert_eq!(
+        profile,
+        doc! { "name": "Budi", "intro": "Backend developer" }
+    );
+}
+
+#[test]
+fn ids_are_remapped_in_values_and_strings() {
+    let media = ObjectId::parse_str("6500000000000000000000c1").unwrap();
+    let new_media = ObjectId::parse_str("6600000000000000000000c1").unwrap();
+    let skill = ObjectId::parse_str("6500000000000000000000b1").unwrap();
+    let untouched = ObjectId::parse_str("6500000000000000000000ff").unwrap();
+    let ids = HashMap::from([(media, new_media), (skill, skill)]);
+    let replacements = vec![(media.to_hex(), new_media.to_hex())];
+
+    let project = doc! {
+        "photo_id": media,
+        "photo_link": format!("/media/{}/photo.png", media.to_hex()),
+        "skill_ids": [skill, untouched],
+        "gallery": [{ "media_id": media, "caption": "Home page" }],
+        "order": 1,
+    };
+
+    let remapped = remap_ids(Bson::Document(project), &ids, &replacements);
+
+    assert_eq!(
+        remapped,
+        Bson::Document(doc! {
+            "photo_id": new_media,
+            "photo_link": format!("/media/{}/photo.png", new_media.to_hex()),
+            "skill_ids": [skill, untouched],
+            "gallery": [{ "media_id": new_media, "caption": "Home page" }],
+            "order": 1,
+        })
+    );
+}
@@ -0,0 +1,134 @@
+use chrono::{TimeZone, Utc};
+use rocket::{
+    http::{Header, Status},
+    local::blocking::Client,
+    serde::json::Json,
+};
+use serde_json::json;
+
+use crate::models::{social::Social, sparse::Sparse};
+use crate::responders::cache::{Cached, LastModified};
+
+fn social(name: &str, created_at: &str, updated_at: Option<&str>) -> Social {
+    Social {
+        id: "6500000000000000000000d1".to_string(),
+        customer_id: "6500000000000000000000aa".to_string(),
+        name: name.to_string(),
+        link: format!("https://{name}.com/budi"),
+        order: 1,
+        created_at: created_at.to_string(),
+        updated_at: updated_at.map(str::to_string),
+    }
+}
+
+#[get("/socials")]
+fn socials() -> Cached<Vec<Social>> {
+    Cached(Json(vec![
+        social(
+            "github",
+            "2024-01-01 10:00:00 UTC",
+            Some("2024-03-05 08:30:15.250 UTC"),
+        ),
+        social("mastodon", "2024-02-01 10:00:00 UTC", None),
+    ]))
+}
+
+#[get("/empty")]
+fn empty() -> Cached<Vec<Social>> {
+    Cached(Json(vec![]))
+}
+
+fn client() -> Client {
+    Client::untracked(rocket::build().mount("/", routes![socials, empty])).unwrap()
+}
+
+#[test]
+fn matching_etag_answers_not_modified() {
+    let client = client();
+
+    let response = client.get("/socials").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let etag = response.headers().get_one("ETag").unwrap().to_string();
+    assert!(etag.starts_with('"') && etag.ends_with('"'), "{etag}");
+
+    for if_none_match in [
+        etag.clone(),
+        format!("W/{etag}"),
+        format!("\"other\", {etag}"),
+        "*".to_string(),
+    ] {
+        let response = client
+            .get("/socials")
+            .header(Header::new("If-None-Match", if_none_match.clone()))
+            .dispatch();
+        assert_eq!(response.status(), Status::NotModified, "{if_none_match}");
+        assert_eq!(response.headers().get_one("ETag"), Some(etag.as_str()));
+        assert!(response.into_string().is_none());
+    }
+}
+
+#[test]
+fn other_etag_or_date_sends_the_content() {
+    let client = client();
+
+    let response = client
+        .get("/socials")
+        .header(Header::new("If-None-Match", "\"stale\""))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.into_string().unwrap().contains("mastodon"));
+
+    // only the ETag is trusted, a list that lost an item keeps its newest date
+    let response = client
+        .get("/socials")
+        .header(Header::new(
+            "If-Modified-Since",
+            "Tue, 05 Mar 2024 08:30:15 GMT",
+        ))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn last_modified_is_the_newest_change() {
+    let client = client();
+
+    let response = client.get("/socials").dispatch();
+    assert_eq!(
+        response.headers().get_one("Last-Modified"),
+        Some("Tue, 05 Mar 2024 08:30:15 GMT")
+    );
+    assert_eq!(
+        response.headers().get_one("Vary"),
+        Some("X-API-KEY, Accept-Language")
+    );
+
+    let response = client.get("/empty").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.headers().get_one("Last-Modified").is_none());
+}
+
+#[test]
+fn last_modified_of_sparse_documents() {
+    let partial: Sparse<Social> = Sparse::Partial(
+        serde_json::from_value(json!({
+            "_id": "6500000000000000000000d1",
+            "created_at": "2024-01-01 10:00:00 UTC",
+            "updated_at": "2024-01-02 10:00:00 UTC",
+        }))
+        .unwrap(),
+    );
+    assert_eq!(
+        partial.last_modified(),
+        Some(Utc.with_ymd_and_hms(2024, 1, 2, 10, 0, 0).unwrap())
+    );
+
+    // without the dates selected there is nothing to tell
+    let no_dates: Sparse<Social> =
+        Sparse::Partial(serde_json::from_value(json!({ "name": "github" })).unwrap());
+    assert_eq!(no_dates.last_modified(), None);
+
+    // an unreadable date is ignored
+    let full = Sparse::Full(social("github", "yesterday", None));
+    assert_eq!(full.last_modified(), None);
+}
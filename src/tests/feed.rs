@@ -0,0 +1,99 @@
+use chrono::{TimeZone, Utc};
+
+use crate::utils::feed::{
+    atom_xml, escape_xml, json_feed, rss_xml, sitemap_xml, Feed, FeedEntry, SitemapUrl,
+};
+
+fn feed() -> Feed {
+    let published = Utc.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap();
+    Feed {
+        title: "Jane's <blog>".to_string(),
+        subtitle: None,
+        author: "Jane & co".to_string(),
+        url: "https://jane.dev".to_string(),
+        feed_url: "https://jane.dev/feed.xml?format=atom&lang=en".to_string(),
+        entries: vec![FeedEntry {
+            title: "Tom & \"Jerry\"".to_string(),
+            url: "https://jane.dev/articles/tom-jerry".to_string(),
+            summary: Some("1 < 2".to_string()),
+            published,
+            updated: published,
+        }],
+    }
+}
+
+#[test]
+fn xml_special_characters_are_escaped() {
+    assert_eq!(
+        escape_xml("<a href=\"x\">Tom & Jerry's</a>"),
+        "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&apos;s&lt;/a&gt;"
+    );
+}
+
+#[test]
+fn characters_invalid_in_xml_are_dropped() {
+    assert_eq!(escape_xml("bell\u{7}\tline\n"), "bell\tline\n");
+}
+
+#[test]
+fn atom_feeds_escape_their_text() {
+    let xml = atom_xml(&feed());
+
+    assert!(
+        xml.contains("<title>Jane&apos;s &lt;blog&gt;</title>"),
+        "{xml}"
+    );
+    assert!(xml.contains("<name>Jane &amp; co</name>"), "{xml}");
+    assert!(
+        xml.contains("href=\"https://jane.dev/feed.xml?format=atom&amp;lang=en\""),
+        "{xml}"
+    );
+    assert!(
+        xml.contains("<title>Tom &amp; &quot;Jerry&quot;</title>"),
+        "{xml}"
+    );
+    assert!(xml.contains("<summary>1 &lt; 2</summary>"), "{xml}");
+    assert!(
+        xml.contains("<updated>2024-03-01T09:30:00Z</updated>"),
+        "{xml}"
+    );
+}
+
+#[test]
+fn rss_feeds_escape_their_text() {
+    let xml = rss_xml(&feed());
+
+    // the title is the description when there is no subtitle
+    assert!(
+        xml.contains("<description>Jane&apos;s &lt;blog&gt;</description>"),
+        "{xml}"
+    );
+    assert!(xml.contains("<description>1 &lt; 2</description>"), "{xml}");
+    assert!(
+        xml.contains("<pubDate>Fri, 1 Mar 2024 09:30:00 +0000</pubDate>"),
+        "{xml}"
+    );
+}
+
+#[test]
+fn json_feeds_keep_the_text_unescaped() {
+    let document = json_feed(&feed());
+
+    assert_eq!(document["title"], "Jane's <blog>");
+    assert_eq!(document["items"][0]["title"], "Tom & \"Jerry\"");
+    assert_eq!(document["items"][0]["content_text"], "1 < 2");
+}
+
+#[test]
+fn sitemap_urls_are_escaped() {
+    let xml = sitemap_xml(&[SitemapUrl {
+        loc: "https://jane.dev/?a=1&b=2".to_string(),
+        lastmod: Some(Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap()),
+    }]);
+
+    assert!(
+        xml.contains("<loc>https://jane.dev/?a=1&amp;b=2</loc>"),
+        "{xml}"
+    );
+    assert!(xml.contains("<lastmod>2024-03-01</lastmod>"), "{xml}");
+}
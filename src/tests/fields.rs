@@ -0,0 +1,70 @@
+use mongodb::bson::{doc, oid::ObjectId, DateTime};
+use serde_json::json;
+
+use crate::models::{experience::EXPERIENCE_FIELDS, project::PROJECT_FIELDS};
+use crate::utils::util::{document_to_json, fields_projection, parse_fields};
+
+#[test]
+fn selected_fields_are_trimmed_and_id_is_implied() {
+    assert_eq!(
+        parse_fields(" name, stack ,,_id,featured ", &PROJECT_FIELDS).unwrap(),
+        vec!["name", "stack", "featured"]
+    );
+    assert!(parse_fields("", &PROJECT_FIELDS).unwrap().is_empty());
+}
+
+#[test]
+fn unknown_fields_are_rejected() {
+    assert_eq!(
+        parse_fields("name,password", &PROJECT_FIELDS).unwrap_err(),
+        "Unknown field password"
+    );
+    // a field of another section
+    assert_eq!(
+        parse_fields("company", &PROJECT_FIELDS).unwrap_err(),
+        "Unknown field company"
+    );
+    assert!(parse_fields("company", &EXPERIENCE_FIELDS).is_ok());
+    // names are matched exactly
+    assert_eq!(
+        parse_fields("Name", &PROJECT_FIELDS).unwrap_err(),
+        "Unknown field Name"
+    );
+}
+
+#[test]
+fn projection_keeps_the_id_and_the_fields() {
+    let fields = parse_fields("name,stack", &PROJECT_FIELDS).unwrap();
+
+    assert_eq!(
+        fields_projection(&fields),
+        doc! { "_id": 1, "name": 1, "stack": 1 }
+    );
+    assert_eq!(fields_projection(&[]), doc! { "_id": 1 });
+}
+
+#[test]
+fn projected_documents_have_the_dto_shape() {
+    let id = ObjectId::parse_str("6500000000000000000000b1").unwrap();
+    let skill = ObjectId::parse_str("6500000000000000000000c1").unwrap();
+    let created_at = DateTime::from_millis(1_704_067_200_000);
+
+    let json = document_to_json(doc! {
+        "_id": id,
+        "name": "Portfolio",
+        "skill_ids": [skill],
+        "created_at": created_at,
+        "team_size": 3,
+    });
+
+    assert_eq!(
+        serde_json::Value::Object(json),
+        json!({
+            "_id": "6500000000000000000000b1",
+            "name": "Portfolio",
+            "skill_ids": ["6500000000000000000000c1"],
+            "created_at": "2024-01-01 00:00:00 UTC",
+            "team_size": 3,
+        })
+    );
+}
@@ -0,0 +1,47 @@
+use crate::utils::highlight::{highlight, search_terms};
+
+#[test]
+fn search_terms_are_lowercased_stemmed_and_longest_first() {
+    assert_eq!(
+        search_terms("Rust projects -OR rust \"API\""),
+        vec!["project", "rust", "api", "or"]
+    );
+    // too short once stripped, the suffix stays
+    assert_eq!(search_terms("bus"), vec!["bus"]);
+}
+
+#[test]
+fn terms_are_marked_case_insensitively() {
+    let terms = search_terms("rust");
+
+    assert_eq!(
+        highlight("I write Rust and rust.", &terms, 100).as_deref(),
+        Some("I write <mark>Rust</mark> and <mark>rust</mark>.")
+    );
+}
+
+#[test]
+fn snippets_are_escaped() {
+    let terms = search_terms("vec");
+
+    assert_eq!(
+        highlight("Use <Vec> & \"slices\"", &terms, 100).as_deref(),
+        Some("Use &lt;<mark>Vec</mark>&gt; &amp; &quot;slices&quot;")
+    );
+}
+
+#[test]
+fn long_texts_are_cut_around_the_first_match() {
+    let text = format!("{} needle {}", "hay ".repeat(50), "hay ".repeat(50));
+    let snippet = highlight(&text, &search_terms("needle"), 40).unwrap();
+
+    assert!(snippet.starts_with('…'), "{snippet}");
+    assert!(snippet.ends_with('…'), "{snippet}");
+    assert!(snippet.contains("<mark>needle</mark>"), "{snippet}");
+    assert!(snippet.chars().count() < 70, "{snippet}");
+}
+
+#[test]
+fn no_snippet_without_a_match() {
+    assert_eq!(highlight("nothing here", &search_terms("rust"), 100), None);
+}
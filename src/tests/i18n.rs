@@ -0,0 +1,123 @@
+use rocket::{http::Header, local::blocking::Client};
+
+use crate::models::customer::Customer;
+use crate::request_guards::locale::AcceptLanguage;
+use crate::utils::i18n::{
+    missing_fields, parse_accept_language, requested_locales, LocaleChain, Translations,
+};
+
+// A customer writing in English, with an Indonesian fallback and a partial French translation.
+fn customer() -> Customer {
+    let translations: Translations = serde_json::from_value(serde_json::json!({
+        "id": { "intro": "Halo, saya Budi", "about": "  " },
+        "fr": { "about": "Développeur Rust" },
+    }))
+    .unwrap();
+
+    Customer {
+        id: "6500000000000000000000aa".to_string(),
+        api_key: "key".to_string(),
+        name: "Budi".to_string(),
+        email: "budi@example.com".to_string(),
+        phone: None,
+        wa_link: None,
+        intro: Some("Hi, I am Budi".to_string()),
+        about: Some("Rust developer".to_string()),
+        profile_picture: None,
+        profile_picture_id: None,
+        profile_picture_srcset: None,
+        profile_picture_blurhash: None,
+        default_locale: Some("en".to_string()),
+        locales: vec!["id".to_string()],
+        translations,
+        site_url: None,
+        seo: None,
+        password: String::new(),
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+        updated_at: None,
+    }
+}
+
+fn localized(requested: &[&str]) -> Customer {
+    let requested: Vec<String> = requested.iter().map(|locale| locale.to_string()).collect();
+    let mut customer = customer();
+    LocaleChain::new(&requested, &customer).localize(&mut customer);
+    customer
+}
+
+#[test]
+fn accept_language_is_ordered_by_quality() {
+    assert_eq!(
+        parse_accept_language("fr;q=0.5, id-ID, en;q=0.8, de;q=0, *;q=0.1, en_US, nl;q=x"),
+        vec!["id-ID", "en", "fr"]
+    );
+    // equal qualities keep the order of the header
+    assert_eq!(
+        parse_accept_language("pt-BR;q=0.9, pt;q=0.9, en"),
+        vec!["en", "pt-BR", "pt"]
+    );
+    assert!(parse_accept_language("").is_empty());
+}
+
+#[test]
+fn locale_query_comes_before_the_header() {
+    let header = vec!["fr".to_string()];
+
+    assert_eq!(requested_locales(Some(" id "), &header), vec!["id", "fr"]);
+    assert_eq!(requested_locales(Some("not a locale"), &header), vec!["fr"]);
+    assert_eq!(requested_locales(None, &header), vec!["fr"]);
+}
+
+#[get("/")]
+fn locales(accept_language: AcceptLanguage) -> String {
+    accept_language.0.join(",")
+}
+
+#[test]
+fn accept_language_guard_reads_the_header() {
+    let client = Client::untracked(rocket::build().mount("/", routes![locales])).unwrap();
+
+    let response = client
+        .get("/")
+        .header(Header::new("Accept-Language", "en;q=0.4, id-ID"))
+        .dispatch();
+    assert_eq!(response.into_string().unwrap(), "id-ID,en");
+
+    let response = client.get("/").dispatch();
+    assert_eq!(response.into_string().unwrap(), "");
+}
+
+#[test]
+fn region_falls_back_to_its_language() {
+    let customer = localized(&["id-ID"]);
+
+    assert_eq!(customer.intro.as_deref(), Some("Halo, saya Budi"));
+    // a blank translation counts as missing
+    assert_eq!(customer.about.as_deref(), Some("Rust developer"));
+}
+
+#[test]
+fn missing_translations_fall_back_along_the_chain() {
+    let customer = localized(&["fr-CA"]);
+
+    assert_eq!(customer.about.as_deref(), Some("Développeur Rust"));
+    // not in French, the locales of the customer come next
+    assert_eq!(customer.intro.as_deref(), Some("Halo, saya Budi"));
+}
+
+#[test]
+fn default_locale_keeps_the_fields() {
+    let customer = localized(&["en-GB", "id"]);
+
+    assert_eq!(customer.intro.as_deref(), Some("Hi, I am Budi"));
+    assert_eq!(customer.about.as_deref(), Some("Rust developer"));
+}
+
+#[test]
+fn missing_fields_ignore_blank_translations() {
+    let customer = customer();
+
+    assert_eq!(missing_fields(&customer, "id"), vec!["about"]);
+    assert_eq!(missing_fields(&customer, "FR"), vec!["intro"]);
+    assert_eq!(missing_fields(&customer, "de"), vec!["intro", "about"]);
+}
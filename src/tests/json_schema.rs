@@ -0,0 +1,49 @@
+use serde_json::json;
+
+use crate::utils::json_schema::{validate_entry, validate_schema};
+
+fn schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "title": { "type": "string" },
+            "year": { "type": "integer", "minimum": 2000 }
+        },
+        "required": ["title"]
+    })
+}
+
+#[test]
+fn schemas_must_describe_an_object() {
+    assert!(validate_schema(&schema()).is_ok());
+
+    let error = validate_schema(&json!({ "type": "array" })).unwrap_err();
+    assert!(error.contains("\"type\": \"object\""), "{error}");
+}
+
+#[test]
+fn invalid_schemas_are_rejected() {
+    let error = validate_schema(&json!({ "type": "object", "minProperties": "two" })).unwrap_err();
+    assert!(error.starts_with("Invalid schema: "), "{error}");
+}
+
+#[test]
+fn valid_entries_pass() {
+    assert!(validate_entry(&schema(), &json!({ "title": "Talk", "year": 2024 })).is_ok());
+}
+
+#[test]
+fn entry_errors_are_listed_with_their_path() {
+    let error = validate_entry(&schema(), &json!({ "year": 1999 })).unwrap_err();
+
+    let errors: Vec<&str> = error.split("; ").collect();
+    assert_eq!(errors.len(), 2, "{error}");
+    assert!(
+        errors.iter().any(|error| error.contains("\"title\"")),
+        "{error}"
+    );
+    assert!(
+        errors.iter().any(|error| error.starts_with("/year: ")),
+        "{error}"
+    );
+}
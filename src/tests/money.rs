@@ -0,0 +1,45 @@
+use crate::utils::money::{format_amount, format_price, is_currency_code};
+
+#[test]
+fn amounts_are_grouped_and_round_ones_drop_their_cents() {
+    assert_eq!(format_amount(1500.0, "USD"), "$1,500");
+    assert_eq!(format_amount(1234567.5, "EUR"), "€1,234,567.50");
+    assert_eq!(format_amount(-42.25, "GBP"), "-£42.25");
+    assert_eq!(format_amount(999.99, "USD"), "$999.99");
+}
+
+#[test]
+fn currencies_follow_their_symbol_position_and_decimals() {
+    assert_eq!(format_amount(1500.0, "SEK"), "1,500 kr");
+    assert_eq!(format_amount(1500.4, "JPY"), "¥1,500");
+    assert_eq!(format_amount(15000000.0, "IDR"), "Rp15,000,000");
+    assert_eq!(format_amount(10.5, "XYZ"), "10.50 XYZ");
+}
+
+#[test]
+fn price_ranges() {
+    assert_eq!(
+        format_price(Some(1500.0), Some(3000.0), "EUR", Some("project")).as_deref(),
+        Some("€1,500 – €3,000 / project")
+    );
+    assert_eq!(
+        format_price(Some(50.0), None, "USD", Some("hour")).as_deref(),
+        Some("From $50 / hour")
+    );
+    assert_eq!(
+        format_price(None, Some(800.0), "GBP", Some("")).as_deref(),
+        Some("Up to £800")
+    );
+    assert_eq!(
+        format_price(Some(100.0), Some(100.0), "USD", None).as_deref(),
+        Some("$100")
+    );
+    assert_eq!(format_price(None, None, "USD", None), None);
+}
+
+#[test]
+fn currency_codes() {
+    assert!(is_currency_code("IDR"));
+    assert!(!is_currency_code("idr"));
+    assert!(!is_currency_code("EURO"));
+}
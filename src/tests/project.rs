@@ -0,0 +1,91 @@
+use crate::db::project::project_update;
+use crate::models::project::{ProjectInput, ProjectLink, ProjectLinkKind};
+
+// What the dashboard sends when a project is saved.
+fn dashboard_input() -> ProjectInput {
+    ProjectInput {
+        name: "Portfolio".to_string(),
+        description: Some("My site".to_string()),
+        link: None,
+        photo_link: None,
+        photo_id: None,
+        gallery: None,
+        links: None,
+        role: None,
+        team_size: None,
+        start_date: None,
+        end_date: None,
+        featured: None,
+        client: None,
+        order: 1,
+        stack: Some(vec!["rust".to_string()]),
+        skill_ids: None,
+        translations: None,
+        seo: None,
+    }
+}
+
+#[test]
+fn partial_project_update_keeps_unsent_fields() {
+    let set = project_update(&dashboard_input(), None, None, None).unwrap();
+
+    assert_eq!(set.get_str("name").unwrap(), "Portfolio");
+    assert_eq!(set.get_i32("order").unwrap(), 1);
+    for field in [
+        "gallery",
+        "links",
+        "role",
+        "team_size",
+        "start_date",
+        "end_date",
+        "featured",
+        "client",
+        "skill_ids",
+        "translations",
+        "seo",
+    ] {
+        assert!(!set.contains_key(field), "{field} should be left as is");
+    }
+}
+
+#[test]
+fn project_update_sets_sent_fields() {
+    let input = ProjectInput {
+        links: Some(vec![ProjectLink {
+            kind: ProjectLinkKind::Repository,
+            url: "https://github.com/jane/site".to_string(),
+            label: None,
+        }]),
+        featured: Some(false),
+        start_date: Some("2023-01-01T00:00:00Z".to_string()),
+        ..dashboard_input()
+    };
+    let set = project_update(&input, None, None, Some(vec![])).unwrap();
+
+    assert_eq!(set.get_array("links").unwrap().len(), 1);
+    assert!(set.get_array("gallery").unwrap().is_empty());
+    assert!(!set.get_bool("featured").unwrap());
+    assert!(set.get_datetime("start_date").is_ok());
+    assert!(!set.contains_key("end_date"));
+}
+
+#[test]
+fn project_update_keeps_the_uploaded_photo_of_an_unchanged_link() {
+    let stored = "/media/1/2.png";
+    let input = ProjectInput {
+        photo_link: Some(stored.to_string()),
+        ..dashboard_input()
+    };
+    let set = project_update(&input, Some(stored), None, None).unwrap();
+    assert_eq!(set.get_str("photo_link").unwrap(), stored);
+    assert!(!set.contains_key("photo_id"));
+    assert!(!set.contains_key("photo_srcset"));
+
+    let input = ProjectInput {
+        photo_link: Some("https://example.com/photo.png".to_string()),
+        ..dashboard_input()
+    };
+    let set = project_update(&input, Some(stored), None, None).unwrap();
+    assert!(set.is_null("photo_id"));
+    assert!(set.is_null("photo_srcset"));
+}
@@ -0,0 +1,233 @@
+use crate::models::{
+    customer::Customer,
+    experience::Experience,
+    resume::{Resume, ResumeProject, ResumeWork},
+    skill::{Skill, SkillProficiency},
+    social::Social,
+};
+use crate::utils::resume::{
+    merge_basics, merge_profile, merge_project, merge_skill, merge_work, resume_skills, to_resume,
+};
+
+// Dates as the DTOs carry them.
+const STARTED: &str = "2021-03-15 09:30:00 UTC";
+const LEFT: &str = "2023-06-01 00:00:00 UTC";
+
+fn customer() -> Customer {
+    Customer {
+        id: "6500000000000000000000aa".to_string(),
+        api_key: "key".to_string(),
+        name: "Budi".to_string(),
+        email: "budi@example.com".to_string(),
+        phone: Some("+62 812 0000".to_string()),
+        wa_link: None,
+        intro: Some("Backend developer".to_string()),
+        about: Some("I build APIs in Rust.".to_string()),
+        profile_picture: None,
+        profile_picture_id: None,
+        profile_picture_srcset: None,
+        profile_picture_blurhash: None,
+        default_locale: None,
+        locales: vec![],
+        translations: Default::default(),
+        site_url: None,
+        seo: None,
+        password: String::new(),
+        created_at: STARTED.to_string(),
+        updated_at: None,
+    }
+}
+
+fn experience() -> Experience {
+    Experience {
+        id: "6500000000000000000000b1".to_string(),
+        customer_id: "6500000000000000000000aa".to_string(),
+        company: "Acme".to_string(),
+        work_type: "Full-time".to_string(),
+        location: "Jakarta".to_string(),
+        start_date: STARTED.to_string(),
+        end_date: Some(LEFT.to_string()),
+        position: "Engineer".to_string(),
+        description: Some(vec!["Shipped the API".to_string()]),
+        order: 1,
+        skill_ids: vec![],
+        project_ids: vec![],
+        translations: Default::default(),
+        created_at: STARTED.to_string(),
+        updated_at: None,
+    }
+}
+
+fn skill(name: &str, category: Option<&str>, proficiency: SkillProficiency) -> Skill {
+    Skill {
+        id: "6500000000000000000000c1".to_string(),
+        customer_id: "6500000000000000000000aa".to_string(),
+        name: name.to_string(),
+        category: category.map(str::to_string),
+        proficiency: Some(proficiency),
+        years: None,
+        order: 1,
+        created_at: STARTED.to_string(),
+        updated_at: None,
+    }
+}
+
+fn social() -> Social {
+    Social {
+        id: "6500000000000000000000d1".to_string(),
+        customer_id: "6500000000000000000000aa".to_string(),
+        name: "GitHub".to_string(),
+        link: "https://github.com/budi".to_string(),
+        order: 1,
+        created_at: STARTED.to_string(),
+        updated_at: None,
+    }
+}
+
+#[test]
+fn exported_resume_imports_without_changes() {
+    let customer = customer();
+    let experience = experience();
+    let skills = vec![
+        skill("Rust", Some("Languages"), SkillProficiency::Expert),
+        skill("Go", Some("Languages"), SkillProficiency::Expert),
+        skill("Docker", None, SkillProficiency::Advanced),
+    ];
+    let social = social();
+
+    let resume = to_resume(
+        &customer,
+        std::slice::from_ref(&experience),
+        &skills,
+        std::slice::from_ref(&social),
+        &[],
+    );
+    // through JSON, the way the file comes back
+    let json = serde_json::to_string(&resume).unwrap();
+    let resume: Resume = serde_json::from_str(&json).unwrap();
+
+    let (_, changed) = merge_basics(&customer, &resume.basics);
+    assert!(changed.is_empty(), "basics changed {changed:?}");
+
+    assert_eq!(resume.work[0].start_date.as_deref(), Some("2021-03-15"));
+    let (input, changed) = merge_work(Some(&experience), &resume.work[0], 1).unwrap();
+    assert!(changed.is_empty(), "work changed {changed:?}");
+    // the time of the day survives, the resume only has the date
+    assert_eq!(input.start_date, "2021-03-15T09:30:00Z");
+
+    let imported = resume_skills(&resume.skills);
+    assert_eq!(imported.len(), skills.len());
+    for (skill, imported) in skills.iter().zip(&imported) {
+        let (_, changed) = merge_skill(Some(skill), imported, 1);
+        assert!(changed.is_empty(), "{} changed {changed:?}", skill.name);
+    }
+
+    let (_, changed) = merge_profile(Some(&social), &resume.basics.profiles[0], 1).unwrap();
+    assert!(changed.is_empty(), "profile changed {changed:?}");
+}
+
+#[test]
+fn partial_dates_start_on_the_first_day() {
+    let work = ResumeWork {
+        name: Some("Acme".to_string()),
+        position: Some("Engineer".to_string()),
+        start_date: Some("2020-07".to_string()),
+        end_date: Some("2022".to_string()),
+        ..Default::default()
+    };
+
+    let (input, changed) = merge_work(None, &work, 3).unwrap();
+
+    assert_eq!(input.start_date, "2020-07-01T00:00:00Z");
+    assert_eq!(input.end_date.as_deref(), Some("2022-01-01T00:00:00Z"));
+    assert_eq!(input.order, 3);
+    assert_eq!(
+        changed,
+        vec!["company", "position", "start_date", "end_date"]
+    );
+}
+
+#[test]
+fn invalid_dates_are_rejected() {
+    let work = ResumeWork {
+        name: Some("Acme".to_string()),
+        position: Some("Engineer".to_string()),
+        start_date: Some("March 2020".to_string()),
+        ..Default::default()
+    };
+    let error = merge_work(None, &work, 1).unwrap_err();
+    assert!(
+        error.starts_with("work: Acme: Invalid date March 2020"),
+        "{error}"
+    );
+
+    let project = ResumeProject {
+        name: Some("Portfolio".to_string()),
+        end_date: Some("2023-13-01".to_string()),
+        ..Default::default()
+    };
+    let error = merge_project(None, &project, 1).unwrap_err();
+    assert!(
+        error.starts_with("projects: Portfolio: Invalid date"),
+        "{error}"
+    );
+}
+
+#[test]
+fn entries_missing_required_fields_are_rejected() {
+    let no_start = ResumeWork {
+        name: Some("Acme".to_string()),
+        position: Some("Engineer".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        merge_work(None, &no_start, 1).unwrap_err(),
+        "work: Acme has no startDate"
+    );
+
+    let no_position = ResumeWork {
+        name: Some("Acme".to_string()),
+        position: Some("  ".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        merge_work(None, &no_position, 1).unwrap_err(),
+        "work: Acme has no position"
+    );
+
+    let no_name = ResumeProject::default();
+    assert_eq!(
+        merge_project(None, &no_name, 1).unwrap_err(),
+        "projects: an entry has no name"
+    );
+}
+
+#[test]
+fn unknown_skill_levels_are_dropped() {
+    let resume: Resume = serde_json::from_value(serde_json::json!({
+        "skills": [
+            { "name": "Web", "level": "Master", "keywords": ["HTML", " ", "CSS"] },
+            { "name": "Cooking", "level": "Michelin" },
+        ]
+    }))
+    .unwrap();
+
+    let skills = resume_skills(&resume.skills);
+
+    assert_eq!(
+        skills,
+        vec![
+            (
+                "HTML".to_string(),
+                Some("Web".to_string()),
+                Some(SkillProficiency::Expert)
+            ),
+            (
+                "CSS".to_string(),
+                Some("Web".to_string()),
+                Some(SkillProficiency::Expert)
+            ),
+            ("Cooking".to_string(), None, None),
+        ]
+    );
+}
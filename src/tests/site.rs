@@ -0,0 +1,157 @@
+use std::fs;
+use std::path::PathBuf;
+
+use sha2::{Digest, Sha256};
+
+use crate::models::site::{SiteContent, SiteProfile};
+use crate::utils::site::{build_site, write_dir, write_zip, SiteFiles, Theme};
+
+fn content() -> SiteContent {
+    SiteContent {
+        profile: SiteProfile {
+            name: "Budi".to_string(),
+            email: "budi@example.com".to_string(),
+            phone: None,
+            wa_link: None,
+            intro: Some("Backend developer".to_string()),
+            about: None,
+            profile_picture: None,
+            profile_picture_srcset: None,
+            profile_picture_blurhash: None,
+        },
+        projects: vec![],
+        experiences: vec![],
+        skills: vec![],
+        socials: vec![],
+        base_url: "https://budi.dev/portfolio".to_string(),
+        base_path: "/portfolio".to_string(),
+        locale: "en".to_string(),
+        generated_at: "2024-01-01T00:00:00Z".to_string(),
+    }
+}
+
+// An empty directory of its own for a test, removed first in case a previous run left it.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("portfolio-site-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn short_hash(bytes: &[u8]) -> String {
+    hex::encode(&Sha256::digest(bytes)[..4])
+}
+
+// Tera escapes the slashes of the urls it prints in HTML, browsers read them back the same.
+fn html(files: &SiteFiles, path: &str) -> String {
+    String::from_utf8(files[path].clone())
+        .unwrap()
+        .replace("&#x2F;", "/")
+}
+
+#[test]
+fn assets_get_hashed_names_in_the_html() {
+    let files = build_site(&Theme::built_in(), &content(), SiteFiles::new()).unwrap();
+
+    let css = files
+        .iter()
+        .find_map(|(path, bytes)| {
+            let name = path.strip_prefix("assets/style.")?.strip_suffix(".css")?;
+            Some((name.to_string(), bytes))
+        })
+        .expect("the stylesheet is written under a hashed name");
+    assert_eq!(css.0, short_hash(css.1));
+    assert!(!files.contains_key("assets/style.css"));
+
+    let index = html(&files, "index.html");
+    assert!(
+        index.contains(&format!("/portfolio/assets/style.{}.css", css.0)),
+        "{index}"
+    );
+    for path in ["404.html", "sitemap.xml", "feed.xml"] {
+        assert!(files.contains_key(path), "{path} is missing");
+    }
+}
+
+#[test]
+fn theme_assets_keep_their_folder() {
+    let dir = scratch_dir("theme");
+    fs::create_dir_all(dir.join("assets/css")).unwrap();
+    fs::write(dir.join("assets/css/site.css"), "body { margin: 0 }").unwrap();
+    fs::write(
+        dir.join("index.html"),
+        r#"<link href="{{ asset(path='/css/site.css') }}">"#,
+    )
+    .unwrap();
+    fs::write(dir.join("about.html"), "{{ page }}").unwrap();
+
+    let files = build_site(
+        &Theme::from_dir(&dir).unwrap(),
+        &content(),
+        SiteFiles::new(),
+    )
+    .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let hashed = format!("css/site.{}.css", short_hash(b"body { margin: 0 }"));
+    assert!(files.contains_key(&format!("assets/{hashed}")));
+    assert_eq!(
+        html(&files, "index.html"),
+        format!(r#"<link href="/portfolio/assets/{hashed}">"#)
+    );
+    assert_eq!(html(&files, "about/index.html"), "/about/");
+}
+
+#[test]
+fn unknown_assets_fail_the_build() {
+    let dir = scratch_dir("unknown-asset");
+    fs::write(
+        dir.join("index.html"),
+        "{{ asset(path='../../etc/passwd') }}",
+    )
+    .unwrap();
+
+    let result = build_site(
+        &Theme::from_dir(&dir).unwrap(),
+        &content(),
+        SiteFiles::new(),
+    );
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn paths_leaving_the_output_are_rejected() {
+    for path in [
+        "../escaped.png",
+        "media/../../escaped.png",
+        "/etc/escaped.png",
+    ] {
+        let mut media = SiteFiles::new();
+        media.insert(path.to_string(), b"image".to_vec());
+        let files = build_site(&Theme::built_in(), &content(), media).unwrap();
+
+        let dir = scratch_dir("out");
+        let output = dir.join("site");
+        assert_eq!(
+            write_dir(&files, &output),
+            Err(format!("Invalid path {path}"))
+        );
+        assert!(!dir.join("escaped.png").exists());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(write_zip(&files), Err(format!("Invalid path {path}")));
+    }
+}
+
+#[test]
+fn media_are_copied_as_they_are() {
+    let mut media = SiteFiles::new();
+    media.insert("media/photo.png".to_string(), b"image".to_vec());
+
+    let files = build_site(&Theme::built_in(), &content(), media).unwrap();
+
+    assert_eq!(files["media/photo.png"], b"image".to_vec());
+    assert!(write_zip(&files).is_ok());
+}
@@ -0,0 +1,36 @@
+use crate::db::skill::skill_update;
+use crate::models::skill::{SkillInput, SkillProficiency};
+
+#[test]
+fn partial_skill_update_keeps_unsent_fields() {
+    let input = SkillInput {
+        name: "Rust".to_string(),
+        category: None,
+        proficiency: None,
+        years: None,
+        order: 2,
+    };
+    let set = skill_update(&input).unwrap();
+
+    assert_eq!(set.get_str("name").unwrap(), "Rust");
+    assert_eq!(set.get_i32("order").unwrap(), 2);
+    for field in ["category", "proficiency", "years"] {
+        assert!(!set.contains_key(field), "{field} should not be set");
+    }
+}
+
+#[test]
+fn skill_update_sets_sent_fields() {
+    let input = SkillInput {
+        name: "Rust".to_string(),
+        category: Some("backend".to_string()),
+        proficiency: Some(SkillProficiency::Expert),
+        years: Some(4),
+        order: 2,
+    };
+    let set = skill_update(&input).unwrap();
+
+    assert_eq!(set.get_str("category").unwrap(), "backend");
+    assert_eq!(set.get_str("proficiency").unwrap(), "expert");
+    assert_eq!(set.get_i32("years").unwrap(), 4);
+}
@@ -0,0 +1,129 @@
+use crate::models::{customer::Customer, experience::Experience, social::Social};
+use crate::utils::vcard::profile_vcard;
+
+fn customer() -> Customer {
+    Customer {
+        id: "65f000000000000000000001".to_string(),
+        api_key: "key".to_string(),
+        name: "Jane van Doe".to_string(),
+        email: "jane@example.com".to_string(),
+        phone: Some("+62 812-3456-7890".to_string()),
+        wa_link: None,
+        intro: Some("Rust, Go; and\\more\nnew line".to_string()),
+        about: None,
+        profile_picture: None,
+        profile_picture_id: None,
+        profile_picture_srcset: None,
+        profile_picture_blurhash: None,
+        default_locale: None,
+        locales: vec![],
+        translations: Default::default(),
+        site_url: Some("https://jane.dev".to_string()),
+        seo: None,
+        password: String::new(),
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+        updated_at: None,
+    }
+}
+
+fn experience(position: &str, end_date: Option<&str>) -> Experience {
+    Experience {
+        id: "65f000000000000000000002".to_string(),
+        customer_id: "65f000000000000000000001".to_string(),
+        company: "Acme, Inc.".to_string(),
+        work_type: "full_time".to_string(),
+        location: "Remote".to_string(),
+        start_date: "2020-01-01T00:00:00Z".to_string(),
+        end_date: end_date.map(str::to_string),
+        position: position.to_string(),
+        description: None,
+        order: 1,
+        skill_ids: vec![],
+        project_ids: vec![],
+        translations: Default::default(),
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+        updated_at: None,
+    }
+}
+
+// Lines of the card with the folding undone.
+fn unfolded(card: &str) -> Vec<String> {
+    card.replace("\r\n ", "")
+        .split("\r\n")
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn text_values_escape_their_separators() {
+    let card = profile_vcard(&customer(), &[], &[], None);
+    let lines = unfolded(&card);
+
+    assert!(lines.contains(&"FN:Jane van Doe".to_string()), "{card}");
+    assert!(lines.contains(&"N:Doe;Jane van;;;".to_string()), "{card}");
+    assert!(
+        lines.contains(&r"NOTE:Rust\, Go\; and\\more\nnew line".to_string()),
+        "{card}"
+    );
+    assert!(
+        lines.contains(&"TEL;VALUE=uri;TYPE=cell:tel:+6281234567890".to_string()),
+        "{card}"
+    );
+}
+
+#[test]
+fn the_current_position_is_the_one_without_an_end_date() {
+    let experiences = [
+        experience("Intern", Some("2019-12-31T00:00:00Z")),
+        experience("Engineer", None),
+    ];
+    let card = profile_vcard(&customer(), &experiences, &[], None);
+    let lines = unfolded(&card);
+
+    assert!(lines.contains(&"TITLE:Engineer".to_string()), "{card}");
+    assert!(lines.contains(&"ORG:Acme\\, Inc.".to_string()), "{card}");
+}
+
+#[test]
+fn social_names_become_type_parameters() {
+    let socials = [Social {
+        id: "65f000000000000000000003".to_string(),
+        customer_id: "65f000000000000000000001".to_string(),
+        name: "Git Hub!".to_string(),
+        link: "https://github.com/jane".to_string(),
+        order: 1,
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+        updated_at: None,
+    }];
+    let card = profile_vcard(&customer(), &[], &socials, None);
+
+    assert!(
+        unfolded(&card).contains(&"URL;TYPE=github:https://github.com/jane".to_string()),
+        "{card}"
+    );
+}
+
+#[test]
+fn long_lines_are_folded_at_75_octets() {
+    let photo = format!("data:image/png;base64,{}", "A".repeat(200));
+    let card = profile_vcard(&customer(), &[], &[], Some(&photo));
+
+    assert!(card.starts_with("BEGIN:VCARD\r\nVERSION:4.0\r\n"), "{card}");
+    assert!(card.ends_with("END:VCARD\r\n"), "{card}");
+    for line in card.split("\r\n") {
+        assert!(line.len() <= 75, "{line:?} is longer than 75 octets");
+    }
+    assert!(unfolded(&card).contains(&format!("PHOTO:{photo}")));
+}
+
+#[test]
+fn folding_does_not_split_multibyte_characters() {
+    let mut customer = customer();
+    customer.intro = Some("é".repeat(100));
+    let card = profile_vcard(&customer, &[], &[], None);
+
+    for line in card.split("\r\n") {
+        assert!(line.len() <= 75, "{line:?} is longer than 75 octets");
+    }
+    assert!(unfolded(&card).contains(&format!("NOTE:{}", "é".repeat(100))));
+}
@@ -0,0 +1,60 @@
+use std::net::IpAddr;
+
+use crate::notifications::delivery::{is_private_url, is_public_ip};
+
+#[test]
+fn non_public_addresses_are_rejected() {
+    for ip in [
+        "127.0.0.1",
+        "10.1.2.3",
+        "172.16.0.1",
+        "192.168.1.1",
+        "169.254.169.254",
+        "100.64.0.1",
+        "0.0.0.0",
+        "::1",
+        "fd00::1",
+        "fe80::1",
+        "::ffff:127.0.0.1",
+        // IPv4-compatible
+        "::127.0.0.1",
+        "::a9fe:a9fe",
+        // NAT64
+        "64:ff9b::a9fe:a9fe",
+        "64:ff9b::10.0.0.1",
+        "64:ff9b:1::1",
+        // 6to4
+        "2002:7f00:1::1",
+        "2002:a9fe:a9fe::1",
+        // site-local
+        "fec0::1",
+        // Teredo
+        "2001:0:4136:e378:8000:63bf:3fff:fdd2",
+    ] {
+        let ip: IpAddr = ip.parse().unwrap();
+        assert!(!is_public_ip(ip), "{ip} should not be public");
+    }
+}
+
+#[test]
+fn public_addresses_are_accepted() {
+    for ip in [
+        "8.8.8.8",
+        "1.1.1.1",
+        "2606:4700:4700::1111",
+        "64:ff9b::808:808",
+        "2002:808:808::1",
+    ] {
+        let ip: IpAddr = ip.parse().unwrap();
+        assert!(is_public_ip(ip), "{ip} should be public");
+    }
+}
+
+#[test]
+fn urls_with_a_private_host_are_rejected() {
+    assert!(is_private_url("http://169.254.169.254/latest/meta-data"));
+    assert!(is_private_url("http://[::1]:8080/hook"));
+    assert!(is_private_url("http://localhost/hook"));
+    assert!(!is_private_url("https://example.com/hook"));
+    assert!(!is_private_url("https://93.184.216.34/hook"));
+}
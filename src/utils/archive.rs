@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read, Write};
+
+use serde_json::{Map, Value};
+use zip::{result::ZipError, write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::models::archive::{PortfolioArchive, ARCHIVE_FORMAT, ARCHIVE_VERSION};
+
+/// Name of the JSON document inside the ZIP archive.
+pub const ARCHIVE_FILE: &str = "portfolio.json";
+/// Folder of the media files inside the ZIP archive, followed by their media store key.
+pub const MEDIA_FOLDER: &str = "media/";
+
+// Uncompressed size read from an uploaded archive, guards against ZIP bombs.
+const MAX_UNCOMPRESSED_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Upgrades an archive of version `n + 1` to `n + 2`, `MIGRATIONS[0]` reads version 1.
+/// <br />Add one whenever the layout of `portfolio.json` or of a section changes, and bump
+/// `ARCHIVE_VERSION`.
+type Migration = fn(&mut Map<String, Value>) -> Result<(), String>;
+
+const MIGRATIONS: &[Migration] = &[];
+
+/// Media files of an archive by media store key.
+pub type MediaFiles = BTreeMap<String, Vec<u8>>;
+
+/// ZIP archive with `portfolio.json` and the media files under `media/`.
+pub fn write_zip(
+    archive: &PortfolioArchive,
+    media_files: Vec<(String, Vec<u8>)>,
+) -> Result<Vec<u8>, String> {
+    let json = serde_json::to_vec_pretty(archive).map_err(|error| error.to_string())?;
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let mut add = |name: &str, bytes: &[u8]| -> Result<(), ZipError> {
+        writer.start_file(name, options)?;
+        writer.write_all(bytes)?;
+        Ok(())
+    };
+    add(ARCHIVE_FILE, &json).map_err(|error| error.to_string())?;
+    for (key, bytes) in media_files {
+        add(&format!("{MEDIA_FOLDER}{key}"), &bytes).map_err(|error| error.to_string())?;
+    }
+
+    writer
+        .finish()
+        .map(Cursor::into_inner)
+        .map_err(|error| error.to_string())
+}
+
+/// Read a ZIP archive, or a bare `portfolio.json`, migrated to `ARCHIVE_VERSION`.
+/// <br />Returns the archive, its version before migration and the media files by key.
+pub fn read_archive(bytes: &[u8]) -> Result<(PortfolioArchive, u32, MediaFiles), String> {
+    let (json, media_files) = if bytes.starts_with(b"PK\x03\x04") {
+        read_zip(bytes)?
+    } else {
+        (bytes.to_vec(), BTreeMap::new())
+    };
+
+    let value: Value = serde_json::from_slice(&json)
+        .map_err(|error| format!("{ARCHIVE_FILE} is not valid JSON: {error}"))?;
+    let Value::Object(mut archive) = value else {
+        return Err(format!("{ARCHIVE_FILE} must be an object"));
+    };
+
+    if archive.get("format").and_then(Value::as_str) != Some(ARCHIVE_FORMAT) {
+        return Err(format!("Not a {ARCHIVE_FORMAT} archive"));
+    }
+    let version = archive
+        .get("version")
+        .and_then(Value::as_u64)
+        .and_then(|version| u32::try_from(version).ok())
+        .filter(|version| *version >= 1)
+        .ok_or_else(|| "Missing archive version".to_string())?;
+    if version > ARCHIVE_VERSION {
+        return Err(format!(
+            "Archive version {version} is newer than the supported version {ARCHIVE_VERSION}"
+        ));
+    }
+
+    for migration in &MIGRATIONS[(version - 1) as usize..] {
+        migration(&mut archive)?;
+    }
+    archive.insert("version".to_string(), Value::from(ARCHIVE_VERSION));
+
+    let archive = serde_json::from_value(Value::Object(archive))
+        .map_err(|error| format!("Invalid archive: {error}"))?;
+
+    Ok((archive, version, media_files))
+}
+
+fn read_zip(bytes: &[u8]) -> Result<(Vec<u8>, MediaFiles), String> {
+    let mut zip = ZipArchive::new(Cursor::new(bytes)).map_err(|error| error.to_string())?;
+
+    let mut json = None;
+    let mut media_files = BTreeMap::new();
+    let mut remaining = MAX_UNCOMPRESSED_SIZE;
+    for index in 0..zip.len() {
+        let file = zip.by_index(index).map_err(|error| error.to_string())?;
+        if file.is_dir() {
+            continue;
+        }
+        // `enclosed_name` rejects absolute paths and `..`
+        let Some(name) = file
+            .enclosed_name()
+            .and_then(|path| path.to_str().map(|name| name.replace('\\', "/")))
+        else {
+            return Err(format!("Invalid file name {} in the archive", file.name()));
+        };
+
+        let mut content = vec![];
+        file.take(remaining + 1)
+            .read_to_end(&mut content)
+            .map_err(|error| error.to_string())?;
+        remaining = remaining
+            .checked_sub(content.len() as u64)
+            .ok_or_else(|| "The archive is too large once uncompressed".to_string())?;
+
+        if name == ARCHIVE_FILE {
+            json = Some(content);
+        } else if let Some(key) = name.strip_prefix(MEDIA_FOLDER) {
+            media_files.insert(key.to_string(), content);
+        }
+    }
+
+    let json = json.ok_or_else(|| format!("{ARCHIVE_FILE} is missing from the archive"))?;
+
+    Ok((json, media_files))
+}
@@ -0,0 +1,254 @@
+use std::{env, fs, path::Path};
+
+use tera::{Context, Tera};
+
+use crate::models::{
+    customer::Customer,
+    cv::{Cv, CvEntry, CvLink, CvSkillGroup},
+    education::Education,
+    experience::Experience,
+    project::Project,
+    skill::Skill,
+    social::Social,
+};
+use crate::utils::util::{parse_datetime, template_error};
+
+// Templates shipped with the binary, by name.
+const BUILT_IN_TEMPLATES: [(&str, &str); 2] = [
+    ("classic", include_str!("../templates/cv/classic.html")),
+    ("modern", include_str!("../templates/cv/modern.html")),
+];
+
+/// HTML templates of the CV, the built-in ones and every `<name>.html` of `CV_TEMPLATE_DIR`.
+/// <br />Templates are Tera templates rendered with a `Cv`, a file named like a built-in one
+/// replaces it.
+pub struct CvTemplates {
+    tera: Tera,
+    names: Vec<String>,
+}
+
+impl CvTemplates {
+    pub fn from_env() -> Self {
+        let mut templates: Vec<(String, String)> = BUILT_IN_TEMPLATES
+            .iter()
+            .map(|(name, source)| (name.to_string(), source.to_string()))
+            .collect();
+
+        if let Some(dir) = env::var("CV_TEMPLATE_DIR")
+            .ok()
+            .filter(|dir| !dir.is_empty())
+        {
+            match read_templates(Path::new(&dir)) {
+                Ok(custom) => {
+                    for (name, source) in custom {
+                        templates.retain(|(known, _)| *known != name);
+                        templates.push((name, source));
+                    }
+                }
+                Err(error) => eprintln!("Failed to read the CV templates of {dir}: {error}"),
+            }
+        }
+
+        let mut tera = Tera::default();
+        let mut names = vec![];
+        for (name, source) in templates {
+            // the `.html` suffix turns autoescaping on
+            match tera.add_raw_template(&format!("{name}.html"), &source) {
+                Ok(()) => names.push(name),
+                Err(error) => eprintln!("Invalid CV template {name}: {}", template_error(error)),
+            }
+        }
+        names.sort();
+
+        Self { tera, names }
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.iter().any(|known| known == name)
+    }
+
+    pub fn render(&self, name: &str, cv: &Cv) -> Result<String, String> {
+        let context = Context::from_serialize(cv).map_err(|error| error.to_string())?;
+        self.tera
+            .render(&format!("{name}.html"), &context)
+            .map_err(template_error)
+    }
+}
+
+fn read_templates(dir: &Path) -> std::io::Result<Vec<(String, String)>> {
+    let mut templates = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|extension| extension.to_str()) != Some("html") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        templates.push((name.to_string(), fs::read_to_string(&path)?));
+    }
+
+    Ok(templates)
+}
+
+/// CV of a portfolio, sections are expected in their display order.
+pub fn to_cv(
+    customer: &Customer,
+    socials: &[Social],
+    experiences: &[Experience],
+    educations: &[Education],
+    skills: &[Skill],
+    projects: &[Project],
+    locale: Option<String>,
+) -> Cv {
+    let experiences = experiences
+        .iter()
+        .map(|experience| CvEntry {
+            title: experience.position.clone(),
+            subtitle: Some(experience.company.clone()),
+            location: [experience.location.as_str(), experience.work_type.as_str()]
+                .into_iter()
+                .map(str::trim)
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join(", ")
+                .into(),
+            period: period(Some(&experience.start_date), experience.end_date.as_deref()),
+            highlights: experience.description.clone().unwrap_or_default(),
+            ..Default::default()
+        })
+        .map(without_blank_fields)
+        .collect();
+
+    let educations = educations
+        .iter()
+        .map(|education| CvEntry {
+            title: match &education.field {
+                Some(field) if !field.trim().is_empty() => {
+                    format!("{}, {}", education.degree, field)
+                }
+                _ => education.degree.clone(),
+            },
+            subtitle: Some(education.institution.clone()),
+            period: period(Some(&education.start_date), education.end_date.as_deref()),
+            summary: education.grade.clone(),
+            highlights: education.description.clone().unwrap_or_default(),
+            ..Default::default()
+        })
+        .map(without_blank_fields)
+        .collect();
+
+    let projects = projects
+        .iter()
+        .map(|project| CvEntry {
+            title: project.name.clone(),
+            subtitle: match (&project.role, &project.client) {
+                (Some(role), Some(client)) => Some(format!("{role}, {client}")),
+                (role, client) => role.clone().or_else(|| client.clone()),
+            },
+            period: project
+                .start_date
+                .as_deref()
+                .and_then(|start| period(Some(start), project.end_date.as_deref())),
+            url: project.link.clone(),
+            summary: project.description.clone(),
+            tags: project.stack.clone().unwrap_or_default(),
+            ..Default::default()
+        })
+        .map(without_blank_fields)
+        .collect();
+
+    Cv {
+        name: customer.name.clone(),
+        email: customer.email.clone(),
+        phone: customer.phone.clone(),
+        intro: customer.intro.clone(),
+        about: customer.about.clone(),
+        picture: customer.profile_picture.clone(),
+        links: socials
+            .iter()
+            .map(|social| CvLink {
+                name: social.name.clone(),
+                url: social.link.clone(),
+            })
+            .collect(),
+        experiences,
+        educations,
+        projects,
+        skills: skill_groups(skills),
+        locale,
+    }
+}
+
+// Skills by category in the order of their first skill, skills without one come last.
+fn skill_groups(skills: &[Skill]) -> Vec<CvSkillGroup> {
+    let mut groups: Vec<CvSkillGroup> = vec![];
+    let mut uncategorized = vec![];
+    for skill in skills {
+        match skill
+            .category
+            .as_deref()
+            .filter(|category| !category.trim().is_empty())
+        {
+            Some(category) => match groups
+                .iter_mut()
+                .find(|group| group.category.as_deref() == Some(category))
+            {
+                Some(group) => group.skills.push(skill.name.clone()),
+                None => groups.push(CvSkillGroup {
+                    category: Some(category.to_string()),
+                    skills: vec![skill.name.clone()],
+                }),
+            },
+            None => uncategorized.push(skill.name.clone()),
+        }
+    }
+    if !uncategorized.is_empty() {
+        groups.push(CvSkillGroup {
+            category: None,
+            skills: uncategorized,
+        });
+    }
+
+    groups
+}
+
+/// `Mar 2021 – Present` from DTO dates, or only the start when it ended the same month.
+pub fn period(start: Option<&str>, end: Option<&str>) -> Option<String> {
+    let start = parse_datetime(start?)?.format("%b %Y").to_string();
+    let end = match end {
+        Some(end) => parse_datetime(end)?.format("%b %Y").to_string(),
+        None => "Present".to_string(),
+    };
+
+    if start == end {
+        Some(start)
+    } else {
+        Some(format!("{start} – {end}"))
+    }
+}
+
+fn without_blank_fields(mut entry: CvEntry) -> CvEntry {
+    for field in [
+        &mut entry.subtitle,
+        &mut entry.location,
+        &mut entry.url,
+        &mut entry.summary,
+    ] {
+        if field
+            .as_deref()
+            .is_some_and(|value| value.trim().is_empty())
+        {
+            *field = None;
+        }
+    }
+    entry
+        .highlights
+        .retain(|highlight| !highlight.trim().is_empty());
+    entry.tags.retain(|tag| !tag.trim().is_empty());
+    entry
+}
@@ -0,0 +1,221 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+
+/// A page listed in `sitemap.xml`.
+pub struct SitemapUrl {
+    /// absolute url
+    pub loc: String,
+    pub lastmod: Option<DateTime<Utc>>,
+}
+
+/// A feed, entries are expected newest first.
+pub struct Feed {
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub author: String,
+    /// absolute url of the site, also the id of the feed
+    pub url: String,
+    /// absolute url the feed is served from
+    pub feed_url: String,
+    pub entries: Vec<FeedEntry>,
+}
+
+pub struct FeedEntry {
+    pub title: String,
+    /// absolute url, also the id of the entry
+    pub url: String,
+    pub summary: Option<String>,
+    pub published: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+pub fn sitemap_xml(urls: &[SitemapUrl]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for url in urls {
+        xml.push_str("  <url>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", escape_xml(&url.loc)));
+        if let Some(lastmod) = url.lastmod {
+            xml.push_str(&format!(
+                "    <lastmod>{}</lastmod>\n",
+                lastmod.format("%Y-%m-%d")
+            ));
+        }
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// Atom 1.0 document of the feed.
+pub fn atom_xml(feed: &Feed) -> String {
+    let updated = feed
+        .entries
+        .iter()
+        .map(|entry| entry.updated)
+        .max()
+        .unwrap_or_else(Utc::now);
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <feed xmlns=\"http://www.w3.org/2005/Atom\">\n",
+    );
+    xml.push_str(&format!("  <title>{}</title>\n", escape_xml(&feed.title)));
+    if let Some(subtitle) = &feed.subtitle {
+        xml.push_str(&format!(
+            "  <subtitle>{}</subtitle>\n",
+            escape_xml(subtitle)
+        ));
+    }
+    xml.push_str(&format!("  <id>{}</id>\n", escape_xml(&feed.url)));
+    xml.push_str(&format!(
+        "  <link rel=\"alternate\" href=\"{}\"/>\n",
+        escape_xml(&feed.url)
+    ));
+    xml.push_str(&format!(
+        "  <link rel=\"self\" href=\"{}\"/>\n",
+        escape_xml(&feed.feed_url)
+    ));
+    xml.push_str(&format!("  <updated>{}</updated>\n", rfc3339(updated)));
+    xml.push_str(&format!(
+        "  <author><name>{}</name></author>\n",
+        escape_xml(&feed.author)
+    ));
+    for entry in &feed.entries {
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!(
+            "    <title>{}</title>\n",
+            escape_xml(&entry.title)
+        ));
+        xml.push_str(&format!("    <id>{}</id>\n", escape_xml(&entry.url)));
+        xml.push_str(&format!(
+            "    <link rel=\"alternate\" href=\"{}\"/>\n",
+            escape_xml(&entry.url)
+        ));
+        xml.push_str(&format!(
+            "    <published>{}</published>\n",
+            rfc3339(entry.published)
+        ));
+        xml.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            rfc3339(entry.updated)
+        ));
+        if let Some(summary) = &entry.summary {
+            xml.push_str(&format!("    <summary>{}</summary>\n", escape_xml(summary)));
+        }
+        xml.push_str("  </entry>\n");
+    }
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// RSS 2.0 document of the feed.
+pub fn rss_xml(feed: &Feed) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n\
+         <channel>\n",
+    );
+    xml.push_str(&format!("  <title>{}</title>\n", escape_xml(&feed.title)));
+    xml.push_str(&format!("  <link>{}</link>\n", escape_xml(&feed.url)));
+    xml.push_str(&format!(
+        "  <description>{}</description>\n",
+        escape_xml(feed.subtitle.as_deref().unwrap_or(&feed.title))
+    ));
+    xml.push_str(&format!(
+        "  <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        escape_xml(&feed.feed_url)
+    ));
+    if let Some(updated) = feed.entries.iter().map(|entry| entry.updated).max() {
+        xml.push_str(&format!(
+            "  <lastBuildDate>{}</lastBuildDate>\n",
+            updated.to_rfc2822()
+        ));
+    }
+    for entry in &feed.entries {
+        xml.push_str("  <item>\n");
+        xml.push_str(&format!(
+            "    <title>{}</title>\n",
+            escape_xml(&entry.title)
+        ));
+        xml.push_str(&format!("    <link>{}</link>\n", escape_xml(&entry.url)));
+        xml.push_str(&format!(
+            "    <guid isPermaLink=\"true\">{}</guid>\n",
+            escape_xml(&entry.url)
+        ));
+        xml.push_str(&format!(
+            "    <pubDate>{}</pubDate>\n",
+            entry.published.to_rfc2822()
+        ));
+        if let Some(summary) = &entry.summary {
+            xml.push_str(&format!(
+                "    <description>{}</description>\n",
+                escape_xml(summary)
+            ));
+        }
+        xml.push_str("  </item>\n");
+    }
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+/// JSON Feed 1.1 document of the feed.
+pub fn json_feed(feed: &Feed) -> Value {
+    let items: Vec<Value> = feed
+        .entries
+        .iter()
+        .map(|entry| {
+            let mut item = json!({
+                "id": entry.url,
+                "url": entry.url,
+                "title": entry.title,
+                "date_published": rfc3339(entry.published),
+                "date_modified": rfc3339(entry.updated),
+            });
+            if let Some(summary) = &entry.summary {
+                item["summary"] = json!(summary);
+                // an item needs a content, the summary is all there is
+                item["content_text"] = json!(summary);
+            } else {
+                item["content_text"] = json!(entry.title);
+            }
+            item
+        })
+        .collect();
+
+    let mut document = json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": feed.title,
+        "home_page_url": feed.url,
+        "feed_url": feed.feed_url,
+        "authors": [{ "name": feed.author }],
+        "items": items,
+    });
+    if let Some(subtitle) = &feed.subtitle {
+        document["description"] = json!(subtitle);
+    }
+    document
+}
+
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // not allowed in XML 1.0
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn rfc3339(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
@@ -0,0 +1,104 @@
+// Suffixes removed from search terms so `projects` also highlights `project`.
+const SUFFIXES: [&str; 4] = ["ing", "es", "ed", "s"];
+
+/// Lowercased terms of a search query, without operators and with common suffixes removed.
+pub fn search_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| {
+            let term = term.to_lowercase();
+            SUFFIXES
+                .iter()
+                .find_map(|suffix| {
+                    term.strip_suffix(suffix)
+                        .filter(|stem| stem.chars().count() >= 3)
+                })
+                .map(str::to_string)
+                .unwrap_or(term)
+        })
+        .collect();
+    terms.sort();
+    terms.dedup();
+    // longest first, so overlapping terms mark as much as possible
+    terms.sort_by_key(|term| std::cmp::Reverse(term.chars().count()));
+
+    terms
+}
+
+/// Excerpt of about `width` characters around the first term found in `text`, HTML escaped
+/// with every term in `<mark>`. `None` when no term is found.
+pub fn highlight(text: &str, terms: &[String], width: usize) -> Option<String> {
+    let chars: Vec<char> = text.chars().collect();
+    // first char of the lowercase form keeps the positions of `chars`
+    let lower: Vec<char> = chars.iter().map(|c| lowercase(*c)).collect();
+    let terms: Vec<Vec<char>> = terms.iter().map(|term| term.chars().collect()).collect();
+
+    let match_at = |index: usize| {
+        terms
+            .iter()
+            .find(|term| !term.is_empty() && lower[index..].starts_with(term))
+            .map(|term| term.len())
+    };
+
+    let first = (0..lower.len()).find(|index| match_at(*index).is_some())?;
+
+    let mut start = first.saturating_sub(width / 3);
+    if start > 0 {
+        // start on a word
+        start = (start..first)
+            .find(|index| chars[*index - 1].is_whitespace())
+            .unwrap_or(first);
+    }
+    let mut end = (start + width).min(chars.len());
+    if end < chars.len() {
+        end = (first..end)
+            .rev()
+            .find(|index| chars[*index].is_whitespace())
+            .unwrap_or(end);
+    }
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push('…');
+    }
+    let mut index = start;
+    while index < end {
+        match match_at(index) {
+            Some(len) => {
+                let len = len.min(end - index);
+                snippet.push_str("<mark>");
+                chars[index..index + len]
+                    .iter()
+                    .for_each(|c| push_escaped(&mut snippet, *c));
+                snippet.push_str("</mark>");
+                index += len;
+            }
+            None => {
+                push_escaped(&mut snippet, chars[index]);
+                index += 1;
+            }
+        }
+    }
+    if end < chars.len() {
+        snippet.push('…');
+    }
+
+    Some(snippet)
+}
+
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn push_escaped(out: &mut String, c: char) {
+    match c {
+        '&' => out.push_str("&amp;"),
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '"' => out.push_str("&quot;"),
+        '\'' => out.push_str("&#39;"),
+        '\n' | '\r' | '\t' => out.push(' '),
+        c => out.push(c),
+    }
+}
@@ -0,0 +1,330 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+use crate::models::{customer::Customer, experience::Experience, project::Project, sparse::Sparse};
+
+/// Locale of the content when the customer has not set one.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Translated fields keyed by locale, e.g. `{"id": {"name": "Situs portofolio"}}`.
+pub type Translations = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Content with text fields that can be translated, the fields themselves hold the default locale.
+/// <br />List fields are translated with one item per line.
+pub trait Translatable {
+    const FIELDS: &'static [&'static str];
+
+    fn translations(&self) -> &Translations;
+    /// whether the field has a value in the default locale
+    fn has_field(&self, field: &str) -> bool;
+    fn set_field(&mut self, field: &str, value: String);
+}
+
+/// Locales tried in order when translating a response.
+#[derive(Debug, Clone)]
+pub struct LocaleChain {
+    locales: Vec<String>,
+    default_locale: String,
+}
+
+impl LocaleChain {
+    /// The requested locales, then the locales of the customer and finally its default one.
+    pub fn new(requested: &[String], customer: &Customer) -> Self {
+        let default_locale = customer
+            .default_locale
+            .clone()
+            .unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+
+        let mut locales: Vec<String> = vec![];
+        let mut push = |locale: &str| {
+            if !locales
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(locale))
+            {
+                locales.push(locale.to_string());
+            }
+        };
+        for locale in requested {
+            push(locale);
+            // `id-ID` falls back to `id`
+            if let Some((language, _)) = locale.split_once('-') {
+                push(language);
+            }
+        }
+        for locale in &customer.locales {
+            push(locale);
+        }
+        push(&default_locale);
+
+        Self {
+            locales,
+            default_locale,
+        }
+    }
+
+    // Translation of `field` in the first locale having one, `None` to keep the default locale.
+    fn pick<'a>(&self, translations: &'a Translations, field: &str) -> Option<&'a str> {
+        for locale in &self.locales {
+            if locale.eq_ignore_ascii_case(&self.default_locale) {
+                return None;
+            }
+            let value = translations
+                .iter()
+                .find(|(known, _)| known.eq_ignore_ascii_case(locale))
+                .and_then(|(_, fields)| fields.get(field))
+                .filter(|value| !value.trim().is_empty());
+            if let Some(value) = value {
+                return Some(value);
+            }
+        }
+
+        None
+    }
+
+    /// Translate the fields of `item` in place.
+    pub fn localize<T: Translatable>(&self, item: &mut T) {
+        for field in T::FIELDS {
+            if let Some(value) = self.pick(item.translations(), field) {
+                let value = value.to_string();
+                item.set_field(field, value);
+            }
+        }
+    }
+
+    // Same as `localize` for a document reduced with `?fields=`, it needs its `translations`.
+    fn localize_map<T: Translatable>(&self, item: &mut Map<String, Value>) {
+        let Some(translations) = item
+            .get("translations")
+            .and_then(|value| serde_json::from_value::<Translations>(value.clone()).ok())
+        else {
+            return;
+        };
+
+        for field in T::FIELDS {
+            let (Some(current), Some(value)) = (item.get(*field), self.pick(&translations, field))
+            else {
+                continue;
+            };
+            let value = match current {
+                Value::Array(_) => Value::Array(
+                    value
+                        .lines()
+                        .map(|line| Value::String(line.to_string()))
+                        .collect(),
+                ),
+                _ => Value::String(value.to_string()),
+            };
+            item.insert(field.to_string(), value);
+        }
+    }
+
+    /// Translate a page of documents, `drop_translations` removes the `translations` that were
+    /// only selected to localize documents reduced with `?fields=`.
+    pub fn localize_sparse<T: Translatable>(
+        &self,
+        items: &mut [Sparse<T>],
+        drop_translations: bool,
+    ) {
+        for item in items {
+            match item {
+                Sparse::Full(item) => self.localize(item),
+                Sparse::Partial(item) => {
+                    self.localize_map::<T>(item);
+                    if drop_translations {
+                        item.remove("translations");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Add `translations` to the fields selected with `?fields=`, returns whether it was missing.
+pub fn select_translations(fields: &mut Option<Vec<String>>) -> bool {
+    match fields {
+        Some(fields) if !fields.iter().any(|field| field == "translations") => {
+            fields.push("translations".to_string());
+            true
+        }
+        _ => false,
+    }
+}
+
+/// `?locale=` first, then the locales of the `Accept-Language` header.
+pub fn requested_locales(locale: Option<&str>, accept_language: &[String]) -> Vec<String> {
+    locale
+        .map(str::trim)
+        .filter(|locale| is_locale(locale))
+        .map(str::to_string)
+        .into_iter()
+        .chain(accept_language.iter().cloned())
+        .collect()
+}
+
+/// Locales of an `Accept-Language` header, most preferred first.
+pub fn parse_accept_language(header: &str) -> Vec<String> {
+    let mut locales: Vec<(String, f32)> = header
+        .split(',')
+        .filter_map(|part| {
+            let mut params = part.split(';');
+            let locale = params.next()?.trim();
+            let quality = params
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |quality| quality.trim().parse::<f32>().ok())?;
+            (is_locale(locale) && quality > 0.0).then(|| (locale.to_string(), quality))
+        })
+        .collect();
+    // stable, so equal qualities keep the order of the header
+    locales.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    locales.into_iter().map(|(locale, _)| locale).collect()
+}
+
+/// BCP 47 like tag, e.g. `en`, `id` or `pt-BR`.
+pub fn is_locale(tag: &str) -> bool {
+    let mut subtags = tag.split('-');
+    let language = subtags.next().unwrap_or_default();
+
+    (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+/// Check the default locale and the fallback locales of a customer.
+pub fn validate_locales(
+    default_locale: Option<&str>,
+    locales: Option<&[String]>,
+) -> Result<(), String> {
+    let mut all = default_locale
+        .into_iter()
+        .chain(locales.into_iter().flatten().map(String::as_str));
+    match all.find(|locale| !is_locale(locale)) {
+        Some(locale) => Err(format!("{locale} is not a valid locale")),
+        None => Ok(()),
+    }
+}
+
+/// Check the locales and that only translatable fields are translated.
+pub fn validate_translations(
+    translations: Option<&Translations>,
+    fields: &[&str],
+) -> Result<(), String> {
+    for (locale, translated) in translations.into_iter().flatten() {
+        if !is_locale(locale) {
+            return Err(format!("{locale} is not a valid locale"));
+        }
+        if let Some(field) = translated
+            .keys()
+            .find(|field| !fields.contains(&field.as_str()))
+        {
+            return Err(format!(
+                "{field} cannot be translated, expected one of {}",
+                fields.join(", ")
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Fields of `item` with a value in the default locale but no translation in `locale`.
+pub fn missing_fields<T: Translatable>(item: &T, locale: &str) -> Vec<String> {
+    let translated = item
+        .translations()
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(locale))
+        .map(|(_, fields)| fields);
+
+    T::FIELDS
+        .iter()
+        .filter(|field| item.has_field(field))
+        .filter(|field| {
+            translated
+                .and_then(|fields| fields.get(**field))
+                .is_none_or(|value| value.trim().is_empty())
+        })
+        .map(|field| field.to_string())
+        .collect()
+}
+
+fn is_filled(value: Option<&str>) -> bool {
+    value.is_some_and(|value| !value.trim().is_empty())
+}
+
+impl Translatable for Project {
+    const FIELDS: &'static [&'static str] = &["name", "description"];
+
+    fn translations(&self) -> &Translations {
+        &self.translations
+    }
+
+    fn has_field(&self, field: &str) -> bool {
+        match field {
+            "name" => !self.name.trim().is_empty(),
+            "description" => is_filled(self.description.as_deref()),
+            _ => false,
+        }
+    }
+
+    fn set_field(&mut self, field: &str, value: String) {
+        match field {
+            "name" => self.name = value,
+            "description" => self.description = Some(value),
+            _ => {}
+        }
+    }
+}
+
+impl Translatable for Experience {
+    const FIELDS: &'static [&'static str] = &["position", "description"];
+
+    fn translations(&self) -> &Translations {
+        &self.translations
+    }
+
+    fn has_field(&self, field: &str) -> bool {
+        match field {
+            "position" => !self.position.trim().is_empty(),
+            "description" => self
+                .description
+                .as_ref()
+                .is_some_and(|lines| !lines.is_empty()),
+            _ => false,
+        }
+    }
+
+    fn set_field(&mut self, field: &str, value: String) {
+        match field {
+            "position" => self.position = value,
+            "description" => self.description = Some(value.lines().map(str::to_string).collect()),
+            _ => {}
+        }
+    }
+}
+
+impl Translatable for Customer {
+    const FIELDS: &'static [&'static str] = &["intro", "about"];
+
+    fn translations(&self) -> &Translations {
+        &self.translations
+    }
+
+    fn has_field(&self, field: &str) -> bool {
+        match field {
+            "intro" => is_filled(self.intro.as_deref()),
+            "about" => is_filled(self.about.as_deref()),
+            _ => false,
+        }
+    }
+
+    fn set_field(&mut self, field: &str, value: String) {
+        match field {
+            "intro" => self.intro = Some(value),
+            "about" => self.about = Some(value),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::env;
+use std::io::Cursor;
+
+use image::{
+    codecs::{jpeg::JpegEncoder, webp::WebPEncoder},
+    imageops::FilterType,
+    DynamicImage, ImageDecoder, ImageFormat, ImageReader,
+};
+
+const VARIANT_JPEG_QUALITY: u8 = 85;
+
+pub struct ImageVariant {
+    pub width: u32,
+    pub height: u32,
+    /// `webp` or `jpg`
+    pub extension: &'static str,
+    pub bytes: Vec<u8>,
+}
+
+pub struct ProcessedImage {
+    /// the upload re-encoded in its own format, without metadata such as EXIF
+    pub original: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    /// from the smallest to the full size
+    pub variants: Vec<ImageVariant>,
+    pub blurhash: Option<String>,
+}
+
+/// Widths of the generated variants, from `MEDIA_IMAGE_WIDTHS`.
+pub fn variant_widths() -> Vec<u32> {
+    env::var("MEDIA_IMAGE_WIDTHS")
+        .unwrap_or_else(|_| "320,640,1024,1600".to_string())
+        .split(',')
+        .filter_map(|width| width.trim().parse().ok())
+        .collect()
+}
+
+/// Decode an uploaded image, strip its metadata and generate variants for `srcset`.
+/// <br />The `image` crate only encodes lossless WebP, which is often larger than a JPEG for
+/// photos, so an opaque variant is kept as a JPEG when that is smaller.
+/// <br />Returns `None` when the bytes are not an image we can decode.
+pub fn process_image(bytes: &[u8], widths: &[u32]) -> Option<ProcessedImage> {
+    let reader = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()?;
+    let format = reader.format()?;
+    let mut decoder = reader.into_decoder().ok()?;
+    let orientation = decoder.orientation().ok();
+    let mut image = DynamicImage::from_decoder(decoder).ok()?;
+    // The EXIF orientation is lost when re-encoding, so bake it into the pixels.
+    if let Some(orientation) = orientation {
+        image.apply_orientation(orientation);
+    }
+
+    let original = match format {
+        // GIFs carry no EXIF, and re-encoding would drop the animation.
+        ImageFormat::Gif => bytes.to_vec(),
+        _ => {
+            let mut original = vec![];
+            image
+                .write_to(&mut Cursor::new(&mut original), format)
+                .ok()?;
+            original
+        }
+    };
+
+    let mut sizes: Vec<u32> = widths
+        .iter()
+        .copied()
+        .filter(|width| *width < image.width())
+        .collect();
+    sizes.sort_unstable();
+    sizes.dedup();
+    sizes.push(image.width());
+
+    let mut variants = vec![];
+    for width in sizes {
+        let resized = if width < image.width() {
+            image.resize(width, u32::MAX, FilterType::Lanczos3)
+        } else {
+            image.clone()
+        };
+        let (extension, bytes) = encode_variant(&resized)?;
+        variants.push(ImageVariant {
+            width: resized.width(),
+            height: resized.height(),
+            extension,
+            bytes,
+        });
+    }
+
+    let thumbnail = image.thumbnail(32, 32).to_rgba8();
+    let blurhash = blurhash::encode(
+        4,
+        3,
+        thumbnail.width(),
+        thumbnail.height(),
+        thumbnail.as_raw(),
+    )
+    .ok();
+
+    Some(ProcessedImage {
+        original,
+        width: image.width(),
+        height: image.height(),
+        variants,
+        blurhash,
+    })
+}
+
+/// A JPEG fitting in `size` x `size` pixels, for contact cards which rarely read WebP.
+pub fn contact_photo(bytes: &[u8], size: u32) -> Option<Vec<u8>> {
+    let image = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()?
+        .decode()
+        .ok()?;
+    let image = if image.width() > size || image.height() > size {
+        image.resize(size, size, FilterType::Lanczos3)
+    } else {
+        image
+    };
+
+    let mut jpeg = vec![];
+    DynamicImage::ImageRgb8(image.to_rgb8())
+        .write_with_encoder(JpegEncoder::new_with_quality(&mut jpeg, 85))
+        .ok()?;
+    Some(jpeg)
+}
+
+// The smaller of a lossless WebP and, without transparency, a JPEG.
+fn encode_variant(image: &DynamicImage) -> Option<(&'static str, Vec<u8>)> {
+    let mut webp = vec![];
+    DynamicImage::ImageRgba8(image.to_rgba8())
+        .write_with_encoder(WebPEncoder::new_lossless(&mut webp))
+        .ok()?;
+    if image.color().has_alpha() {
+        return Some(("webp", webp));
+    }
+
+    let mut jpeg = vec![];
+    DynamicImage::ImageRgb8(image.to_rgb8())
+        .write_with_encoder(JpegEncoder::new_with_quality(
+            &mut jpeg,
+            VARIANT_JPEG_QUALITY,
+        ))
+        .ok()?;
+    if jpeg.len() < webp.len() {
+        Some(("jpg", jpeg))
+    } else {
+        Some(("webp", webp))
+    }
+}
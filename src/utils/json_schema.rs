@@ -0,0 +1,36 @@
+use serde_json::Value;
+
+// Errors reported back at most, a broken entry can have a lot of them.
+const MAX_ERRORS: usize = 10;
+
+/// Check that `schema` is a valid JSON Schema describing an object.
+pub fn validate_schema(schema: &Value) -> Result<(), String> {
+    if schema.get("type").and_then(Value::as_str) != Some("object") {
+        return Err("schema must describe an object, i.e. have \"type\": \"object\"".to_string());
+    }
+
+    jsonschema::validator_for(schema)
+        .map(|_| ())
+        .map_err(|error| format!("Invalid schema: {error}"))
+}
+
+/// Validate an entry against the schema of its collection, every error is listed.
+pub fn validate_entry(schema: &Value, data: &Value) -> Result<(), String> {
+    let validator =
+        jsonschema::validator_for(schema).map_err(|error| format!("Invalid schema: {error}"))?;
+
+    let errors: Vec<String> = validator
+        .iter_errors(data)
+        .take(MAX_ERRORS)
+        .map(|error| match error.instance_path.to_string() {
+            path if path.is_empty() => error.to_string(),
+            path => format!("{path}: {error}"),
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
@@ -0,0 +1,29 @@
+use pulldown_cmark::{html, Options, Parser};
+
+// Average silent reading speed used for the estimate.
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Render Markdown to HTML, anything unsafe (scripts, event handlers, ...) is stripped.
+pub fn render_markdown(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS;
+
+    let mut unsafe_html = String::new();
+    html::push_html(&mut unsafe_html, Parser::new_ext(markdown, options));
+
+    ammonia::clean(&unsafe_html)
+}
+
+/// Estimated reading time in minutes, at least 1.
+pub fn reading_time(markdown: &str) -> i32 {
+    let words = markdown.split_whitespace().count();
+
+    words.div_ceil(WORDS_PER_MINUTE).max(1) as i32
+}
+
+/// URL friendly version of a title, e.g. `Hello, World!` becomes `hello-world`.
+pub fn slugify(title: &str) -> String {
+    slug::slugify(title)
+}
@@ -1 +1,17 @@
+pub mod archive;
+pub mod cv;
+pub mod feed;
+pub mod highlight;
+pub mod i18n;
+pub mod image;
+pub mod json_schema;
+pub mod markdown;
+pub mod money;
+pub mod pdf;
+pub mod rate_limit;
+pub mod resume;
+pub mod seo;
+pub mod site;
+pub mod spam;
 pub mod util;
+pub mod vcard;
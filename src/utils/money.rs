@@ -0,0 +1,92 @@
+// ISO 4217 code, symbol, minor unit digits and whether the symbol goes after the amount.
+const CURRENCIES: [(&str, &str, usize, bool); 17] = [
+    ("AUD", "A$", 2, false),
+    ("BRL", "R$", 2, false),
+    ("CAD", "CA$", 2, false),
+    ("CHF", "CHF ", 2, false),
+    ("CNY", "CN¥", 2, false),
+    ("CZK", " Kč", 2, true),
+    ("DKK", " kr.", 2, true),
+    ("EUR", "€", 2, false),
+    ("GBP", "£", 2, false),
+    ("IDR", "Rp", 0, false),
+    ("INR", "₹", 2, false),
+    ("JPY", "¥", 0, false),
+    ("KRW", "₩", 0, false),
+    ("NOK", " kr", 2, true),
+    ("PLN", " zł", 2, true),
+    ("SEK", " kr", 2, true),
+    ("USD", "$", 2, false),
+];
+
+/// Whether `code` looks like an ISO 4217 currency code, e.g. `EUR`.
+pub fn is_currency_code(code: &str) -> bool {
+    code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase())
+}
+
+/// Format an amount in a currency, e.g. `$1,500` or `1,500 kr`. Cents are only shown
+/// when there are some, unknown currencies are written after the amount with their code.
+pub fn format_amount(amount: f64, currency: &str) -> String {
+    let (symbol, decimals, after) = CURRENCIES
+        .iter()
+        .find(|(code, ..)| *code == currency)
+        .map(|(_, symbol, decimals, after)| (symbol.to_string(), *decimals, *after))
+        .unwrap_or_else(|| (format!(" {currency}"), 2, true));
+
+    let sign = if amount < 0.0 { "-" } else { "" };
+    let number = format_number(amount.abs(), decimals);
+    if after {
+        format!("{sign}{number}{symbol}")
+    } else {
+        format!("{sign}{symbol}{number}")
+    }
+}
+
+/// Human readable price range, e.g. `€1,500 – €3,000 / project`, `From $50 / hour` or
+/// `Up to £800`. None when no price is given.
+pub fn format_price(
+    min: Option<f64>,
+    max: Option<f64>,
+    currency: &str,
+    unit: Option<&str>,
+) -> Option<String> {
+    let price = match (min, max) {
+        (Some(min), Some(max)) if min == max => format_amount(min, currency),
+        (Some(min), Some(max)) => format!(
+            "{} – {}",
+            format_amount(min, currency),
+            format_amount(max, currency)
+        ),
+        (Some(min), None) => format!("From {}", format_amount(min, currency)),
+        (None, Some(max)) => format!("Up to {}", format_amount(max, currency)),
+        (None, None) => return None,
+    };
+
+    match unit.filter(|unit| !unit.is_empty()) {
+        Some(unit) => Some(format!("{price} / {unit}")),
+        None => Some(price),
+    }
+}
+
+// Group the thousands of a positive amount with `,` and drop the decimals of round amounts.
+fn format_number(amount: f64, decimals: usize) -> String {
+    let formatted = format!("{:.*}", decimals, amount);
+    let (integer, fraction) = match formatted.split_once('.') {
+        Some((integer, fraction)) => (integer, fraction.trim_end_matches('0')),
+        None => (formatted.as_str(), ""),
+    };
+
+    let mut grouped = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    if !fraction.is_empty() {
+        // Never show a single cent digit, e.g. `1.5` is `1.50`.
+        grouped.push('.');
+        grouped.push_str(&format!("{fraction:0<decimals$}"));
+    }
+    grouped
+}
@@ -0,0 +1,445 @@
+use pdf_writer::{
+    types::{ActionType, AnnotationType},
+    Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr,
+};
+
+use crate::models::cv::{Cv, CvEntry};
+
+// A4 in points.
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+const TEXT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN;
+
+type Rgb = (f32, f32, f32);
+
+const TEXT: Rgb = (0.13, 0.13, 0.13);
+const MUTED: Rgb = (0.4, 0.4, 0.4);
+
+/// The standard fonts, every PDF reader has them so nothing is embedded.
+#[derive(Clone, Copy)]
+enum Font {
+    Regular,
+    Bold,
+    Oblique,
+}
+
+impl Font {
+    const ALL: [Font; 3] = [Font::Regular, Font::Bold, Font::Oblique];
+
+    fn resource(self) -> Name<'static> {
+        match self {
+            Font::Regular => Name(b"F1"),
+            Font::Bold => Name(b"F2"),
+            Font::Oblique => Name(b"F3"),
+        }
+    }
+
+    fn base_font(self) -> Name<'static> {
+        match self {
+            Font::Regular => Name(b"Helvetica"),
+            Font::Bold => Name(b"Helvetica-Bold"),
+            Font::Oblique => Name(b"Helvetica-Oblique"),
+        }
+    }
+
+    // Advance of a WinAnsi byte in thousandths of the font size, from the Adobe font metrics.
+    fn width(self, byte: u8) -> u16 {
+        let table = match self {
+            Font::Bold => &HELVETICA_BOLD_WIDTHS,
+            Font::Regular | Font::Oblique => &HELVETICA_WIDTHS,
+        };
+        match byte {
+            32..=126 => table[(byte - 32) as usize],
+            0x91 | 0x92 | 0x82 => 278,
+            0x93 | 0x94 | 0x84 => 500,
+            0x95 => 350,
+            0x96 => 556,
+            0x85 | 0x97 => 1000,
+            0xC0..=0xDE => 722,
+            _ => 556,
+        }
+    }
+
+    fn text_width(self, text: &[u8], size: f32) -> f32 {
+        text.iter()
+            .map(|byte| f32::from(self.width(*byte)))
+            .sum::<f32>()
+            * size
+            / 1000.0
+    }
+}
+
+#[rustfmt::skip]
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+#[rustfmt::skip]
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// Text in WinAnsiEncoding, characters it lacks become `?`.
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .filter(|c| !c.is_control())
+        .map(|c| match c {
+            ' '..='~' | '\u{A0}'..='\u{FF}' => c as u8,
+            '€' => 0x80,
+            '‚' => 0x82,
+            '„' => 0x84,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            '™' => 0x99,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// Lines of at most `width` points, broken between words, or inside a word too long for a line.
+fn wrap(text: &[u8], font: Font, size: f32, width: f32) -> Vec<Vec<u8>> {
+    let space = font.text_width(b" ", size);
+    let mut lines = vec![];
+    let mut line: Vec<u8> = vec![];
+    let mut line_width = 0.0;
+
+    for word in text
+        .split(|byte| *byte == b' ')
+        .filter(|word| !word.is_empty())
+    {
+        let word_width = font.text_width(word, size);
+        if !line.is_empty() && line_width + space + word_width <= width {
+            line.push(b' ');
+            line.extend_from_slice(word);
+            line_width += space + word_width;
+            continue;
+        }
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        line_width = 0.0;
+        for byte in word {
+            let byte_width = font.text_width(&[*byte], size);
+            if !line.is_empty() && line_width + byte_width > width {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0.0;
+            }
+            line.push(*byte);
+            line_width += byte_width;
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+// Lays text out top to bottom, opening a page whenever the current one is full.
+struct Layout {
+    accent: Rgb,
+    pages: Vec<(Content, Vec<(Rect, String)>)>,
+    content: Content,
+    links: Vec<(Rect, String)>,
+    y: f32,
+}
+
+impl Layout {
+    fn new(accent: Rgb) -> Self {
+        Self {
+            accent,
+            pages: vec![],
+            content: Content::new(),
+            links: vec![],
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn ensure(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            let content = std::mem::replace(&mut self.content, Content::new());
+            self.pages.push((content, std::mem::take(&mut self.links)));
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+    }
+
+    fn gap(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    fn show(&mut self, x: f32, y: f32, font: Font, size: f32, color: Rgb, text: &[u8]) {
+        self.content
+            .set_fill_rgb(color.0, color.1, color.2)
+            .begin_text()
+            .set_font(font.resource(), size)
+            .next_line(x, y)
+            .show(Str(text))
+            .end_text();
+    }
+
+    // Wrapped text starting `indent` points in.
+    fn paragraph(&mut self, text: &str, font: Font, size: f32, color: Rgb, indent: f32) {
+        let leading = size * 1.35;
+        let lines = wrap(&encode(text), font, size, TEXT_WIDTH - indent);
+        for line in &lines {
+            self.ensure(leading);
+            self.y -= leading;
+            self.show(MARGIN + indent, self.y, font, size, color, line);
+        }
+    }
+
+    fn link(&mut self, x: f32, width: f32, size: f32, url: &str) {
+        let rect = Rect::new(x, self.y - size * 0.25, x + width, self.y + size * 0.85);
+        self.links.push((rect, url.to_string()));
+    }
+
+    fn rule(&mut self, color: Rgb, width: f32) {
+        self.content
+            .set_stroke_rgb(color.0, color.1, color.2)
+            .set_line_width(width)
+            .move_to(MARGIN, self.y)
+            .line_to(PAGE_WIDTH - MARGIN, self.y)
+            .stroke();
+    }
+
+    fn heading(&mut self, title: &str) {
+        // a heading is never left alone at the bottom of a page
+        self.ensure(60.0);
+        self.gap(22.0);
+        let accent = self.accent;
+        self.show(
+            MARGIN,
+            self.y,
+            Font::Bold,
+            11.0,
+            accent,
+            &encode(&title.to_uppercase()),
+        );
+        self.gap(5.0);
+        self.rule(accent, 0.75);
+        self.gap(4.0);
+    }
+
+    fn entry(&mut self, entry: &CvEntry) {
+        let title_size = 10.5;
+        let period = entry.period.as_deref().map(encode).unwrap_or_default();
+        let period_width = Font::Regular.text_width(&period, 9.0);
+        let title = encode(&entry.title);
+        let title_lines = wrap(
+            &title,
+            Font::Bold,
+            title_size,
+            TEXT_WIDTH - period_width - 12.0,
+        );
+
+        self.ensure(40.0);
+        self.gap(8.0);
+        for (index, line) in title_lines.iter().enumerate() {
+            self.ensure(title_size * 1.35);
+            self.y -= title_size * 1.35;
+            self.show(MARGIN, self.y, Font::Bold, title_size, TEXT, line);
+            if index == 0 {
+                if let Some(url) = &entry.url {
+                    let width = Font::Bold.text_width(line, title_size);
+                    self.link(MARGIN, width, title_size, url);
+                }
+                if !period.is_empty() {
+                    let x = PAGE_WIDTH - MARGIN - period_width;
+                    self.show(x, self.y, Font::Regular, 9.0, MUTED, &period);
+                }
+            }
+        }
+
+        let subtitle = [entry.subtitle.as_deref(), entry.location.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" · ");
+        if !subtitle.is_empty() {
+            self.paragraph(&subtitle, Font::Oblique, 9.5, MUTED, 0.0);
+        }
+        if let Some(summary) = &entry.summary {
+            self.gap(2.0);
+            self.paragraph(summary, Font::Regular, 9.5, TEXT, 0.0);
+        }
+        for highlight in &entry.highlights {
+            self.ensure(9.5 * 1.35);
+            let y = self.y - 9.5 * 1.35;
+            self.show(MARGIN + 4.0, y, Font::Regular, 9.5, TEXT, &[0x95]);
+            self.paragraph(highlight, Font::Regular, 9.5, TEXT, 14.0);
+        }
+        if !entry.tags.is_empty() {
+            self.paragraph(&entry.tags.join(", "), Font::Regular, 8.5, MUTED, 0.0);
+        }
+    }
+
+    fn finish(mut self, title: &str) -> Vec<u8> {
+        self.pages.push((self.content, self.links));
+
+        let catalog_id = Ref::new(1);
+        let tree_id = Ref::new(2);
+        let info_id = Ref::new(3);
+        let font_ids: Vec<Ref> = (4..4 + Font::ALL.len() as i32).map(Ref::new).collect();
+        let first_page = 4 + Font::ALL.len() as i32;
+        let page_ids: Vec<Ref> = (0..self.pages.len() as i32)
+            .map(|index| Ref::new(first_page + 2 * index))
+            .collect();
+
+        let mut pdf = Pdf::new();
+        pdf.catalog(catalog_id).pages(tree_id);
+        pdf.document_info(info_id)
+            .title(TextStr(title))
+            .producer(TextStr("portfolio-cms"));
+
+        let mut pages = pdf.pages(tree_id);
+        pages
+            .kids(page_ids.iter().copied())
+            .count(page_ids.len() as i32);
+        let mut resources = pages.resources();
+        let mut fonts = resources.fonts();
+        for (font, id) in Font::ALL.iter().zip(&font_ids) {
+            fonts.pair(font.resource(), *id);
+        }
+        fonts.finish();
+        resources.finish();
+        pages.finish();
+
+        for (font, id) in Font::ALL.iter().zip(&font_ids) {
+            pdf.type1_font(*id)
+                .base_font(font.base_font())
+                .encoding_predefined(Name(b"WinAnsiEncoding"));
+        }
+
+        for ((content, links), page_id) in self.pages.into_iter().zip(page_ids) {
+            let content_id = Ref::new(page_id.get() + 1);
+            let mut page = pdf.page(page_id);
+            page.media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+                .parent(tree_id)
+                .contents(content_id);
+            let mut annotations = page.annotations();
+            for (rect, url) in &links {
+                let mut annotation = annotations.push();
+                annotation
+                    .subtype(AnnotationType::Link)
+                    .rect(*rect)
+                    .border(0.0, 0.0, 0.0, None);
+                annotation
+                    .action()
+                    .action_type(ActionType::Uri)
+                    .uri(Str(url.as_bytes()));
+            }
+            annotations.finish();
+            page.finish();
+            pdf.stream(content_id, &content.finish());
+        }
+
+        pdf.finish()
+    }
+}
+
+/// CV as an A4 PDF with the standard Helvetica fonts, `modern` uses an accent color.
+pub fn cv_pdf(cv: &Cv, template: &str) -> Vec<u8> {
+    let accent = match template {
+        "modern" => (0.12, 0.44, 0.92),
+        _ => TEXT,
+    };
+    let mut layout = Layout::new(accent);
+
+    layout.y -= 22.0;
+    layout.show(
+        MARGIN,
+        layout.y,
+        Font::Bold,
+        22.0,
+        accent,
+        &encode(&cv.name),
+    );
+    layout.gap(4.0);
+    if let Some(intro) = &cv.intro {
+        layout.paragraph(intro, Font::Oblique, 12.0, MUTED, 0.0);
+    }
+
+    // contact line, links are clickable
+    layout.gap(4.0);
+    let mut contacts = vec![(cv.email.clone(), Some(format!("mailto:{}", cv.email)))];
+    contacts.extend(cv.phone.clone().map(|phone| (phone, None)));
+    contacts.extend(
+        cv.links
+            .iter()
+            .map(|link| (link.name.clone(), Some(link.url.clone()))),
+    );
+    let size = 9.0;
+    let separator = encode("  ·  ");
+    let separator_width = Font::Regular.text_width(&separator, size);
+    let mut x = MARGIN;
+    layout.y -= size * 1.35;
+    for (index, (label, url)) in contacts.iter().enumerate() {
+        let label = encode(label);
+        let width = Font::Regular.text_width(&label, size);
+        if index > 0 {
+            if x + separator_width + width > PAGE_WIDTH - MARGIN {
+                x = MARGIN;
+                layout.y -= size * 1.35;
+            } else {
+                layout.show(x, layout.y, Font::Regular, size, MUTED, &separator);
+                x += separator_width;
+            }
+        }
+        layout.show(x, layout.y, Font::Regular, size, TEXT, &label);
+        if let Some(url) = url {
+            layout.link(x, width, size, url);
+        }
+        x += width;
+    }
+    layout.gap(10.0);
+    layout.rule(accent, 1.5);
+
+    if let Some(about) = &cv.about {
+        layout.gap(6.0);
+        layout.paragraph(about, Font::Regular, 10.0, TEXT, 0.0);
+    }
+
+    for (title, entries) in [
+        ("Experience", &cv.experiences),
+        ("Education", &cv.educations),
+        ("Projects", &cv.projects),
+    ] {
+        if entries.is_empty() {
+            continue;
+        }
+        layout.heading(title);
+        for entry in entries {
+            layout.entry(entry);
+        }
+    }
+
+    if !cv.skills.is_empty() {
+        layout.heading("Skills");
+        for group in &cv.skills {
+            layout.gap(4.0);
+            if let Some(category) = &group.category {
+                layout.paragraph(category, Font::Bold, 9.5, TEXT, 0.0);
+            }
+            layout.paragraph(&group.skills.join(", "), Font::Regular, 9.5, TEXT, 0.0);
+        }
+    }
+
+    layout.finish(&format!("{} – CV", cv.name))
+}
@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Fixed window rate limiter keyed by e.g. the client address.
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    hits: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            hits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count a hit for `key`, false once the limit of the current window is reached.
+    pub fn check(&self, key: &str) -> bool {
+        let now = Instant::now();
+        let mut hits = self.hits.lock().unwrap();
+
+        // Drop finished windows so the map does not grow forever.
+        hits.retain(|_, (start, _)| now.duration_since(*start) < self.window);
+
+        let (_, count) = hits.entry(key.to_string()).or_insert((now, 0));
+        if *count >= self.limit {
+            return false;
+        }
+        *count += 1;
+
+        true
+    }
+}
@@ -0,0 +1,540 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::models::{
+    customer::{Customer, CustomerUpdateInput},
+    experience::{Experience, ExperienceInput},
+    project::{Project, ProjectImageInput, ProjectInput},
+    resume::{Resume, ResumeBasics, ResumeProfile, ResumeProject, ResumeSkill, ResumeWork},
+    skill::{Skill, SkillInput, SkillProficiency},
+    social::{Social, SocialInput},
+};
+use crate::utils::util::parse_datetime;
+
+/// Version of the jsonresume.org schema written by the export.
+pub const RESUME_SCHEMA: &str =
+    "https://raw.githubusercontent.com/jsonresume/resume-schema/v1.0.0/schema.json";
+
+/// JSON Resume of a portfolio, sections are expected in their display order.
+pub fn to_resume(
+    customer: &Customer,
+    experiences: &[Experience],
+    skills: &[Skill],
+    socials: &[Social],
+    projects: &[Project],
+) -> Resume {
+    let basics = ResumeBasics {
+        name: Some(customer.name.clone()),
+        label: customer.intro.clone(),
+        image: customer.profile_picture.clone(),
+        email: Some(customer.email.clone()),
+        phone: customer.phone.clone(),
+        url: None,
+        summary: customer.about.clone(),
+        profiles: socials
+            .iter()
+            .map(|social| ResumeProfile {
+                network: Some(social.name.clone()),
+                username: None,
+                url: Some(social.link.clone()),
+            })
+            .collect(),
+    };
+
+    let work = experiences
+        .iter()
+        .map(|experience| ResumeWork {
+            name: Some(experience.company.clone()),
+            position: Some(experience.position.clone()),
+            location: Some(experience.location.clone()).filter(|location| !location.is_empty()),
+            url: None,
+            start_date: resume_date(&experience.start_date),
+            end_date: experience.end_date.as_deref().and_then(resume_date),
+            summary: None,
+            highlights: experience.description.clone().unwrap_or_default(),
+        })
+        .collect();
+
+    let projects = projects
+        .iter()
+        .map(|project| ResumeProject {
+            name: Some(project.name.clone()),
+            description: project.description.clone(),
+            highlights: vec![],
+            keywords: project.stack.clone().unwrap_or_default(),
+            start_date: project.start_date.as_deref().and_then(resume_date),
+            end_date: project.end_date.as_deref().and_then(resume_date),
+            url: project.link.clone(),
+            roles: project.role.clone().into_iter().collect(),
+            entity: project.client.clone(),
+        })
+        .collect();
+
+    Resume {
+        schema: Some(RESUME_SCHEMA.to_string()),
+        basics,
+        work,
+        skills: group_skills(skills),
+        projects,
+    }
+}
+
+// Skills of a category become one entry with the skills as keywords, the others stay alone.
+fn group_skills(skills: &[Skill]) -> Vec<ResumeSkill> {
+    let mut groups: Vec<(Option<&str>, Vec<&Skill>)> = vec![];
+    for skill in skills {
+        match skill.category.as_deref() {
+            Some(category) => match groups
+                .iter_mut()
+                .find(|(known, _)| *known == Some(category))
+            {
+                Some((_, group)) => group.push(skill),
+                None => groups.push((Some(category), vec![skill])),
+            },
+            None => groups.push((None, vec![skill])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(category, group)| {
+            // a level is only kept when the whole group shares it
+            let level = group[0]
+                .proficiency
+                .filter(|level| group.iter().all(|skill| skill.proficiency == Some(*level)))
+                .map(|level| level_name(level).to_string());
+            match category {
+                Some(category) => ResumeSkill {
+                    name: Some(category.to_string()),
+                    level,
+                    keywords: group.iter().map(|skill| skill.name.clone()).collect(),
+                },
+                None => ResumeSkill {
+                    name: Some(group[0].name.clone()),
+                    level,
+                    keywords: vec![],
+                },
+            }
+        })
+        .collect()
+}
+
+/// Skills of a resume as (name, category, proficiency), keywords are skills of the entry's category.
+pub fn resume_skills(
+    skills: &[ResumeSkill],
+) -> Vec<(String, Option<String>, Option<SkillProficiency>)> {
+    skills
+        .iter()
+        .flat_map(|skill| {
+            let name = non_empty(skill.name.as_deref());
+            let level = skill.level.as_deref().and_then(parse_level);
+            let skills: Vec<_> = if skill.keywords.is_empty() {
+                name.map(|name| (name, None, level)).into_iter().collect()
+            } else {
+                skill
+                    .keywords
+                    .iter()
+                    .filter_map(|keyword| non_empty(Some(keyword)))
+                    .map(|keyword| (keyword, name.clone(), level))
+                    .collect()
+            };
+            skills
+        })
+        .collect()
+}
+
+fn level_name(level: SkillProficiency) -> &'static str {
+    match level {
+        SkillProficiency::Beginner => "Beginner",
+        SkillProficiency::Intermediate => "Intermediate",
+        SkillProficiency::Advanced => "Advanced",
+        SkillProficiency::Expert => "Expert",
+    }
+}
+
+fn parse_level(level: &str) -> Option<SkillProficiency> {
+    match level.trim().to_lowercase().as_str() {
+        "beginner" | "novice" | "basic" => Some(SkillProficiency::Beginner),
+        "intermediate" => Some(SkillProficiency::Intermediate),
+        "advanced" => Some(SkillProficiency::Advanced),
+        "expert" | "master" => Some(SkillProficiency::Expert),
+        _ => None,
+    }
+}
+
+// `YYYY-MM-DD` of a DTO date.
+fn resume_date(date: &str) -> Option<String> {
+    parse_datetime(date).map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+// RFC 3339 date of an input, the first day of the month or year when the resume omits it.
+fn input_date(date: &str) -> Result<String, String> {
+    let date = date.trim();
+    let padded = match date.len() {
+        4 => format!("{date}-01-01"),
+        7 => format!("{date}-01"),
+        _ => date.get(..10).unwrap_or(date).to_string(),
+    };
+
+    NaiveDate::parse_from_str(&padded, "%Y-%m-%d")
+        .map(|date| rfc3339(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()))
+        .map_err(|_| format!("Invalid date {date}, expected YYYY-MM-DD, YYYY-MM or YYYY"))
+}
+
+fn rfc3339(date: DateTime<Utc>) -> String {
+    date.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+// Date of a DTO as sent back in an input.
+fn dto_input_date(date: &str) -> String {
+    parse_datetime(date)
+        .map(rfc3339)
+        .unwrap_or_else(|| date.to_string())
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+// Set `target` and remember `field` when the value changes.
+fn assign<T: PartialEq>(changed: &mut Vec<String>, field: &str, target: &mut T, value: T) {
+    if *target != value {
+        *target = value;
+        changed.push(field.to_string());
+    }
+}
+
+// Same as `assign` for a date, a resume only has the day so the time of the same day is kept.
+fn assign_date(
+    changed: &mut Vec<String>,
+    field: &str,
+    target: &mut Option<String>,
+    value: Option<String>,
+) {
+    let same_day = match (target.as_deref(), value.as_deref()) {
+        (Some(target), Some(value)) => target.get(..10) == value.get(..10),
+        (None, None) => true,
+        _ => false,
+    };
+    if !same_day {
+        *target = value;
+        changed.push(field.to_string());
+    }
+}
+
+/// Customer fields taken from the basics, the email is kept since it is used to sign in.
+pub fn merge_basics(
+    customer: &Customer,
+    basics: &ResumeBasics,
+) -> (CustomerUpdateInput, Vec<String>) {
+    let mut input = CustomerUpdateInput {
+        name: customer.name.clone(),
+        email: customer.email.clone(),
+        phone: customer.phone.clone(),
+        wa_link: customer.wa_link.clone(),
+        intro: customer.intro.clone(),
+        about: customer.about.clone(),
+        profile_picture: customer.profile_picture.clone(),
+        profile_picture_id: customer.profile_picture_id.clone(),
+        default_locale: customer.default_locale.clone(),
+        locales: Some(customer.locales.clone()),
+        translations: Some(customer.translations.clone()),
+        site_url: customer.site_url.clone(),
+        seo: customer.seo.clone(),
+    };
+    let mut changed = vec![];
+
+    if let Some(name) = non_empty(basics.name.as_deref()) {
+        assign(&mut changed, "name", &mut input.name, name);
+    }
+    if let Some(label) = non_empty(basics.label.as_deref()) {
+        assign(&mut changed, "intro", &mut input.intro, Some(label));
+    }
+    if let Some(summary) = non_empty(basics.summary.as_deref()) {
+        assign(&mut changed, "about", &mut input.about, Some(summary));
+    }
+    if let Some(phone) = non_empty(basics.phone.as_deref()) {
+        assign(&mut changed, "phone", &mut input.phone, Some(phone));
+    }
+    if let Some(image) = non_empty(basics.image.as_deref()) {
+        if input.profile_picture.as_deref() != Some(image.as_str()) {
+            // an uploaded picture would take precedence over the link
+            input.profile_picture_id = None;
+        }
+        assign(
+            &mut changed,
+            "profile_picture",
+            &mut input.profile_picture,
+            Some(image),
+        );
+    }
+
+    (input, changed)
+}
+
+/// Experience from a work entry, merged into `existing` when there is one.
+pub fn merge_work(
+    existing: Option<&Experience>,
+    work: &ResumeWork,
+    order: i32,
+) -> Result<(ExperienceInput, Vec<String>), String> {
+    let company = non_empty(work.name.as_deref()).ok_or("work: an entry has no name")?;
+    let position = non_empty(work.position.as_deref())
+        .ok_or_else(|| format!("work: {company} has no position"))?;
+
+    let mut input = match existing {
+        Some(experience) => ExperienceInput {
+            company: experience.company.clone(),
+            work_type: experience.work_type.clone(),
+            location: experience.location.clone(),
+            start_date: dto_input_date(&experience.start_date),
+            end_date: experience.end_date.as_deref().map(dto_input_date),
+            position: experience.position.clone(),
+            description: experience.description.clone(),
+            order: experience.order,
+            skill_ids: Some(experience.skill_ids.clone()),
+            project_ids: Some(experience.project_ids.clone()),
+            translations: Some(experience.translations.clone()),
+        },
+        None => ExperienceInput {
+            company: String::new(),
+            work_type: String::new(),
+            location: String::new(),
+            start_date: String::new(),
+            end_date: None,
+            position: String::new(),
+            description: None,
+            order,
+            skill_ids: None,
+            project_ids: None,
+            translations: None,
+        },
+    };
+    let mut changed = vec![];
+
+    assign(&mut changed, "company", &mut input.company, company.clone());
+    assign(&mut changed, "position", &mut input.position, position);
+    if let Some(location) = non_empty(work.location.as_deref()) {
+        assign(&mut changed, "location", &mut input.location, location);
+    }
+
+    match work.start_date.as_deref().map(input_date).transpose() {
+        Ok(Some(start_date)) => {
+            let mut current = Some(input.start_date.clone()).filter(|date| !date.is_empty());
+            assign_date(&mut changed, "start_date", &mut current, Some(start_date));
+            input.start_date = current.unwrap_or_default();
+        }
+        Ok(None) if existing.is_some() => {}
+        Ok(None) => return Err(format!("work: {company} has no startDate")),
+        Err(error) => return Err(format!("work: {company}: {error}")),
+    }
+    let end_date = work
+        .end_date
+        .as_deref()
+        .map(input_date)
+        .transpose()
+        .map_err(|error| format!("work: {company}: {error}"))?;
+    assign_date(&mut changed, "end_date", &mut input.end_date, end_date);
+
+    let description: Vec<String> = non_empty(work.summary.as_deref())
+        .into_iter()
+        .chain(
+            work.highlights
+                .iter()
+                .filter_map(|line| non_empty(Some(line))),
+        )
+        .collect();
+    if !description.is_empty() {
+        assign(
+            &mut changed,
+            "description",
+            &mut input.description,
+            Some(description),
+        );
+    }
+
+    Ok((input, changed))
+}
+
+/// Project from a resume project, merged into `existing` when there is one.
+pub fn merge_project(
+    existing: Option<&Project>,
+    project: &ResumeProject,
+    order: i32,
+) -> Result<(ProjectInput, Vec<String>), String> {
+    let name = non_empty(project.name.as_deref()).ok_or("projects: an entry has no name")?;
+
+    let mut input = match existing {
+        Some(project) => ProjectInput {
+            name: project.name.clone(),
+            description: project.description.clone(),
+            link: project.link.clone(),
+            photo_link: project.photo_link.clone(),
+            photo_id: project.photo_id.clone(),
+            gallery: Some(
+                project
+                    .gallery
+                    .iter()
+                    .map(|image| ProjectImageInput {
+                        media_id: image.media_id.clone(),
+                        caption: image.caption.clone(),
+                    })
+                    .collect(),
+            ),
+            links: Some(project.links.clone()),
+            role: project.role.clone(),
+            team_size: project.team_size,
+            start_date: project.start_date.as_deref().map(dto_input_date),
+            end_date: project.end_date.as_deref().map(dto_input_date),
+            featured: Some(project.featured),
+            client: project.client.clone(),
+            order: project.order,
+            stack: project.stack.clone(),
+            skill_ids: Some(project.skill_ids.clone()),
+            translations: Some(project.translations.clone()),
+            seo: project.seo.clone(),
+        },
+        None => ProjectInput {
+            name: String::new(),
+            description: None,
+            link: None,
+            photo_link: None,
+            photo_id: None,
+            gallery: None,
+            links: None,
+            role: None,
+            team_size: None,
+            start_date: None,
+            end_date: None,
+            featured: None,
+            client: None,
+            order,
+            stack: None,
+            skill_ids: None,
+            translations: None,
+            seo: None,
+        },
+    };
+    let mut changed = vec![];
+
+    assign(&mut changed, "name", &mut input.name, name.clone());
+
+    let description: Vec<String> = non_empty(project.description.as_deref())
+        .into_iter()
+        .chain(
+            project
+                .highlights
+                .iter()
+                .filter_map(|line| non_empty(Some(line))),
+        )
+        .collect();
+    if !description.is_empty() {
+        assign(
+            &mut changed,
+            "description",
+            &mut input.description,
+            Some(description.join("\n")),
+        );
+    }
+    if let Some(url) = non_empty(project.url.as_deref()) {
+        assign(&mut changed, "link", &mut input.link, Some(url));
+    }
+    if let Some(role) = non_empty(project.roles.first().map(String::as_str)) {
+        assign(&mut changed, "role", &mut input.role, Some(role));
+    }
+    if let Some(entity) = non_empty(project.entity.as_deref()) {
+        assign(&mut changed, "client", &mut input.client, Some(entity));
+    }
+    if !project.keywords.is_empty() {
+        assign(
+            &mut changed,
+            "stack",
+            &mut input.stack,
+            Some(project.keywords.clone()),
+        );
+    }
+
+    for (field, date, target) in [
+        ("start_date", &project.start_date, &mut input.start_date),
+        ("end_date", &project.end_date, &mut input.end_date),
+    ] {
+        if let Some(date) = date.as_deref() {
+            let date = input_date(date).map_err(|error| format!("projects: {name}: {error}"))?;
+            assign_date(&mut changed, field, target, Some(date));
+        }
+    }
+
+    Ok((input, changed))
+}
+
+/// Skill from a resume skill, merged into `existing` when there is one.
+pub fn merge_skill(
+    existing: Option<&Skill>,
+    (name, category, level): &(String, Option<String>, Option<SkillProficiency>),
+    order: i32,
+) -> (SkillInput, Vec<String>) {
+    let mut input = match existing {
+        Some(skill) => SkillInput {
+            name: skill.name.clone(),
+            category: skill.category.clone(),
+            proficiency: skill.proficiency,
+            years: skill.years,
+            order: skill.order,
+        },
+        None => SkillInput {
+            name: String::new(),
+            category: None,
+            proficiency: None,
+            years: None,
+            order,
+        },
+    };
+    let mut changed = vec![];
+
+    assign(&mut changed, "name", &mut input.name, name.clone());
+    if category.is_some() {
+        assign(
+            &mut changed,
+            "category",
+            &mut input.category,
+            category.clone(),
+        );
+    }
+    if level.is_some() {
+        assign(&mut changed, "proficiency", &mut input.proficiency, *level);
+    }
+
+    (input, changed)
+}
+
+/// Social from a resume profile, merged into `existing` when there is one.
+pub fn merge_profile(
+    existing: Option<&Social>,
+    profile: &ResumeProfile,
+    order: i32,
+) -> Result<(SocialInput, Vec<String>), String> {
+    let name = non_empty(profile.network.as_deref()).ok_or("profiles: an entry has no network")?;
+    let url =
+        non_empty(profile.url.as_deref()).ok_or_else(|| format!("profiles: {name} has no url"))?;
+
+    let mut input = match existing {
+        Some(social) => SocialInput {
+            name: social.name.clone(),
+            link: social.link.clone(),
+            order: social.order,
+        },
+        None => SocialInput {
+            name: String::new(),
+            link: String::new(),
+            order,
+        },
+    };
+    let mut changed = vec![];
+
+    assign(&mut changed, "name", &mut input.name, name);
+    assign(&mut changed, "link", &mut input.link, url);
+
+    Ok((input, changed))
+}
@@ -0,0 +1,388 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+
+use crate::models::{
+    article::Article,
+    customer::Customer,
+    experience::Experience,
+    project::Project,
+    seo::{PageMeta, Seo},
+    skill::Skill,
+    social::Social,
+};
+use crate::utils::feed::{escape_xml, Feed, FeedEntry, SitemapUrl};
+use crate::utils::site::project_slugs;
+use crate::utils::util::parse_datetime;
+
+// Search engines cut descriptions around this length.
+const DESCRIPTION_LENGTH: usize = 160;
+
+/// A page of the portfolio site: the home page, `/projects/<slug>/` or `/articles/<slug>/`.
+pub struct SitePage {
+    /// path below `site_url`
+    pub path: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    /// `og:type`
+    pub kind: &'static str,
+    pub published: Option<DateTime<Utc>>,
+    pub updated: Option<DateTime<Utc>>,
+    pub seo: Option<Seo>,
+}
+
+pub fn is_http_url(url: &str) -> bool {
+    url.strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .is_some_and(|rest| !rest.is_empty())
+}
+
+pub fn validate_site_url(site_url: Option<&str>) -> Result<(), String> {
+    match site_url {
+        Some(url) if !is_http_url(url.trim()) => {
+            Err(format!("Invalid site_url {url}, expected an http(s) url"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// `canonical_url` and `og_image` end up on other sites, they have to be absolute.
+pub fn validate_seo(seo: Option<&Seo>) -> Result<(), String> {
+    let Some(seo) = seo else {
+        return Ok(());
+    };
+    for (field, url) in [
+        ("canonical_url", &seo.canonical_url),
+        ("og_image", &seo.og_image),
+    ] {
+        if let Some(url) = url.as_deref().filter(|url| !is_http_url(url)) {
+            return Err(format!(
+                "Invalid seo.{field} {url}, expected an http(s) url"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Pages of the site, articles are expected to be published.
+pub fn site_pages(
+    customer: &Customer,
+    projects: &[Project],
+    articles: &[Article],
+) -> Vec<SitePage> {
+    let mut pages = vec![SitePage {
+        path: "/".to_string(),
+        title: customer.name.clone(),
+        description: customer.intro.clone(),
+        image: customer.profile_picture.clone(),
+        kind: "profile",
+        published: None,
+        updated: customer.updated_at.as_deref().and_then(parse_datetime),
+        seo: customer.seo.clone(),
+    }];
+
+    for (project, slug) in projects.iter().zip(project_slugs(projects)) {
+        let created = parse_datetime(&project.created_at);
+        pages.push(SitePage {
+            path: format!("/projects/{slug}/"),
+            title: project.name.clone(),
+            description: project.description.clone(),
+            image: project.photo_link.clone(),
+            kind: "website",
+            published: project
+                .start_date
+                .as_deref()
+                .and_then(parse_datetime)
+                .or(created),
+            updated: project
+                .updated_at
+                .as_deref()
+                .and_then(parse_datetime)
+                .or(created),
+            seo: project.seo.clone(),
+        });
+    }
+
+    for article in articles {
+        let published = article.published_at.as_deref().and_then(parse_datetime);
+        pages.push(SitePage {
+            path: format!("/articles/{}/", article.slug),
+            title: article.title.clone(),
+            description: article.excerpt.clone(),
+            image: article.cover_link.clone(),
+            kind: "article",
+            published,
+            updated: article
+                .updated_at
+                .as_deref()
+                .and_then(parse_datetime)
+                .or(published),
+            seo: article.seo.clone(),
+        });
+    }
+
+    pages
+}
+
+pub fn sitemap_urls(site_url: &str, pages: &[SitePage]) -> Vec<SitemapUrl> {
+    pages
+        .iter()
+        // pages published elsewhere first are indexed there
+        .filter(|page| canonical_override(page).is_none())
+        .map(|page| SitemapUrl {
+            loc: format!("{site_url}{}", page.path),
+            lastmod: page.updated,
+        })
+        .collect()
+}
+
+pub fn robots_txt(site_url: &str) -> String {
+    format!("User-agent: *\nAllow: /\n\nSitemap: {site_url}/sitemap.xml\n")
+}
+
+/// Feed of the projects and articles, newest first. `feed_path` is where the site serves it.
+pub fn site_feed(customer: &Customer, site_url: &str, feed_path: &str, pages: &[SitePage]) -> Feed {
+    let mut entries: Vec<FeedEntry> = pages
+        .iter()
+        .filter_map(|page| {
+            let published = page.published?;
+            Some(FeedEntry {
+                title: page.title.clone(),
+                url: canonical_url(site_url, page),
+                summary: page.description.clone(),
+                published,
+                // a project can start after it was entered
+                updated: page.updated.unwrap_or(published).max(published),
+            })
+        })
+        .collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.published));
+
+    Feed {
+        title: customer.name.clone(),
+        subtitle: customer.intro.clone(),
+        author: customer.name.clone(),
+        url: format!("{site_url}/"),
+        feed_url: format!("{site_url}{feed_path}"),
+        entries,
+    }
+}
+
+/// `/projects/my-app`, `projects/my-app/?ref=x` and `/projects/my-app/` are the same page.
+pub fn normalize_path(path: &str) -> String {
+    let path = path
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .trim_matches('/');
+    if path.is_empty() {
+        "/".to_string()
+    } else {
+        format!("/{path}/")
+    }
+}
+
+fn canonical_override(page: &SitePage) -> Option<&str> {
+    page.seo
+        .as_ref()
+        .and_then(|seo| seo.canonical_url.as_deref())
+        .filter(|url| !url.is_empty())
+}
+
+fn canonical_url(site_url: &str, page: &SitePage) -> String {
+    canonical_override(page)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{site_url}{}", page.path))
+}
+
+// Uploaded media are served under a path, the static site keeps that same path.
+fn absolute_url(site_url: &str, url: &str) -> String {
+    if url.starts_with('/') {
+        format!("{site_url}{url}")
+    } else {
+        url.to_string()
+    }
+}
+
+// Cut at a word boundary, the description of a page is a sentence or two.
+fn shorten(text: &str, length: usize) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= length {
+        return text;
+    }
+    let cut: String = text.chars().take(length - 1).collect();
+    let cut = match cut.rfind(' ') {
+        Some(index) => &cut[..index],
+        None => &cut,
+    };
+    format!("{}…", cut.trim_end_matches([',', '.', ';', ':']))
+}
+
+/// Meta tags of a page, fields of its `seo` take precedence over its content.
+pub fn page_meta(customer: &Customer, site_url: &str, page: &SitePage) -> PageMeta {
+    let seo = page.seo.clone().unwrap_or_default();
+    let title = seo
+        .meta_title
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| {
+            if page.path == "/" {
+                page.title.clone()
+            } else {
+                format!("{} – {}", page.title, customer.name)
+            }
+        });
+    let description = seo
+        .meta_description
+        .or_else(|| page.description.clone())
+        .map(|description| shorten(&description, DESCRIPTION_LENGTH))
+        .filter(|description| !description.is_empty());
+    let canonical_url = canonical_url(site_url, page);
+    let image = seo
+        .og_image
+        .or_else(|| page.image.clone())
+        .filter(|image| !image.is_empty())
+        .map(|image| absolute_url(site_url, &image));
+
+    let mut html = format!("<title>{}</title>\n", escape_xml(&title));
+    let mut meta = |attribute: &str, key: &str, value: &str| {
+        html.push_str(&format!(
+            "<meta {attribute}=\"{key}\" content=\"{}\">\n",
+            escape_xml(value)
+        ));
+    };
+    if let Some(description) = &description {
+        meta("name", "description", description);
+    }
+    meta("property", "og:type", page.kind);
+    meta("property", "og:title", &title);
+    if let Some(description) = &description {
+        meta("property", "og:description", description);
+    }
+    meta("property", "og:url", &canonical_url);
+    meta("property", "og:site_name", &customer.name);
+    if let Some(image) = &image {
+        meta("property", "og:image", image);
+    }
+    if page.kind == "article" {
+        if let Some(published) = page.published {
+            meta(
+                "property",
+                "article:published_time",
+                &published.to_rfc3339_opts(SecondsFormat::Secs, true),
+            );
+        }
+    }
+    let card = if image.is_some() {
+        "summary_large_image"
+    } else {
+        "summary"
+    };
+    meta("name", "twitter:card", card);
+    html.push_str(&format!(
+        "<link rel=\"canonical\" href=\"{}\">\n",
+        escape_xml(&canonical_url)
+    ));
+
+    PageMeta {
+        path: page.path.clone(),
+        title,
+        description,
+        canonical_url,
+        image,
+        og_type: page.kind.to_string(),
+        html,
+    }
+}
+
+/// schema.org `Person` of the customer: current positions are `worksFor`, every position is in
+/// `hasOccupation` and the socials are `sameAs`.
+pub fn person_json_ld(
+    customer: &Customer,
+    experiences: &[Experience],
+    socials: &[Social],
+    skills: &[Skill],
+) -> Value {
+    let site_url = customer.site_url.as_deref().filter(|url| !url.is_empty());
+
+    let mut person = json!({
+        "@context": "https://schema.org",
+        "@type": "Person",
+        "name": customer.name,
+        "email": format!("mailto:{}", customer.email),
+    });
+    if let Some(site_url) = site_url {
+        person["@id"] = json!(format!("{site_url}/#person"));
+        person["url"] = json!(format!("{site_url}/"));
+    }
+    if let Some(phone) = customer.phone.as_deref().filter(|phone| !phone.is_empty()) {
+        person["telephone"] = json!(phone);
+    }
+    if let Some(intro) = customer.intro.as_deref().filter(|intro| !intro.is_empty()) {
+        person["description"] = json!(intro);
+    }
+    // relative pictures are only usable once they live on the site
+    let image = customer
+        .profile_picture
+        .as_deref()
+        .filter(|picture| !picture.is_empty())
+        .and_then(|picture| match site_url {
+            Some(site_url) => Some(absolute_url(site_url, picture)),
+            None => Some(picture.to_string()).filter(|picture| !picture.starts_with('/')),
+        });
+    if let Some(image) = image {
+        person["image"] = json!(image);
+    }
+
+    let works_for: Vec<Value> = experiences
+        .iter()
+        .filter(|experience| experience.end_date.is_none())
+        .map(|experience| json!({ "@type": "Organization", "name": experience.company }))
+        .collect();
+    if !works_for.is_empty() {
+        person["worksFor"] = json!(works_for);
+    }
+
+    let occupations: Vec<Value> = experiences
+        .iter()
+        .map(|experience| {
+            let mut occupation = json!({
+                "@type": "Occupation",
+                "name": experience.position,
+            });
+            if let Some(description) = experience
+                .description
+                .as_ref()
+                .filter(|lines| !lines.is_empty())
+            {
+                occupation["description"] = json!(description.join("\n"));
+            }
+            if !experience.location.is_empty() {
+                occupation["occupationLocation"] =
+                    json!({ "@type": "AdministrativeArea", "name": experience.location });
+            }
+            let names: Vec<&str> = skills
+                .iter()
+                .filter(|skill| experience.skill_ids.contains(&skill.id))
+                .map(|skill| skill.name.as_str())
+                .collect();
+            if !names.is_empty() {
+                occupation["skills"] = json!(names.join(", "));
+            }
+            occupation
+        })
+        .collect();
+    if !occupations.is_empty() {
+        person["hasOccupation"] = json!(occupations);
+    }
+
+    let same_as: Vec<&str> = socials
+        .iter()
+        .map(|social| social.link.as_str())
+        .filter(|link| link.starts_with("http"))
+        .collect();
+    if !same_as.is_empty() {
+        person["sameAs"] = json!(same_as);
+    }
+
+    person
+}
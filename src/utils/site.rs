@@ -0,0 +1,342 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Cursor, Write};
+use std::path::{Component, Path};
+use std::{env, fs};
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tera::{Context, Tera};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::models::{project::Project, site::SiteContent};
+use crate::utils::feed::{atom_xml, sitemap_xml, Feed, FeedEntry, SitemapUrl};
+use crate::utils::util::{parse_datetime, template_error};
+
+/// Files of a built site by path, e.g. `projects/my-app/index.html`.
+pub type SiteFiles = BTreeMap<String, Vec<u8>>;
+
+// The default theme, shipped with the binary.
+const BUILT_IN_TEMPLATES: [(&str, &str); 4] = [
+    (
+        "_layout.html",
+        include_str!("../templates/site/_layout.html"),
+    ),
+    ("index.html", include_str!("../templates/site/index.html")),
+    (
+        "project.html",
+        include_str!("../templates/site/project.html"),
+    ),
+    ("404.html", include_str!("../templates/site/404.html")),
+];
+const BUILT_IN_ASSETS: [(&str, &[u8]); 1] = [(
+    "style.css",
+    include_bytes!("../templates/site/assets/style.css"),
+)];
+
+/// A theme directory: Tera templates at its root and static files under `assets/`.
+/// <br />`index.html` is the home page, `project.html` is rendered once per project into
+/// `projects/<slug>/`, `404.html` stays at the root and any other `<name>.html` becomes
+/// `<name>/index.html`. Templates starting with `_` are only extended or included.
+/// <br />`{{ asset(path="style.css") }}` gives the url of an asset, its name carries a hash of
+/// its content so it can be cached forever.
+pub struct Theme {
+    templates: Vec<(String, String)>,
+    assets: BTreeMap<String, Vec<u8>>,
+}
+
+impl Theme {
+    pub fn built_in() -> Self {
+        Self {
+            templates: BUILT_IN_TEMPLATES
+                .iter()
+                .map(|(name, source)| (name.to_string(), source.to_string()))
+                .collect(),
+            assets: BUILT_IN_ASSETS
+                .iter()
+                .map(|(path, bytes)| (path.to_string(), bytes.to_vec()))
+                .collect(),
+        }
+    }
+
+    pub fn from_dir(dir: &Path) -> Result<Self, String> {
+        let read_error = |error: std::io::Error| format!("{}: {error}", dir.display());
+
+        let mut templates = vec![];
+        for entry in fs::read_dir(dir).map_err(read_error)? {
+            let path = entry.map_err(read_error)?.path();
+            if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some("html") {
+                continue;
+            }
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let source = fs::read_to_string(&path).map_err(read_error)?;
+            templates.push((name.to_string(), source));
+        }
+        if !templates.iter().any(|(name, _)| name == "index.html") {
+            return Err(format!("{} has no index.html", dir.display()));
+        }
+
+        let mut assets = BTreeMap::new();
+        let assets_dir = dir.join("assets");
+        if assets_dir.is_dir() {
+            read_assets(&assets_dir, "", &mut assets).map_err(read_error)?;
+        }
+
+        Ok(Self { templates, assets })
+    }
+
+    /// The theme of `SITE_THEME_DIR`, or the built-in one.
+    pub fn from_env() -> Result<Self, String> {
+        match env::var("SITE_THEME_DIR") {
+            Ok(dir) if !dir.is_empty() => Self::from_dir(Path::new(&dir)),
+            _ => Ok(Self::built_in()),
+        }
+    }
+}
+
+fn read_assets(
+    dir: &Path,
+    prefix: &str,
+    assets: &mut BTreeMap<String, Vec<u8>>,
+) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let name = format!("{prefix}{name}");
+        if path.is_dir() {
+            read_assets(&path, &format!("{name}/"), assets)?;
+        } else {
+            assets.insert(name, fs::read(&path)?);
+        }
+    }
+    Ok(())
+}
+
+/// Render the theme with the content, along with `sitemap.xml` and an Atom `feed.xml` of the
+/// projects. `media` are copied as they are.
+pub fn build_site(
+    theme: &Theme,
+    content: &SiteContent,
+    media: SiteFiles,
+) -> Result<SiteFiles, String> {
+    let mut files = media;
+
+    let mut asset_urls = HashMap::new();
+    for (path, bytes) in &theme.assets {
+        let hashed = hashed_path(path, bytes);
+        asset_urls.insert(
+            path.clone(),
+            format!("{}/assets/{hashed}", content.base_path),
+        );
+        files.insert(format!("assets/{hashed}"), bytes.clone());
+    }
+
+    let mut tera = Tera::default();
+    tera.add_raw_templates(
+        theme
+            .templates
+            .iter()
+            .map(|(name, source)| (name.as_str(), source.as_str())),
+    )
+    .map_err(template_error)?;
+    tera.register_function("asset", move |args: &HashMap<String, Value>| {
+        let path = args
+            .get("path")
+            .and_then(Value::as_str)
+            .ok_or_else(|| tera::Error::msg("asset() takes a `path`"))?;
+        asset_urls
+            .get(path.trim_start_matches('/'))
+            .map(|url| Value::String(url.clone()))
+            .ok_or_else(|| tera::Error::msg(format!("Unknown asset {path}")))
+    });
+
+    let context = Context::from_serialize(content).map_err(template_error)?;
+    let render = |name: &str, page: &str, extra: &[(&str, Value)]| {
+        let mut context = context.clone();
+        context.insert("page", page);
+        for (key, value) in extra {
+            context.insert(*key, value);
+        }
+        tera.render(name, &context).map_err(template_error)
+    };
+
+    // pages of the sitemap, by path below `base_url`
+    let mut pages = vec![];
+    for (name, _) in &theme.templates {
+        if name.starts_with('_') {
+            continue;
+        }
+        match name.as_str() {
+            "index.html" => {
+                files.insert(name.clone(), render(name, "/", &[])?.into_bytes());
+                pages.push(("/".to_string(), None));
+            }
+            "404.html" => {
+                files.insert(name.clone(), render(name, "/404.html", &[])?.into_bytes());
+            }
+            "project.html" => {
+                for project in &content.projects {
+                    let page = format!("/projects/{}/", project.slug);
+                    let value = serde_json::to_value(project).map_err(|error| error.to_string())?;
+                    let html = render(name, &page, &[("project", value)])?;
+                    files.insert(
+                        format!("projects/{}/index.html", project.slug),
+                        html.into_bytes(),
+                    );
+                    let lastmod = project
+                        .project
+                        .updated_at
+                        .as_deref()
+                        .unwrap_or(&project.project.created_at);
+                    pages.push((page, parse_datetime(lastmod)));
+                }
+            }
+            _ => {
+                let stem = name.trim_end_matches(".html");
+                let page = format!("/{stem}/");
+                files.insert(
+                    format!("{stem}/index.html"),
+                    render(name, &page, &[])?.into_bytes(),
+                );
+                pages.push((page, None));
+            }
+        }
+    }
+
+    let urls: Vec<SitemapUrl> = pages
+        .into_iter()
+        .map(|(page, lastmod)| SitemapUrl {
+            loc: format!("{}{page}", content.base_url),
+            lastmod,
+        })
+        .collect();
+    files.insert("sitemap.xml".to_string(), sitemap_xml(&urls).into_bytes());
+
+    let has_project_pages = theme
+        .templates
+        .iter()
+        .any(|(name, _)| name == "project.html");
+    let mut entries: Vec<FeedEntry> = content
+        .projects
+        .iter()
+        .filter_map(|project| {
+            let created = parse_datetime(&project.project.created_at)?;
+            let published = project
+                .project
+                .start_date
+                .as_deref()
+                .and_then(parse_datetime)
+                .unwrap_or(created);
+            let updated = project
+                .project
+                .updated_at
+                .as_deref()
+                .and_then(parse_datetime)
+                .unwrap_or(created);
+            let url = if has_project_pages {
+                format!("{}/projects/{}/", content.base_url, project.slug)
+            } else {
+                project
+                    .project
+                    .link
+                    .clone()
+                    .unwrap_or_else(|| format!("{}/", content.base_url))
+            };
+            Some(FeedEntry {
+                title: project.project.name.clone(),
+                url,
+                summary: project.project.description.clone(),
+                published,
+                updated,
+            })
+        })
+        .collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.published));
+    let feed = Feed {
+        title: content.profile.name.clone(),
+        subtitle: content.profile.intro.clone(),
+        author: content.profile.name.clone(),
+        url: format!("{}/", content.base_url),
+        feed_url: format!("{}/feed.xml", content.base_url),
+        entries,
+    };
+    files.insert("feed.xml".to_string(), atom_xml(&feed).into_bytes());
+
+    Ok(files)
+}
+
+/// Slug of every project, from its name and unique within the list.
+pub fn project_slugs(projects: &[Project]) -> Vec<String> {
+    let mut slugs: Vec<String> = vec![];
+    for project in projects {
+        let base = Some(slug::slugify(&project.name))
+            .filter(|slug| !slug.is_empty())
+            .unwrap_or_else(|| project.id.clone());
+        let mut slug = base.clone();
+        let mut counter = 1;
+        while slugs.contains(&slug) {
+            counter += 1;
+            slug = format!("{base}-{counter}");
+        }
+        slugs.push(slug);
+    }
+    slugs
+}
+
+// `css/site.css` becomes `css/site.<hash>.css`.
+fn hashed_path(path: &str, bytes: &[u8]) -> String {
+    let hash = hex::encode(&Sha256::digest(bytes)[..4]);
+    let (dir, file) = match path.rsplit_once('/') {
+        Some((dir, file)) => (format!("{dir}/"), file),
+        None => (String::new(), path),
+    };
+    match file.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => format!("{dir}{stem}.{hash}.{extension}"),
+        _ => format!("{dir}{file}.{hash}"),
+    }
+}
+
+// Paths come from the database and the theme, none may leave the output.
+fn is_safe_path(path: &str) -> bool {
+    Path::new(path)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Write the site into `dir`, files already there are overwritten but never removed.
+pub fn write_dir(files: &SiteFiles, dir: &Path) -> Result<(), String> {
+    for (path, bytes) in files {
+        if !is_safe_path(path) {
+            return Err(format!("Invalid path {path}"));
+        }
+        let target = dir.join(path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|error| format!("{}: {error}", parent.display()))?;
+        }
+        fs::write(&target, bytes).map_err(|error| format!("{}: {error}", target.display()))?;
+    }
+    Ok(())
+}
+
+pub fn write_zip(files: &SiteFiles) -> Result<Vec<u8>, String> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (path, bytes) in files {
+        if !is_safe_path(path) {
+            return Err(format!("Invalid path {path}"));
+        }
+        writer
+            .start_file(path.as_str(), options)
+            .map_err(|error| error.to_string())?;
+        writer.write_all(bytes).map_err(|error| error.to_string())?;
+    }
+
+    writer
+        .finish()
+        .map(Cursor::into_inner)
+        .map_err(|error| error.to_string())
+}
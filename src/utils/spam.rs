@@ -0,0 +1,192 @@
+use std::env;
+use std::time::Duration;
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use super::rate_limit::RateLimiter;
+use super::util::generate_api_key;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Why a contact message was turned down.
+pub enum SpamCheck {
+    /// The honeypot was filled, pretend everything went fine.
+    Honeypot,
+    InvalidToken,
+    TooFast,
+    Expired,
+    InvalidProofOfWork,
+    RateLimited,
+}
+
+/// Public form a submission comes from, each one has its own rate limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpamForm {
+    Contact,
+    Testimonial,
+}
+
+/// Spam checks of the public contact and testimonial forms, configured from `CONTACT_*`
+/// variables, and `TESTIMONIAL_RATE_*` for the rate limit of testimonials.
+pub struct ContactProtection {
+    secret: String,
+    min_seconds: i64,
+    token_ttl: i64,
+    difficulty: u32,
+    limiter: RateLimiter,
+    testimonial_limiter: RateLimiter,
+}
+
+impl ContactProtection {
+    pub fn new() -> Self {
+        // Without a secret tokens are signed with a key of this process, they do not survive a
+        // restart but building the server needs no environment.
+        let secret = env::var("JWT_SECRET").unwrap_or_else(|_| generate_api_key());
+        let min_seconds = env::var("CONTACT_MIN_SECONDS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(3);
+        let token_ttl = env::var("CONTACT_TOKEN_TTL")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(3600);
+        let difficulty = env::var("CONTACT_POW_DIFFICULTY")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0);
+        let rate_limit = env::var("CONTACT_RATE_LIMIT")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(5);
+        let rate_window = env::var("CONTACT_RATE_WINDOW")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(3600);
+        let testimonial_rate_limit = env::var("TESTIMONIAL_RATE_LIMIT")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(3);
+        let testimonial_rate_window = env::var("TESTIMONIAL_RATE_WINDOW")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(86400);
+
+        Self {
+            secret,
+            min_seconds,
+            token_ttl,
+            difficulty,
+            limiter: RateLimiter::new(rate_limit, Duration::from_secs(rate_window)),
+            testimonial_limiter: RateLimiter::new(
+                testimonial_rate_limit,
+                Duration::from_secs(testimonial_rate_window),
+            ),
+        }
+    }
+
+    /// Leading zero bits asked from the proof-of-work, 0 when it is disabled.
+    pub fn difficulty(&self) -> u32 {
+        self.difficulty
+    }
+
+    /// Token to send back with the form, `{issued_at}.{signature}`. It is bound to the api
+    /// key so it can not be replayed against another portfolio.
+    pub fn issue_token(&self, api_key: &str) -> String {
+        let issued_at = Utc::now().timestamp_millis();
+
+        format!("{issued_at}.{}", self.sign(api_key, issued_at))
+    }
+
+    /// Run every check on a submission, the rate limit is only counted for otherwise valid ones.
+    /// <br />Without a client address, e.g. from a local client, the rate limit is skipped rather
+    /// than shared by every such client.
+    pub fn check(
+        &self,
+        form: SpamForm,
+        api_key: &str,
+        ip: Option<&str>,
+        token: &str,
+        nonce: Option<&str>,
+        honeypot: Option<&str>,
+    ) -> Result<(), SpamCheck> {
+        if honeypot.is_some_and(|value| !value.trim().is_empty()) {
+            return Err(SpamCheck::Honeypot);
+        }
+
+        let Some((issued_at, signature)) = token
+            .split_once('.')
+            .and_then(|(issued_at, signature)| Some((issued_at.parse::<i64>().ok()?, signature)))
+        else {
+            return Err(SpamCheck::InvalidToken);
+        };
+        let Ok(signature) = hex::decode(signature) else {
+            return Err(SpamCheck::InvalidToken);
+        };
+        if self
+            .mac(api_key, issued_at)
+            .verify_slice(&signature)
+            .is_err()
+        {
+            return Err(SpamCheck::InvalidToken);
+        }
+
+        // Bots post right away, humans need some time to fill the form.
+        let age = Utc::now().timestamp_millis() - issued_at;
+        if age < self.min_seconds * 1000 {
+            return Err(SpamCheck::TooFast);
+        }
+        if age > self.token_ttl * 1000 {
+            return Err(SpamCheck::Expired);
+        }
+
+        if self.difficulty > 0
+            && nonce.is_none_or(|nonce| leading_zero_bits(token, nonce) < self.difficulty)
+        {
+            return Err(SpamCheck::InvalidProofOfWork);
+        }
+
+        let limiter = match form {
+            SpamForm::Contact => &self.limiter,
+            SpamForm::Testimonial => &self.testimonial_limiter,
+        };
+        if ip.is_some_and(|ip| !limiter.check(ip)) {
+            return Err(SpamCheck::RateLimited);
+        }
+
+        Ok(())
+    }
+
+    fn mac(&self, api_key: &str, issued_at: i64) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(self.secret.as_bytes())
+            .expect("HMAC accepts keys of any size");
+        mac.update(format!("contact:{api_key}:{issued_at}").as_bytes());
+        mac
+    }
+
+    fn sign(&self, api_key: &str, issued_at: i64) -> String {
+        hex::encode(self.mac(api_key, issued_at).finalize().into_bytes())
+    }
+}
+
+impl Default for ContactProtection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Leading zero bits of `sha256("{token}:{nonce}")`.
+fn leading_zero_bits(token: &str, nonce: &str) -> u32 {
+    let hash = Sha256::digest(format!("{token}:{nonce}").as_bytes());
+
+    let mut bits = 0;
+    for byte in hash {
+        bits += byte.leading_zeros();
+        if byte != 0 {
+            break;
+        }
+    }
+
+    bits
+}
@@ -1,6 +1,10 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
 use hex;
+use mongodb::bson::{oid::ObjectId, Bson, Document};
 use rand::rngs::OsRng;
 use rand::TryRngCore;
+use serde::de::Error as _;
+use serde_json::{Map, Value};
 
 pub mod option_chrono_datetime_as_bson_datetime {
     use bson::Bson;
@@ -49,3 +53,105 @@ pub fn generate_api_key() -> String {
     let _ = rng.try_fill_bytes(&mut key);
     hex::encode(key)
 }
+
+/// Split a `?fields=` value and check every field against the selectable ones.
+pub fn parse_fields(fields: &str, allowed: &[&str]) -> Result<Vec<String>, String> {
+    fields
+        .split(',')
+        .map(|field| field.trim())
+        .filter(|field| !field.is_empty() && *field != "_id")
+        .map(|field| {
+            if allowed.contains(&field) {
+                Ok(field.to_string())
+            } else {
+                Err(format!("Unknown field {field}"))
+            }
+        })
+        .collect()
+}
+
+/// Split a comma separated query value, e.g. `?stack=rust,react`.
+pub fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| item.to_string())
+        .collect()
+}
+
+/// Parse a list of ids, invalid ones are skipped.
+pub fn parse_oids(ids: Option<&[String]>) -> Vec<ObjectId> {
+    ids.unwrap_or_default()
+        .iter()
+        .filter_map(|id| ObjectId::parse_str(id).ok())
+        .collect()
+}
+
+/// Projection keeping `_id` and the given fields.
+pub fn fields_projection(fields: &[String]) -> Document {
+    let mut projection = Document::new();
+    projection.insert("_id", 1);
+    for field in fields {
+        projection.insert(field, 1);
+    }
+    projection
+}
+
+/// Convert a (projected) document into the same JSON shape as the DTOs,
+/// i.e. with ObjectIds and dates as strings.
+pub fn document_to_json(document: Document) -> Map<String, Value> {
+    document
+        .into_iter()
+        .map(|(key, value)| (key, bson_to_json(value)))
+        .collect()
+}
+
+fn bson_to_json(value: Bson) -> Value {
+    match value {
+        Bson::ObjectId(oid) => Value::String(oid.to_string()),
+        Bson::DateTime(datetime) => Value::String(datetime.to_chrono().to_string()),
+        Bson::Array(items) => Value::Array(items.into_iter().map(bson_to_json).collect()),
+        Bson::Document(document) => Value::Object(document_to_json(document)),
+        other => other.into_relaxed_extjson(),
+    }
+}
+
+/// Parse a date of a DTO, they carry `DateTime<Utc>::to_string()`, e.g. `2024-01-31 10:00:00.123 UTC`.
+pub fn parse_datetime(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.strip_suffix(" UTC")?;
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|datetime| datetime.and_utc())
+}
+
+/// Parse an optional RFC 3339 date sent in an input.
+pub fn parse_date(date: Option<&str>) -> mongodb::error::Result<Option<mongodb::bson::DateTime>> {
+    date.map(|date| {
+        mongodb::bson::DateTime::parse_rfc3339_str(date).map_err(|_| {
+            mongodb::bson::de::Error::custom(format!("Invalid date {date}, expected RFC 3339"))
+                .into()
+        })
+    })
+    .transpose()
+}
+
+/// Message of a template error, the causes hold the line of the template that failed.
+pub fn template_error(error: tera::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(&error);
+    while let Some(cause) = source {
+        message = format!("{message}: {cause}");
+        source = cause.source();
+    }
+    message
+}
+
+/// Whether the customer may see the operations of the whole server, listed in `ADMIN_CUSTOMER_IDS`.
+pub fn is_admin(customer_id: &str) -> bool {
+    std::env::var("ADMIN_CUSTOMER_IDS").is_ok_and(|ids| {
+        ids.split(',')
+            .map(str::trim)
+            .any(|id| !id.is_empty() && id == customer_id)
+    })
+}
@@ -0,0 +1,117 @@
+use crate::models::{customer::Customer, experience::Experience, social::Social};
+use crate::utils::util::parse_datetime;
+
+// Lines longer than this are folded, RFC 6350 3.2.
+const LINE_LENGTH: usize = 75;
+
+/// vCard 4.0 of the customer. `photo` is a url or a `data:` uri, the current position is the
+/// first experience without an end date.
+pub fn profile_vcard(
+    customer: &Customer,
+    experiences: &[Experience],
+    socials: &[Social],
+    photo: Option<&str>,
+) -> String {
+    let mut lines = vec!["BEGIN:VCARD".to_string(), "VERSION:4.0".to_string()];
+
+    let name = customer.name.trim();
+    lines.push(format!("FN:{}", escape(name)));
+    // Family names cannot be told apart reliably, a single name stays the given name.
+    let (given, family) = match name.rsplit_once(' ') {
+        Some((given, family)) => (given.trim(), family),
+        None => (name, ""),
+    };
+    lines.push(format!("N:{};{};;;", escape(family), escape(given)));
+
+    lines.push(format!("EMAIL:{}", escape(&customer.email)));
+    if let Some(phone) = customer.phone.as_deref().filter(|phone| !phone.is_empty()) {
+        let number: String = phone
+            .chars()
+            .filter(|c| c.is_ascii_digit() || *c == '+')
+            .collect();
+        lines.push(format!("TEL;VALUE=uri;TYPE=cell:tel:{number}"));
+    }
+
+    if let Some(current) = experiences
+        .iter()
+        .find(|experience| experience.end_date.is_none())
+    {
+        lines.push(format!("TITLE:{}", escape(&current.position)));
+        lines.push(format!("ORG:{}", escape(&current.company)));
+    }
+    if let Some(intro) = customer.intro.as_deref().filter(|intro| !intro.is_empty()) {
+        lines.push(format!("NOTE:{}", escape(intro)));
+    }
+    if let Some(photo) = photo {
+        lines.push(format!("PHOTO:{photo}"));
+    }
+
+    if let Some(site_url) = customer.site_url.as_deref().filter(|url| !url.is_empty()) {
+        lines.push(format!("URL;PREF=1:{site_url}"));
+    }
+    if let Some(wa_link) = customer.wa_link.as_deref().filter(|link| !link.is_empty()) {
+        lines.push(format!("URL;TYPE=whatsapp:{wa_link}"));
+    }
+    for social in socials {
+        lines.push(format!(
+            "URL;TYPE={}:{}",
+            type_param(&social.name),
+            social.link
+        ));
+    }
+
+    if let Some(updated) = customer.updated_at.as_deref().and_then(parse_datetime) {
+        lines.push(format!("REV:{}", updated.format("%Y%m%dT%H%M%SZ")));
+    }
+    lines.push("END:VCARD".to_string());
+
+    let mut card = String::new();
+    for line in lines {
+        fold(&line, &mut card);
+    }
+    card
+}
+
+// Text values escape their separators, RFC 6350 3.4.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ',' => escaped.push_str("\\,"),
+            ';' => escaped.push_str("\\;"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// `GitHub` becomes `github`, parameter values cannot hold spaces or separators.
+fn type_param(name: &str) -> String {
+    let value: String = name
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_lowercase();
+    if value.is_empty() {
+        "x-social".to_string()
+    } else {
+        value
+    }
+}
+
+// Lines end with CRLF and are cut every 75 octets, continuations start with a space.
+fn fold(line: &str, card: &mut String) {
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > LINE_LENGTH {
+            card.push_str("\r\n ");
+            length = 1;
+        }
+        card.push(c);
+        length += c.len_utf8();
+    }
+    card.push_str("\r\n");
+}